@@ -30,13 +30,14 @@ use servlin::{print_log_response, socket_addr_127_0_0_1, HttpServerBuilder, Requ
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-struct SessionState {}
+pub struct SessionState {}
 
-struct ServerState {
+pub struct ServerState {
     displayed_string: Roster<String, SessionState>,
     sessions: SessionSet<SessionState>,
 }
 impl ServerState {
+    #[must_use]
     pub fn new(executor: &Arc<safina_executor::Executor>) -> Self {
         Self {
             displayed_string: Roster::new(String::new()).with_cleanup_task(executor),
@@ -45,7 +46,7 @@ impl ServerState {
     }
 }
 
-fn page_map(state: &Arc<ServerState>) -> PageMap<SessionState> {
+pub fn page_map(state: &Arc<ServerState>) -> PageMap<SessionState> {
     let mut keys = PageMap::new();
     let state_clone = state.clone();
     keys.add_page_fn("/", move |rebuilder| {
@@ -1,4 +1,4 @@
-use crate::Session;
+use super::Session;
 use applin::action::{nothing, push};
 use applin::session::{PageKey, PageMap};
 use applin::widget::{AlertModal, Form, FormButton, FormSection, NavPage, Scroll, Text};
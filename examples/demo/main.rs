@@ -13,8 +13,10 @@ mod updates;
 mod vars;
 mod widgets;
 
-use applin::action::{choose_photo, push};
+use applin::action::{choose_photo, push, take_photo};
 use applin::data::Roster;
+use applin::image_store::{ImageId, ImageStore};
+use applin::lazy_list::LazyListLoader;
 use applin::session::{ApplinSession, PageMap, SessionSet};
 use applin::widget::{Column, FormSection, NavButton, NavPage, Scroll};
 use core::fmt::Debug;
@@ -28,36 +30,57 @@ use temp_dir::TempDir;
 
 pub const CHECK_VARS_RPC_PATH: &str = "/check-vars-rpc";
 pub const ERROR_RPC_PATH: &str = "/error";
-pub const GET_PHOTO_PATH: &str = "/get-photo";
+pub const IMAGES_PATH: &str = "/images";
+pub const LAZY_LIST_PATH: &str = "/lazy-list/more";
 pub const OK_RPC_PATH: &str = "/ok";
+pub const PROGRESS_RPC_PATH: &str = "/progress";
+pub const PULL_TO_REFRESH_PATH: &str = "/pull-to-refresh";
+pub const TOAST_RPC_PATH: &str = "/toast";
+pub const UNDO_RPC_PATH: &str = "/undo";
 pub const UPLOAD_PHOTO_PATH: &str = "/upload-photo";
 
 #[derive(Debug)]
 pub struct Session {
-    photo: Roster<Option<(Vec<u8>, u32)>, Session>,
+    photo: Roster<Option<ImageId>, Session>,
+}
+impl Session {
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            photo: Roster::new(None),
+        }
+    }
 }
 
 pub struct ServerState {
     clock_epoch_seconds: Roster<u64, Session>,
+    images: ImageStore,
+    lazy_list: LazyListLoader<Session>,
     sessions: SessionSet<Session>,
 }
 impl ServerState {
     #[must_use]
-    pub fn new(executor: &Arc<safina_executor::Executor>) -> Self {
+    pub fn new(executor: &Arc<safina_executor::Executor>, images: ImageStore) -> Self {
         Self {
             clock_epoch_seconds: Roster::new(0),
+            images,
+            lazy_list: widgets::lazy_list_loader(),
             sessions: SessionSet::new(executor),
         }
     }
 }
 
-fn page_map(state: &Arc<ServerState>) -> PageMap<Session> {
+pub fn page_map(state: &Arc<ServerState>) -> PageMap<Session> {
     let mut keys = PageMap::new();
     // Pages
     let drawer_modal = pages::add_drawer_modal_page(&mut keys);
     let alert_modal = pages::add_alert_page(&drawer_modal, &mut keys);
     let nav_page = pages::add_nav_page(&mut keys);
     let plain_page = pages::add_plain_page(&mut keys);
+    let sheet_modal = pages::add_sheet_modal_page(&mut keys);
+    let tab_page = pages::add_tab_page(&mut keys);
+    let toast_page = pages::add_toast_page(&mut keys);
     // Widgets
     let back_buttons_page = widgets::add_back_button_pages(&mut keys);
     let buttons_page = widgets::add_button_page(&mut keys);
@@ -68,13 +91,19 @@ fn page_map(state: &Arc<ServerState>) -> PageMap<Session> {
     let form_section_page = form_widgets::add_form_section_page(&mut keys);
     let grouped_row_table_page = widgets::add_grouped_row_table_page(&mut keys);
     let image_page = widgets::add_image_page(&mut keys);
+    let layout_page = widgets::add_layout_page(&mut keys);
+    let lazy_list_page = widgets::add_lazy_list_page(state, &mut keys);
+    let rich_text_page = widgets::add_rich_text_page(&mut keys);
+    let styles_page = widgets::add_styles_page(&mut keys);
     let text_page = widgets::add_text_page(&mut keys);
     let textfield_page = widgets::add_textfield_page(&mut keys);
     // Photos
-    let view_photo_page = photos::add_view_photo_page(&mut keys);
+    let view_photo_page = photos::add_view_photo_page(state, &mut keys);
     // Update Modes
     let inert_page = updates::add_inert_page(state, &mut keys);
     let poll_page = updates::add_poll_page(state, &mut keys);
+    let progress_page = updates::add_progress_page(&mut keys);
+    let pull_to_refresh_page = updates::add_pull_to_refresh_page(&mut keys);
     let stream_page = updates::add_stream_page(state, &mut keys);
     // Vars
     let check_vars_page = vars::add_check_vars_page(&mut keys);
@@ -88,6 +117,9 @@ fn page_map(state: &Arc<ServerState>) -> PageMap<Session> {
                     NavButton::new("Drawer Modal").with_action(push(&drawer_modal)),
                     NavButton::new("Nav Page").with_action(push(&nav_page)),
                     NavButton::new("Plain Page").with_action(push(&plain_page)),
+                    NavButton::new("Sheet Modal").with_action(push(&sheet_modal)),
+                    NavButton::new("Tab Page").with_action(push(&tab_page)),
+                    NavButton::new("Toast").with_action(push(&toast_page)),
                 )),
                 FormSection::new().with_title("Widgets").with_widgets((
                     NavButton::new("Back Button").with_action(push(&back_buttons_page)),
@@ -99,21 +131,27 @@ fn page_map(state: &Arc<ServerState>) -> PageMap<Session> {
                     NavButton::new("Form Section").with_action(push(&form_section_page)),
                     NavButton::new("Grouped Row Table").with_action(push(&grouped_row_table_page)),
                     NavButton::new("Image").with_action(push(&image_page)),
+                    NavButton::new("Layout").with_action(push(&layout_page)),
+                    NavButton::new("Lazy List").with_action(push(&lazy_list_page)),
+                    NavButton::new("Rich Text").with_action(push(&rich_text_page)),
+                    NavButton::new("Styles").with_action(push(&styles_page)),
                     NavButton::new("Text").with_action(push(&text_page)),
                     NavButton::new("Textfield").with_action(push(&textfield_page)),
                 )),
                 FormSection::new().with_title("Photos").with_widgets((
                     NavButton::new("Choose Photo")
-                        .with_action(choose_photo("/upload-photo"))
+                        .with_action(choose_photo(UPLOAD_PHOTO_PATH))
                         .with_action(push(&view_photo_page)),
                     NavButton::new("Take Photo")
-                        .with_action(choose_photo("/upload-photo"))
+                        .with_action(take_photo(UPLOAD_PHOTO_PATH))
                         .with_action(push(&view_photo_page)),
                     NavButton::new("View Photo").with_action(push(&view_photo_page)),
                 )),
                 FormSection::new().with_title("Update Modes").with_widgets((
                     NavButton::new("Inert").with_action(push(&inert_page)),
                     NavButton::new("Poll").with_action(push(&poll_page)),
+                    NavButton::new("Progress").with_action(push(&progress_page)),
+                    NavButton::new("Pull to Refresh").with_action(push(&pull_to_refresh_page)),
                     NavButton::new("Stream").with_action(push(&stream_page)),
                 )),
                 FormSection::new().with_title("Vars").with_widgets((
@@ -134,9 +172,7 @@ fn get_or_new_session(
     state.sessions.get_or_new(
         req,
         move |_rebuilder| Ok(page_map(&state_clone)),
-        || Session {
-            photo: Roster::new(None),
-        },
+        Session::new,
     )
 }
 
@@ -154,9 +190,15 @@ fn handle_req(state: &Arc<ServerState>, req: &Request) -> Result<Response, Respo
         ("GET", "/stream") => get_or_new_session(state, req)?.stream(),
         ("POST", ERROR_RPC_PATH) => Err(Response::text(500, "error1")),
         ("POST", OK_RPC_PATH) => ok_rpc(state, req),
+        ("POST", PROGRESS_RPC_PATH) => updates::progress_rpc(state, req),
+        ("POST", PULL_TO_REFRESH_PATH) => updates::pull_to_refresh_rpc(state, req),
+        ("POST", "/refresh") => state.sessions.get(req)?.refresh_response(req),
+        ("POST", TOAST_RPC_PATH) => pages::toast_rpc(state, req),
+        ("POST", UNDO_RPC_PATH) => pages::undo_rpc(state, req),
         ("POST", CHECK_VARS_RPC_PATH) => vars::check_vars_rpc(state, req),
-        (_, GET_PHOTO_PATH) => photos::get_photo_handler(state, req),
+        ("POST", LAZY_LIST_PATH) => state.lazy_list.handle(&state.sessions.get(req)?, req),
         (_, UPLOAD_PHOTO_PATH) => photos::upload_photo_handler(state, req),
+        (_, path) if path.starts_with(IMAGES_PATH) => state.images.handle(req),
         ("GET", "/placeholder-200x200.png") => Ok(Response::new(200)
             .with_type(ContentType::Png)
             .with_max_age_seconds(365 * 24 * 60 * 60)
@@ -172,7 +214,9 @@ pub fn main() {
     safina_timer::start_timer_thread();
     let executor = safina_executor::Executor::default();
     let cache_dir = TempDir::new().unwrap();
-    let state = Arc::new(ServerState::new(&executor));
+    let image_dir = TempDir::new().unwrap();
+    let images = ImageStore::new(image_dir.path(), IMAGES_PATH);
+    let state = Arc::new(ServerState::new(&executor, images));
     updates::start_updater_thread(state.clone());
     let request_handler = move |req: Request| print_log_response(&req, handle_req(&state, &req));
     executor
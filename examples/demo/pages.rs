@@ -1,9 +1,13 @@
-use crate::{Session, ERROR_RPC_PATH, OK_RPC_PATH};
+use super::{ServerState, Session, ERROR_RPC_PATH, OK_RPC_PATH, TOAST_RPC_PATH, UNDO_RPC_PATH};
 use applin::action::{pop, push, rpc};
+use applin::internal::{Tab, Toast};
 use applin::session::{PageKey, PageMap};
 use applin::widget::{
-    AlertModal, DrawerModal, Form, FormButton, ModalButton, NavPage, PlainPage, Text,
+    AlertModal, Column, DrawerModal, Form, FormButton, ModalButton, NavButton, NavPage, PlainPage,
+    SheetDetent, SheetModal, TabPage, Text,
 };
+use servlin::{Request, Response};
+use std::sync::Arc;
 
 pub fn add_alert_page(drawer: &PageKey, keys: &mut PageMap<Session>) -> PageKey {
     const KEY: &str = "/pages/alert";
@@ -80,3 +84,84 @@ pub fn add_plain_page(keys: &mut PageMap<Session>) -> PageKey {
         ),
     )
 }
+
+pub fn add_sheet_modal_page(keys: &mut PageMap<Session>) -> PageKey {
+    keys.add_static_page(
+        "/pages/sheet-modal",
+        SheetModal::new(
+            "Sheet Modal",
+            Form::new((
+                Text::new("Drag the sheet between its detents."),
+                FormButton::new("Close").with_action(pop()),
+            )),
+        )
+        .with_detent(SheetDetent::Medium)
+        .with_detent(SheetDetent::Large),
+    )
+}
+
+pub fn add_toast_page(keys: &mut PageMap<Session>) -> PageKey {
+    keys.add_static_page(
+        "/pages/toast",
+        NavPage::new(
+            "Toast",
+            Form::new((FormButton::new("Show Toast").with_action(rpc(TOAST_RPC_PATH)),)),
+        ),
+    )
+}
+
+#[allow(clippy::missing_errors_doc)]
+pub fn toast_rpc(state: &Arc<ServerState>, req: &Request) -> Result<Response, Response> {
+    let session = state.sessions.get(req)?;
+    session.show_toast(
+        Toast::new("Deleted item").with_button("Undo", [rpc(UNDO_RPC_PATH)]),
+        session.rpc_context(),
+    );
+    session.rpc_response()
+}
+
+/// Shows the sheet from the RPC response, like a server that asks the user a follow-up question.
+#[allow(clippy::missing_errors_doc)]
+pub fn undo_rpc(state: &Arc<ServerState>, req: &Request) -> Result<Response, Response> {
+    let session = state.sessions.get(req)?;
+    session.show_toast(Toast::new("Restored item"), session.rpc_context());
+    session.show_page(&PageKey::new("/pages/sheet-modal"), session.rpc_context());
+    session.rpc_response()
+}
+
+pub fn add_tab_page(keys: &mut PageMap<Session>) -> PageKey {
+    let detail = keys.add_static_page(
+        "/pages/tab-page/detail",
+        NavPage::new(
+            "Detail",
+            Text::new("Switch tabs and come back.  This page stays."),
+        ),
+    );
+    let first = keys.add_static_page(
+        "/pages/tab-page/first",
+        NavPage::new(
+            "First Tab",
+            Column::new((
+                NavButton::new("Detail").with_action(push(&detail)),
+                Form::new(FormButton::new("Leave Tabs").with_action(pop())),
+            )),
+        )
+        .without_back(),
+    );
+    let second = keys.add_static_page(
+        "/pages/tab-page/second",
+        NavPage::new("Second Tab", Text::new("Hello")).without_back(),
+    );
+    keys.add_static_page(
+        "/pages/tab-page",
+        TabPage::new(
+            "Tab Page",
+            [
+                Tab::new("First", &first).with_icon("star"),
+                Tab::new("Second", &second)
+                    .with_icon("info")
+                    .with_badge_text("3"),
+            ],
+        ),
+    )
+}
@@ -1,7 +1,9 @@
-use crate::{ServerState, Session};
+use super::{ServerState, Session, PROGRESS_RPC_PATH, PULL_TO_REFRESH_PATH};
+use applin::action::{push, refresh, rpc_with_progress};
 use applin::data::Context;
 use applin::session::{PageKey, PageMap};
-use applin::widget::{Column, NavPage, Text};
+use applin::widget::{AlertModal, Button, Column, NavPage, ProgressBar, Scroll, Text};
+use servlin::{Request, Response};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
@@ -53,6 +55,37 @@ pub fn add_poll_page(state: &Arc<ServerState>, keys: &mut PageMap<Session>) -> P
     })
 }
 
+pub fn add_progress_page(keys: &mut PageMap<Session>) -> PageKey {
+    let done = keys.add_static_page("/updates/progress-done", AlertModal::new("Done").with_ok());
+    keys.add_static_page(
+        "/updates/progress",
+        NavPage::new(
+            "Progress",
+            Scroll::new(Column::new((
+                ProgressBar::new(),
+                ProgressBar::new().with_percent(30),
+                ProgressBar::new().with_percent(100).with_text("Uploaded"),
+                Button::new("Start Job")
+                    .with_action(rpc_with_progress(PROGRESS_RPC_PATH))
+                    .with_action(push(&done)),
+            ))),
+        )
+        .with_stream(),
+    )
+}
+
+/// Starts a job that takes a few seconds and reports its progress.
+pub fn progress_rpc(state: &Arc<ServerState>, req: &Request) -> Result<Response, Response> {
+    let session = state.sessions.get(req)?;
+    session.start_job(|job| {
+        for percent in (0..100).step_by(20) {
+            job.set_progress(percent, format!("Step {} of 5", percent / 20 + 1));
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        Ok(())
+    })
+}
+
 pub fn add_stream_page(state: &Arc<ServerState>, keys: &mut PageMap<Session>) -> PageKey {
     let state_clone = state.clone();
     keys.add_page_fn("/updates/stream", move |rebuilder| {
@@ -70,3 +103,26 @@ pub fn add_stream_page(state: &Arc<ServerState>, keys: &mut PageMap<Session>) ->
         .with_stream())
     })
 }
+
+pub fn add_pull_to_refresh_page(keys: &mut PageMap<Session>) -> PageKey {
+    let key = "/updates/pull-to-refresh";
+    keys.add_page_fn(key, move |_rebuilder| {
+        Ok(NavPage::new(
+            "Pull to Refresh",
+            Column::new((
+                // The page does not subscribe to the clock, so it changes only when refreshed.
+                Text::new(format!("epoch seconds: {}", epoch_seconds())),
+                Text::new("Pull down to refresh."),
+                Button::new("Refresh").with_action(refresh(key)),
+            )),
+        )
+        .with_pull_to_refresh(PULL_TO_REFRESH_PATH))
+    })
+}
+
+#[allow(clippy::missing_errors_doc)]
+pub fn pull_to_refresh_rpc(state: &Arc<ServerState>, req: &Request) -> Result<Response, Response> {
+    let session = state.sessions.get(req)?;
+    session.refresh(["/updates/pull-to-refresh"]);
+    session.rpc_response()
+}
@@ -1,4 +1,4 @@
-use crate::{ServerState, Session, CHECK_VARS_RPC_PATH};
+use super::{ServerState, Session, CHECK_VARS_RPC_PATH};
 use applin::session::{PageKey, PageMap};
 use applin::widget::{Checkbox, Form, NavPage};
 use serde::{Deserialize, Serialize};
@@ -1,11 +1,15 @@
-use crate::{Session, ERROR_RPC_PATH, OK_RPC_PATH};
-use applin::action::{nothing, pop, push, rpc};
-use applin::internal::ImageDisposition;
+use super::{ServerState, Session, ERROR_RPC_PATH, LAZY_LIST_PATH, OK_RPC_PATH};
+use applin::action::{confirm, copy_to_clipboard, nothing, pop, push, refresh, rpc, rpc_branch};
+use applin::internal::{Chunk, ImageDisposition, Widget};
+use applin::lazy_list::LazyListLoader;
 use applin::session::{PageKey, PageMap};
+use applin::style::{Color, Style, Theme, ThemeColor};
 use applin::widget::{
-    AlertModal, BackButton, Button, Checkbox, Column, Empty, ErrorText, Form, FormSection,
-    GroupedRowTable, Image, NavButton, NavPage, Scroll, Text, Textfield,
+    AlertModal, BackButton, Button, Checkbox, Column, Empty, ErrorText, Form, FormSection, Frame,
+    GroupedRowTable, HAlignment, Icon, Image, NavButton, NavPage, Padding, RichText, Row, Scroll,
+    Spacer, Span, Stack, Text, Textfield, VAlignment,
 };
+use std::sync::Arc;
 
 pub fn add_back_button_pages(keys: &mut PageMap<Session>) -> PageKey {
     let default = keys.add_static_page(
@@ -56,7 +60,7 @@ pub fn add_back_button_pages(keys: &mut PageMap<Session>) -> PageKey {
         "/back-button",
         NavPage::new(
             "Back Button",
-            Scroll::new(Form::new((
+            Scroll::new(Column::new((
                 NavButton::new("Default").with_action(push(&default)),
                 NavButton::new("Disabled").with_action(push(&disabled)),
                 NavButton::new("Missing").with_action(push(&missing)),
@@ -76,7 +80,7 @@ pub fn add_button_page(keys: &mut PageMap<Session>) -> PageKey {
         "/button",
         NavPage::new(
             "Button",
-            Scroll::new(Form::new((
+            Scroll::new(Column::new((
                 Button::new("Button").with_action(push(&pressed)),
                 Button::new(
                     "MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM MMMM",
@@ -89,6 +93,29 @@ pub fn add_button_page(keys: &mut PageMap<Session>) -> PageKey {
                 Button::new("").with_action(push(&pressed)),
                 Button::new("Disabled Button"),
                 Button::new("Does Nothing").with_action(nothing()),
+                Button::new("With Icon")
+                    .with_icon("star")
+                    .with_action(push(&pressed)),
+                Row::new((Icon::new("heart"), Icon::new("bell").with_size(32))),
+                Button::new("Confirm First").with_action(confirm(
+                    "Press the button?",
+                    "",
+                    [push(&pressed)],
+                )),
+                Button::new("RPC With Branches").with_action(rpc_branch(
+                    OK_RPC_PATH,
+                    [push(&pressed)],
+                    [copy_to_clipboard("RPC failed")],
+                )),
+                Button::new("Failing RPC With Branches").with_action(rpc_branch(
+                    ERROR_RPC_PATH,
+                    [push(&pressed)],
+                    [confirm(
+                        "RPC failed.  Try again?",
+                        "",
+                        [rpc(ERROR_RPC_PATH)],
+                    )],
+                )),
             ))),
         ),
     )
@@ -144,7 +171,7 @@ pub fn add_grouped_row_table_page(keys: &mut PageMap<Session>) -> PageKey {
         "/grouped-row-table",
         NavPage::new(
             "Grouped Row Table",
-            Scroll::new(Form::new((
+            Scroll::new(Column::new((
                 FormSection::new().with_title("Single Group").with_widget(
                     GroupedRowTable::new().with_spacing(8).with_row_group((
                         (Text::new("A1"), Text::new("B1"), Text::new("C1")),
@@ -162,7 +189,7 @@ pub fn add_grouped_row_table_page(keys: &mut PageMap<Session>) -> PageKey {
                             ))
                             .with_row_group((
                                 (Text::new("A3"), Text::new("B3"), Text::new("C3")),
-                                (Text::new("A4"), Text::new("B4")),
+                                (Text::new("A4"), Text::new("B4"), None),
                             )),
                     ),
                 FormSection::new()
@@ -251,7 +278,7 @@ pub fn add_image_page(keys: &mut PageMap<Session>) -> PageKey {
         "/image",
         NavPage::new(
             "Image",
-            Scroll::new(Form::new((
+            Scroll::new(Column::new((
                 Text::new("Fit"),
                 Image::new(2.0, "/placeholder-200x200.png"),
                 Text::new("Cover"),
@@ -262,6 +289,137 @@ pub fn add_image_page(keys: &mut PageMap<Session>) -> PageKey {
                     .with_disposition(ImageDisposition::Stretch),
                 Text::new("Not found"),
                 Image::new(4.0, "/nonexistent.png"),
+                Button::new("Refresh Images").with_action(refresh("/placeholder-200x200.png")),
+            ))),
+        ),
+    )
+}
+
+pub fn add_rich_text_page(keys: &mut PageMap<Session>) -> PageKey {
+    keys.add_static_page(
+        "/rich-text",
+        NavPage::new(
+            "Rich Text",
+            Scroll::new(
+                Column::new((
+                    RichText::new((
+                        "Plain, ",
+                        Span::new("bold").with_bold(),
+                        ", ",
+                        Span::new("italic").with_italic(),
+                        ", ",
+                        Span::new("monospace").with_monospace(),
+                        ", ",
+                        Span::new("struck").with_strikethrough(),
+                        ", and ",
+                        Span::new("accent").with_color("accent"),
+                        ".",
+                    )),
+                    RichText::from_markdown(
+                        "From **Markdown** with `code`, a [link](https://www.example.com/), \
+                         and a [page link](/styles).\n\n- One\n- Two",
+                    ),
+                ))
+                .with_spacing(8),
+            ),
+        ),
+    )
+}
+
+pub fn add_styles_page(keys: &mut PageMap<Session>) -> PageKey {
+    keys.add_static_page(
+        "/styles",
+        NavPage::new(
+            "Styles",
+            Scroll::new(
+                Column::new((
+                    Text::new("Title").with_style(Style::new().with_text_style("title")),
+                    Text::new("Caption in secondary text color").with_style(
+                        Style::new()
+                            .with_color("secondary-text")
+                            .with_text_style("caption"),
+                    ),
+                    Text::new("Card").with_style(
+                        Style::new()
+                            .with_background("surface")
+                            .with_corner_radius("large")
+                            .with_padding("large"),
+                    ),
+                    Button::new("Brand Button").with_style(
+                        Style::new()
+                            .with_background("brand")
+                            .with_color("#ffffff")
+                            .with_corner_radius("medium"),
+                    ),
+                ))
+                .with_spacing(8),
+            ),
+        )
+        .with_theme(Theme::empty().with_color(
+            "brand",
+            ThemeColor::new(Color::rgb(0x5e, 0x5c, 0xe6), Color::rgb(0x7d, 0x7a, 0xff)),
+        )),
+    )
+}
+
+const LAZY_LIST_ROWS: usize = 1000;
+
+fn lazy_list_chunk(start: usize) -> Chunk {
+    let end = (start + 30).min(LAZY_LIST_ROWS);
+    let chunk = Chunk::new(
+        (start..end)
+            .map(|n| Text::new(format!("Row {n}")).into())
+            .collect::<Vec<Widget>>(),
+    );
+    if end < LAZY_LIST_ROWS {
+        chunk.with_next_cursor(end.to_string())
+    } else {
+        chunk
+    }
+}
+
+pub fn lazy_list_loader() -> LazyListLoader<Session> {
+    LazyListLoader::new(LAZY_LIST_PATH, |_session, cursor| {
+        Ok(lazy_list_chunk(cursor.parse()?))
+    })
+}
+
+pub fn add_lazy_list_page(state: &Arc<ServerState>, keys: &mut PageMap<Session>) -> PageKey {
+    keys.add_static_page(
+        "/lazy-list",
+        NavPage::new(
+            "Lazy List",
+            Scroll::new(state.lazy_list.list(lazy_list_chunk(0))),
+        ),
+    )
+}
+
+pub fn add_layout_page(keys: &mut PageMap<Session>) -> PageKey {
+    keys.add_static_page(
+        "/layout",
+        NavPage::new(
+            "Layout",
+            Scroll::new(Column::new((
+                Text::new("Row"),
+                Row::new((Text::new("Left"), Spacer::new(), Text::new("Right"))),
+                Text::new("Row with weights"),
+                Row::new((
+                    Frame::new(Button::new("One")).with_weight(1),
+                    Frame::new(Button::new("Two")).with_weight(2),
+                ))
+                .with_spacing(8),
+                Text::new("Padding"),
+                Padding::new(16, Text::new("Padded text")),
+                Text::new("Frame"),
+                Frame::new(Image::new(1.0, "/placeholder-200x200.png"))
+                    .with_width(100)
+                    .with_height(100),
+                Text::new("Stack"),
+                Stack::new((
+                    Image::new(2.0, "/placeholder-200x200.png"),
+                    Padding::new(8, Text::new("Caption")),
+                ))
+                .with_alignment(HAlignment::End, VAlignment::Bottom),
             ))),
         ),
     )
@@ -278,7 +436,7 @@ pub fn add_nav_button_page(keys: &mut PageMap<Session>) -> PageKey {
         "/nav-button",
         NavPage::new(
             "Nav Button",
-            Scroll::new(Form::new((
+            Scroll::new(Column::new((
                 FormSection::new().with_title("Text").with_widgets((
                     NavButton::new("Text").with_action(push(&pressed)),
                     NavButton::new(
@@ -29,7 +29,7 @@
 use applin::action::push;
 use applin::data::{Context, Rebuilder, Roster};
 use applin::session::{ApplinSession, PageKey, PageMap, SessionSet};
-use applin::widget::{Column, Empty, FormButton, NavPage, Text};
+use applin::widget::{Button, Column, Empty, NavPage, Text};
 use servlin::reexport::permit::Permit;
 use servlin::reexport::{safina_executor, safina_timer};
 use servlin::{print_log_response, socket_addr_127_0_0_1, HttpServerBuilder, Request, Response};
@@ -38,13 +38,14 @@ use std::ops::BitXorAssign;
 use std::sync::Arc;
 use std::time::Duration;
 
-struct SessionState {}
+pub struct SessionState {}
 
-struct ServerState {
+pub struct ServerState {
     show_page_2: Roster<bool, SessionState>,
     sessions: SessionSet<SessionState>,
 }
 impl ServerState {
+    #[must_use]
     pub fn new(executor: &Arc<safina_executor::Executor>) -> Self {
         Self {
             show_page_2: Roster::new(false).with_cleanup_task(executor),
@@ -53,8 +54,8 @@ impl ServerState {
     }
 }
 
-#[allow(clippy::unnecessary_wraps)]
-fn page_map(
+#[allow(clippy::missing_errors_doc, clippy::unnecessary_wraps)]
+pub fn page_map(
     state: &Arc<ServerState>,
     rebuilder: Rebuilder<SessionState>,
 ) -> Result<PageMap<SessionState>, Box<dyn Error>> {
@@ -75,7 +76,7 @@ fn page_map(
             Column::new((
                 Text::new("The page below appears and disappears every 5 seconds:"),
                 if show_page_2 {
-                    FormButton::new("Page 2")
+                    Button::new("Page 2")
                         .with_action(push(&PageKey::new(PAGE_2)))
                         .to_widget()
                 } else {
@@ -25,26 +25,29 @@ use applin::session::{PageMap, SessionSet};
 use applin::widget::{NavPage, Text};
 use servlin::reexport::{safina_executor, safina_timer};
 use servlin::{socket_addr_127_0_0_1, HttpServerBuilder, Request, Response};
+use std::error::Error;
 use std::sync::Arc;
 
+#[allow(clippy::missing_errors_doc, clippy::unnecessary_wraps)]
+pub fn page_map(_rebuilder: Rebuilder<()>) -> Result<PageMap<()>, Box<dyn Error>> {
+    Ok(PageMap::new().with_static_page(
+        "/",
+        NavPage::new("Minimal Example", Text::new("Hello")).with_poll(10),
+    ))
+}
+
 pub fn main() {
     println!("Access the app with an Applin client at http://127.0.0.1:8000/");
     safina_timer::start_timer_thread();
     let executor = safina_executor::Executor::default();
     let sessions: Arc<SessionSet<()>> = Arc::new(SessionSet::new(&executor));
-    let page_map_fn = move |_rebuilder: Rebuilder<()>| {
-        Ok(PageMap::new().with_static_page(
-            "/",
-            NavPage::new("Minimal Example", Text::new("Hello")).with_poll(10),
-        ))
-    };
     let session_state_fn = move || ();
     let req_handler = move |req: Request| match (req.method(), req.url().path()) {
         ("GET", "/") => sessions
-            .get_or_new(&req, page_map_fn, session_state_fn)?
+            .get_or_new(&req, page_map, session_state_fn)?
             .poll(),
         ("GET", "/stream") => sessions
-            .get_or_new(&req, page_map_fn, session_state_fn)?
+            .get_or_new(&req, page_map, session_state_fn)?
             .stream(),
         _ => Ok(Response::text(404, "Not found")),
     };
@@ -47,19 +47,20 @@
 use applin::action::rpc;
 use applin::data::Roster;
 use applin::session::{ApplinSession, PageMap, SessionSet};
-use applin::widget::{Column, FormButton, NavPage, Text};
+use applin::widget::{Button, Column, NavPage, Text};
 use servlin::reexport::{safina_executor, safina_timer};
 use servlin::{print_log_response, socket_addr_127_0_0_1, HttpServerBuilder, Request, Response};
 use std::ops::AddAssign;
 use std::sync::Arc;
 
-struct SessionState {}
+pub struct SessionState {}
 
-struct ServerState {
+pub struct ServerState {
     counter: Roster<u64, SessionState>,
     sessions: SessionSet<SessionState>,
 }
 impl ServerState {
+    #[must_use]
     pub fn new(executor: &Arc<safina_executor::Executor>) -> Self {
         Self {
             counter: Roster::new(0).with_cleanup_task(executor),
@@ -68,7 +69,7 @@ impl ServerState {
     }
 }
 
-fn page_map(state: &Arc<ServerState>) -> PageMap<SessionState> {
+pub fn page_map(state: &Arc<ServerState>) -> PageMap<SessionState> {
     let mut keys = PageMap::new();
     let state_clone = state.clone();
     keys.add_page_fn("/", move |rebuilder| {
@@ -82,7 +83,7 @@ fn page_map(state: &Arc<ServerState>) -> PageMap<SessionState> {
                     // and pushes it to the client.
                     *state_clone.counter.read(rebuilder)
                 )),
-                FormButton::new("Increment").with_action(rpc("/increment")),
+                Button::new("Increment").with_action(rpc("/increment")),
             )),
         )
         .with_stream())
@@ -39,8 +39,9 @@ use std::ops::AddAssign;
 use std::sync::Arc;
 
 #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct UserId(pub i64);
+pub struct UserId(pub i64);
 impl UserId {
+    #[must_use]
     pub fn new_random() -> Self {
         Self(random_positive_nonzero_i64())
     }
@@ -52,7 +53,7 @@ impl Debug for UserId {
 }
 
 #[derive(Debug)]
-struct SessionState {
+pub struct SessionState {
     #[allow(dead_code)]
     pub user_id: UserId,
     pub count: Roster<i64, Self>,
@@ -67,10 +68,11 @@ impl SessionState {
     }
 }
 
-struct ServerState {
+pub struct ServerState {
     sessions: SessionSet<SessionState>,
 }
 impl ServerState {
+    #[must_use]
     pub fn new(executor: &Arc<safina_executor::Executor>) -> Self {
         Self {
             sessions: SessionSet::new(executor),
@@ -78,7 +80,8 @@ impl ServerState {
     }
 }
 
-fn page_map(_state: &Arc<ServerState>) -> PageMap<SessionState> {
+#[must_use]
+pub fn page_map(_state: &Arc<ServerState>) -> PageMap<SessionState> {
     let mut keys = PageMap::new();
     keys.add_page_fn("/", move |rebuilder: Rebuilder<SessionState>| {
         let session = rebuilder.session()?;
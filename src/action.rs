@@ -1,11 +1,46 @@
 use crate::internal::Action;
 use crate::session::PageKey;
 
+/// Lets the user pick a file and uploads it to `upload_url`.
+/// Clients accept files with one of the MIME types in `accepted_types`, like `application/pdf`
+/// or `image/*`.  An empty list accepts all files.
+/// Clients reject files larger than `max_bytes`.
+///
+/// Use [`crate::upload::UploadHandler`] to receive the file.
+#[must_use]
+pub fn choose_file<S: Into<String>>(
+    upload_url: impl Into<String>,
+    accepted_types: impl IntoIterator<Item = S>,
+    max_bytes: u64,
+) -> Action {
+    Action::ChooseFile {
+        accepted_types: accepted_types.into_iter().map(Into::into).collect(),
+        max_bytes,
+        upload_url: upload_url.into(),
+    }
+}
+
 #[must_use]
 pub fn choose_photo(upload_url: impl Into<String>) -> Action {
     Action::ChoosePhoto(upload_url.into())
 }
 
+/// Asks the user to confirm, then performs `then` and the remaining actions.
+/// When the user cancels, the client skips the remaining actions.
+/// An empty `text` shows only the title.
+#[must_use]
+pub fn confirm(
+    title: impl Into<String>,
+    text: impl Into<String>,
+    then: impl IntoIterator<Item = Action>,
+) -> Action {
+    Action::Confirm {
+        text: text.into(),
+        then: then.into_iter().collect(),
+        title: title.into(),
+    }
+}
+
 #[must_use]
 pub fn copy_to_clipboard(s: impl Into<String>) -> Action {
     Action::CopyToClipboard(s.into())
@@ -36,11 +71,46 @@ pub fn pop() -> Action {
     Action::Pop
 }
 
+/// Fetches the page with key `page_key_or_image_url` again,
+/// or reloads the images with URL `page_key_or_image_url`, bypassing caches.
+///
+/// Clients fetch pages from the session's refresh path.
+/// See [`crate::session::ApplinSession::refresh_response`].
+#[must_use]
+pub fn refresh(page_key_or_image_url: impl Into<String>) -> Action {
+    Action::Refresh(page_key_or_image_url.into())
+}
+
 #[must_use]
 pub fn rpc(url: impl Into<String>) -> Action {
     Action::Rpc(url.into())
 }
 
+/// Calls the RPC at `url` and shows the progress of the job that it starts.
+/// The client performs the remaining actions after the job finishes without an error.
+///
+/// The RPC handler calls [`crate::session::ApplinSession::start_job`].
+#[must_use]
+pub fn rpc_with_progress(url: impl Into<String>) -> Action {
+    Action::RpcWithProgress(url.into())
+}
+
+/// Calls the RPC at `url`, then performs `on_success` or `on_error`.
+/// After an error, the client skips the remaining actions.
+/// When `on_error` is empty, the client shows the error like an [`rpc`] action.
+#[must_use]
+pub fn rpc_branch(
+    url: impl Into<String>,
+    on_success: impl IntoIterator<Item = Action>,
+    on_error: impl IntoIterator<Item = Action>,
+) -> Action {
+    Action::RpcBranch {
+        on_error: on_error.into_iter().collect(),
+        on_success: on_success.into_iter().collect(),
+        url: url.into(),
+    }
+}
+
 #[must_use]
 pub fn take_photo(upload_url: impl Into<String>) -> Action {
     Action::TakePhoto(upload_url.into())
@@ -0,0 +1,106 @@
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+use servlin::ContentType;
+
+/// Image file types that [`crate::image_store::ImageStore`] accepts.
+/// It does not accept SVG files because they can contain scripts.
+pub const IMAGE_TYPES: [(&str, &str); 4] = [
+    ("gif", "image/gif"),
+    ("jpg", "image/jpeg"),
+    ("png", "image/png"),
+    ("webp", "image/webp"),
+];
+
+/// Identifies a stored image by the SHA-256 digest of its contents and its file extension,
+/// like `e3b0c442...b855.png`.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ImageId {
+    ext: &'static str,
+    sha256: String,
+}
+impl ImageId {
+    /// Makes an id for an image with `content_type`, like `image/png`.
+    ///
+    /// # Errors
+    /// Returns an error when `sha256` is not 64 lowercase hex digits
+    /// or `content_type` is not an image type.
+    pub fn new(sha256: impl Into<String>, content_type: &str) -> Result<Self, String> {
+        let sha256 = sha256.into();
+        check_sha256(&sha256)?;
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let ext = IMAGE_TYPES
+            .iter()
+            .find(|(_, t)| *t == mime)
+            .map(|(ext, _)| *ext)
+            .ok_or_else(|| format!("unsupported image type {content_type:?}"))?;
+        Ok(Self { ext, sha256 })
+    }
+
+    #[must_use]
+    pub fn ext(&self) -> &str {
+        self.ext
+    }
+
+    #[must_use]
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// The MIME type, like `image/png`.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn mime_type(&self) -> &'static str {
+        IMAGE_TYPES
+            .iter()
+            .find(|(ext, _)| *ext == self.ext)
+            .map(|(_, t)| *t)
+            .unwrap()
+    }
+
+    #[must_use]
+    pub fn content_type(&self) -> ContentType {
+        match self.ext {
+            "gif" => ContentType::Gif,
+            "jpg" => ContentType::Jpeg,
+            "png" => ContentType::Png,
+            _ => ContentType::String(self.mime_type().to_string()),
+        }
+    }
+}
+impl Display for ImageId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{}.{}", self.sha256, self.ext)
+    }
+}
+impl FromStr for ImageId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sha256, ext) = s
+            .split_once('.')
+            .ok_or_else(|| format!("malformed image id {s:?}"))?;
+        check_sha256(sha256)?;
+        let ext = IMAGE_TYPES
+            .iter()
+            .find(|(e, _)| *e == ext)
+            .map(|(e, _)| *e)
+            .ok_or_else(|| format!("malformed image id {s:?}"))?;
+        Ok(Self {
+            ext,
+            sha256: sha256.to_string(),
+        })
+    }
+}
+
+fn check_sha256(s: &str) -> Result<(), String> {
+    if s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        Ok(())
+    } else {
+        Err(format!("malformed sha256 {s:?}"))
+    }
+}
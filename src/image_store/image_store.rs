@@ -0,0 +1,388 @@
+use crate::data::random_positive_nonzero_i64;
+use crate::error::server_error;
+use crate::image_store::{ImageId, IMAGE_TYPES};
+use crate::upload::{Upload, UploadHandler};
+use crate::widget::NAV_BUTTON_PHOTO_SIZE;
+use core::fmt::{Debug, Formatter};
+use servlin::{AsciiString, Request, Response};
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The largest width or height of a thumbnail, in pixels.
+pub const MAX_THUMBNAIL_SIZE: u32 = 4096;
+
+/// Image URLs never change, so clients may cache them for a year.
+const MAX_AGE_SECONDS: u32 = 365 * 24 * 60 * 60;
+
+/// Makes a thumbnail.
+/// Receives the image bytes, the MIME type, and the width and height in pixels.
+/// Returns the thumbnail bytes, with the same MIME type.
+#[allow(clippy::module_name_repetitions)]
+pub type ResizeFn =
+    dyn 'static + Send + Sync + Fn(&[u8], &str, u32, u32) -> Result<Vec<u8>, String>;
+
+fn parse_size(s: &str) -> Option<(u32, u32)> {
+    let (w, h) = s.split_once('x')?;
+    let w: u32 = w.parse().ok().filter(|n: &u32| n.to_string() == w)?;
+    let h: u32 = h.parse().ok().filter(|n: &u32| n.to_string() == h)?;
+    Some((w, h))
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, Response> {
+    std::fs::read(path).map_err(|e| {
+        if e.kind() == ErrorKind::NotFound {
+            Response::not_found_404()
+        } else {
+            server_error(format!("error reading {}: {e}", path.display()))
+        }
+    })
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), Response> {
+    let temp_path = path.with_extension(format!("{}.tmp", random_positive_nonzero_i64()));
+    std::fs::write(&temp_path, bytes)
+        .and_then(|()| std::fs::rename(&temp_path, path))
+        .map_err(|e| {
+            let _ignored = std::fs::remove_file(&temp_path);
+            server_error(format!("error writing {}: {e}", path.display()))
+        })
+}
+
+/// Saves images in a directory and serves them with content-addressed URLs.
+///
+/// Each image's URL contains the SHA-256 digest of its contents,
+/// so a URL always refers to the same bytes.
+/// Clients may cache the images for a long time and they still see new images,
+/// because new images have new URLs.
+///
+/// Route GET requests for paths that start with the URL prefix to [`ImageStore::handle`].
+///
+/// ```no_run
+/// use applin::image_store::ImageStore;
+/// use applin::widget::{Image, NavButton};
+/// use servlin::{Request, Response};
+///
+/// let store = ImageStore::new("/var/app/images", "/images");
+/// let bytes = std::fs::read("photo.png").unwrap();
+/// let id = store.save_bytes("image/png", &bytes).ok().unwrap();
+/// let image = Image::from_store(1.0, &store, &id);
+/// let button = NavButton::new("Photo").with_stored_photo(&store, &id);
+///
+/// fn handle_req(store: &ImageStore, req: &Request) -> Result<Response, Response> {
+///     if req.url.path().starts_with(store.url_prefix()) {
+///         return store.handle(req);
+///     }
+///     Ok(Response::not_found_404())
+/// }
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
+pub struct ImageStore {
+    dir: PathBuf,
+    max_bytes: u64,
+    resize_fn: Option<Arc<ResizeFn>>,
+    thumbnail_sizes: BTreeSet<(u32, u32)>,
+    url_prefix: String,
+}
+impl ImageStore {
+    /// Makes a store that keeps images in `dir` and serves them at URLs
+    /// that start with `url_prefix`, like `/images`.
+    /// It accepts images up to 10 MiB.
+    /// It serves thumbnails only with the [`crate::widget::NavButton::with_stored_photo`] size.
+    ///
+    /// # Panics
+    /// Panics when `url_prefix` does not start with `/` or ends with `/`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>, url_prefix: impl Into<String>) -> Self {
+        let url_prefix = url_prefix.into();
+        assert!(
+            url_prefix.starts_with('/') && !url_prefix.ends_with('/'),
+            "url_prefix must start with '/' and not end with '/'"
+        );
+        Self {
+            dir: dir.into(),
+            max_bytes: 10 * 1024 * 1024,
+            resize_fn: None,
+            thumbnail_sizes: [(NAV_BUTTON_PHOTO_SIZE, NAV_BUTTON_PHOTO_SIZE)].into(),
+            url_prefix,
+        }
+    }
+
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Makes thumbnails with `resize_fn`.
+    /// The store saves each thumbnail and serves it for later requests.
+    ///
+    /// Without a resizer, thumbnail URLs serve the original image.
+    #[must_use]
+    pub fn with_resizer(
+        mut self,
+        resize_fn: impl 'static + Send + Sync + Fn(&[u8], &str, u32, u32) -> Result<Vec<u8>, String>,
+    ) -> Self {
+        self.resize_fn = Some(Arc::new(resize_fn));
+        self
+    }
+
+    /// Serves thumbnails with these `(width, height)` sizes, too.
+    /// Requests for other sizes get 404 Not Found,
+    /// so clients cannot make the server resize and save images of every size.
+    ///
+    /// # Panics
+    /// Panics when a width or height is zero or larger than [`MAX_THUMBNAIL_SIZE`].
+    #[must_use]
+    pub fn with_thumbnail_sizes(mut self, sizes: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        for (width, height) in sizes {
+            assert!(
+                (1..=MAX_THUMBNAIL_SIZE).contains(&width)
+                    && (1..=MAX_THUMBNAIL_SIZE).contains(&height),
+                "thumbnail size must be from 1 to {MAX_THUMBNAIL_SIZE}"
+            );
+            self.thumbnail_sizes.insert((width, height));
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    #[must_use]
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    #[must_use]
+    pub fn url_prefix(&self) -> &str {
+        &self.url_prefix
+    }
+
+    #[must_use]
+    pub fn thumbnail_sizes(&self) -> &BTreeSet<(u32, u32)> {
+        &self.thumbnail_sizes
+    }
+
+    /// Makes a handler that accepts image uploads up to the store's maximum size.
+    #[must_use]
+    pub fn upload_handler(&self) -> UploadHandler {
+        UploadHandler::new(&self.dir)
+            .with_accepted_types(IMAGE_TYPES.iter().map(|(_, t)| *t))
+            .with_max_bytes(self.max_bytes)
+    }
+
+    fn thumbs_dir(&self) -> PathBuf {
+        self.dir.join("thumbs")
+    }
+
+    fn create_dirs(&self) -> Result<(), Response> {
+        std::fs::create_dir_all(self.thumbs_dir()).map_err(|e| {
+            server_error(format!(
+                "error creating image dir {}: {e}",
+                self.dir.display()
+            ))
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self, id: &ImageId) -> PathBuf {
+        self.dir.join(id.to_string())
+    }
+
+    fn thumbnail_path(&self, id: &ImageId, width: u32, height: u32) -> PathBuf {
+        self.thumbs_dir().join(format!("{width}x{height}-{id}"))
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &ImageId) -> bool {
+        self.path(id).is_file()
+    }
+
+    /// The URL of the original image.
+    #[must_use]
+    pub fn url(&self, id: &ImageId) -> String {
+        format!("{}/{id}", self.url_prefix)
+    }
+
+    /// The URL of the image resized to fit in `width` x `height` pixels.
+    ///
+    /// # Panics
+    /// Panics when the store does not serve the size.  See [`ImageStore::with_thumbnail_sizes`].
+    #[must_use]
+    pub fn thumbnail_url(&self, id: &ImageId, width: u32, height: u32) -> String {
+        assert!(
+            self.thumbnail_sizes.contains(&(width, height)),
+            "thumbnail size {width}x{height} is not in the store's thumbnail sizes"
+        );
+        format!("{}/{width}x{height}/{id}", self.url_prefix)
+    }
+
+    /// Keeps the uploaded image.
+    /// When the store already has an image with the same contents, it drops the upload.
+    ///
+    /// # Errors
+    /// Returns an error response when the upload is not an image or it fails to save the file.
+    pub fn save_upload(&self, upload: Upload) -> Result<ImageId, Response> {
+        let id = ImageId::new(upload.sha256(), upload.content_type())
+            .map_err(|e| Response::text(415, e))?;
+        self.create_dirs()?;
+        let path = self.path(&id);
+        if !path.is_file() {
+            upload
+                .persist(&path)
+                .map_err(|e| server_error(format!("error saving {}: {e}", path.display())))?;
+        }
+        Ok(id)
+    }
+
+    /// Saves an image.
+    ///
+    /// # Errors
+    /// Returns an error response when `content_type` is not an image type,
+    /// `bytes` is larger than the maximum, or it fails to save the file.
+    pub fn save_bytes(&self, content_type: &str, bytes: &[u8]) -> Result<ImageId, Response> {
+        self.create_dirs()?;
+        let upload = self.upload_handler().save(content_type, bytes)?;
+        self.save_upload(upload)
+    }
+
+    /// Receives an image that a client uploaded with [`crate::action::choose_photo`]
+    /// or [`crate::action::take_photo`].
+    ///
+    /// # Errors
+    /// Returns the errors from [`UploadHandler::handle`] and [`ImageStore::save_upload`].
+    pub fn receive(&self, req: &Request) -> Result<ImageId, Response> {
+        self.create_dirs()?;
+        let upload = self.upload_handler().handle(req)?;
+        self.save_upload(upload)
+    }
+
+    /// Deletes the image and its thumbnails.
+    /// Clients may still show the image from their caches.
+    ///
+    /// # Errors
+    /// Returns an error when it fails to delete a file.
+    pub fn delete(&self, id: &ImageId) -> Result<(), std::io::Error> {
+        let suffix = format!("-{id}");
+        match std::fs::read_dir(self.thumbs_dir()) {
+            Ok(entries) => {
+                for entry in entries {
+                    let entry = entry?;
+                    if entry.file_name().to_string_lossy().ends_with(&suffix) {
+                        std::fs::remove_file(entry.path())?;
+                    }
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        match std::fs::remove_file(self.path(id)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the thumbnail bytes, making and saving the thumbnail if needed.
+    ///
+    /// # Errors
+    /// Returns an error response when the store does not serve the size, the image is not found,
+    /// the resizer fails, or it fails to read or write a file.
+    pub fn thumbnail(&self, id: &ImageId, width: u32, height: u32) -> Result<Vec<u8>, Response> {
+        if !self.thumbnail_sizes.contains(&(width, height)) {
+            return Err(Response::not_found_404());
+        }
+        let Some(resize_fn) = &self.resize_fn else {
+            return read(&self.path(id));
+        };
+        let path = self.thumbnail_path(id, width, height);
+        match read(&path) {
+            Err(e) if e.code == 404 => {}
+            result => return result,
+        }
+        let original = read(&self.path(id))?;
+        let bytes = resize_fn(&original, id.mime_type(), width, height)
+            .map_err(|e| server_error(format!("error resizing image {id}: {e}")))?;
+        self.create_dirs()?;
+        write(&path, &bytes)?;
+        Ok(bytes)
+    }
+
+    /// Serves images and thumbnails.
+    /// Responses have an `ETag` header and let clients cache them for a year.
+    /// When the request has a matching `If-None-Match` header, it returns 304 Not Modified.
+    ///
+    /// # Errors
+    /// Returns an error response when the request is not a GET,
+    /// the path is not an image URL, the image is not found,
+    /// or it fails to make the thumbnail.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn handle(&self, req: &Request) -> Result<Response, Response> {
+        if req.method != "GET" {
+            return Err(Response::method_not_allowed_405(&["GET"]));
+        }
+        let rest = req
+            .url
+            .path()
+            .strip_prefix(&self.url_prefix)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .ok_or_else(Response::not_found_404)?;
+        let (size, id) = match rest.split_once('/') {
+            Some((size, id)) => (
+                Some(
+                    parse_size(size)
+                        .filter(|size| self.thumbnail_sizes.contains(size))
+                        .ok_or_else(Response::not_found_404)?,
+                ),
+                id,
+            ),
+            None => (None, rest),
+        };
+        let id: ImageId = id.parse().map_err(|_| Response::not_found_404())?;
+        let etag = match size {
+            Some((width, height)) => format!("\"{}-{width}x{height}\"", id.sha256()),
+            None => format!("\"{}\"", id.sha256()),
+        };
+        let not_modified = req
+            .headers
+            .get_only("if-none-match")
+            .map(AsciiString::as_str)
+            .is_some_and(|value| {
+                value
+                    .split(',')
+                    .map(|tag| tag.trim().trim_start_matches("W/"))
+                    .any(|tag| tag == "*" || tag == etag)
+            });
+        if not_modified && self.contains(&id) {
+            return Ok(Response::new(304)
+                .with_header("etag", AsciiString::try_from(etag).unwrap())
+                .with_max_age_seconds(MAX_AGE_SECONDS));
+        }
+        let bytes = match size {
+            Some((width, height)) => self.thumbnail(&id, width, height)?,
+            None => read(&self.path(&id))?,
+        };
+        Ok(Response::new(200)
+            .with_type(id.content_type())
+            .with_header("etag", AsciiString::try_from(etag).unwrap())
+            .with_max_age_seconds(MAX_AGE_SECONDS)
+            .with_body(bytes))
+    }
+}
+impl Debug for ImageStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "ImageStore{{dir={:?}, url_prefix={:?}, max_bytes={}, resizer={}, thumbnail_sizes={:?}}}",
+            self.dir,
+            self.url_prefix,
+            self.max_bytes,
+            self.resize_fn.is_some(),
+            self.thumbnail_sizes
+        )
+    }
+}
@@ -0,0 +1,7 @@
+//! Storing and serving images.
+mod image_id;
+#[allow(clippy::module_inception)]
+mod image_store;
+
+pub use image_id::*;
+pub use image_store::*;
@@ -1,10 +1,27 @@
-use serde::de::Visitor;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Action {
+    /// Lets the user pick a file and uploads it.
+    /// An empty `accepted_types` list accepts all files.
+    ChooseFile {
+        accepted_types: Vec<String>,
+        max_bytes: u64,
+        upload_url: String,
+    },
     ChoosePhoto(String),
+    /// Asks the user to confirm.
+    /// When they confirm, the client performs `then` and the remaining actions.
+    /// When they cancel, the client skips the remaining actions.
+    Confirm {
+        text: String,
+        then: Vec<Action>,
+        title: String,
+    },
     TakePhoto(String),
     CopyToClipboard(String),
     LaunchUrl(String),
@@ -12,7 +29,20 @@ pub enum Action {
     Nothing,
     Pop,
     Push(String),
+    /// Fetches the page with this key again, or reloads images with this URL.
+    Refresh(String),
     Rpc(String),
+    /// Calls the RPC, which starts a job, and shows the job's progress until it finishes.
+    /// See [`crate::session::ApplinSession::start_job`].
+    RpcWithProgress(String),
+    /// Calls the RPC and then performs `on_success` or `on_error`.
+    /// On error, the client skips the remaining actions.
+    /// When `on_error` is empty, the client shows the error like an `rpc` action.
+    RpcBranch {
+        on_error: Vec<Action>,
+        on_success: Vec<Action>,
+        url: String,
+    },
 }
 impl Action {
     #[must_use]
@@ -20,19 +50,107 @@ impl Action {
     pub fn to_value(&self) -> Value {
         serde_json::to_value(self).unwrap()
     }
+
+    /// Returns the first protocol version that supports this action.
+    #[must_use]
+    pub fn min_protocol_version(&self) -> u32 {
+        match self {
+            Action::ChoosePhoto(_)
+            | Action::CopyToClipboard(_)
+            | Action::LaunchUrl(_)
+            | Action::Logout
+            | Action::Nothing
+            | Action::Pop
+            | Action::Push(_)
+            | Action::Rpc(_)
+            | Action::TakePhoto(_) => 1,
+            Action::ChooseFile { .. }
+            | Action::Confirm { .. }
+            | Action::Refresh(_)
+            | Action::RpcBranch { .. }
+            | Action::RpcWithProgress(_) => 2,
+        }
+    }
+}
+
+/// Replaces `actions` with `nothing` when `protocol_version` does not support one of them.
+/// Skipping only the unsupported actions could, for example, delete something without confirming.
+pub fn downgrade_actions(actions: &mut Vec<Action>, protocol_version: u32) {
+    if actions
+        .iter()
+        .any(|action| protocol_version < action.min_protocol_version())
+    {
+        *actions = vec![Action::Nothing];
+    }
 }
 impl From<Action> for Value {
     fn from(src: Action) -> Self {
         src.to_value()
     }
 }
+/// The JSON object format of actions that contain other actions.
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "typ")]
+enum ActionObject {
+    #[serde(rename = "confirm")]
+    Confirm {
+        #[serde(default)]
+        text: String,
+        then: Vec<Action>,
+        title: String,
+    },
+    #[serde(rename = "rpc")]
+    Rpc {
+        #[serde(rename = "on-error", default)]
+        on_error: Vec<Action>,
+        #[serde(rename = "on-success", default)]
+        on_success: Vec<Action>,
+        url: String,
+    },
+}
+impl From<ActionObject> for Action {
+    fn from(src: ActionObject) -> Self {
+        match src {
+            ActionObject::Confirm { text, then, title } => Action::Confirm { text, then, title },
+            ActionObject::Rpc {
+                on_error,
+                on_success,
+                url,
+            } => Action::RpcBranch {
+                on_error,
+                on_success,
+                url,
+            },
+        }
+    }
+}
+
 impl Serialize for Action {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         match self {
+            Action::ChooseFile {
+                accepted_types,
+                max_bytes,
+                upload_url,
+            } => serializer.serialize_str(&format!(
+                "choose-file:{max_bytes}:{}:{upload_url}",
+                accepted_types.join(",")
+            )),
             Action::ChoosePhoto(s) => serializer.serialize_str(&format!("choose-photo:{s}")),
+            Action::Confirm { text, then, title } => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("typ", "confirm")?;
+                map.serialize_entry("title", title)?;
+                if !text.is_empty() {
+                    map.serialize_entry("text", text)?;
+                }
+                map.serialize_entry("then", then)?;
+                map.end()
+            }
             Action::CopyToClipboard(s) => {
                 serializer.serialize_str(&format!("copy-to-clipboard:{s}"))
             }
@@ -41,7 +159,27 @@ impl Serialize for Action {
             Action::Nothing => serializer.serialize_str("nothing"),
             Action::Pop => serializer.serialize_str("pop"),
             Action::Push(s) => serializer.serialize_str(&format!("push:{s}")),
+            Action::Refresh(s) => serializer.serialize_str(&format!("refresh:{s}")),
             Action::Rpc(s) => serializer.serialize_str(&format!("rpc:{s}")),
+            Action::RpcWithProgress(s) => {
+                serializer.serialize_str(&format!("rpc-with-progress:{s}"))
+            }
+            Action::RpcBranch {
+                on_error,
+                on_success,
+                url,
+            } => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("typ", "rpc")?;
+                map.serialize_entry("url", url)?;
+                if !on_success.is_empty() {
+                    map.serialize_entry("on-success", on_success)?;
+                }
+                if !on_error.is_empty() {
+                    map.serialize_entry("on-error", on_error)?;
+                }
+                map.end()
+            }
             Action::TakePhoto(s) => serializer.serialize_str(&format!("take-photo:{s}")),
         }
     }
@@ -59,12 +197,34 @@ impl<'de> Deserialize<'de> for Action {
                 &self,
                 formatter: &mut core::fmt::Formatter,
             ) -> Result<(), core::fmt::Error> {
-                formatter.write_str("a string matching the action string format")
+                formatter
+                    .write_str("a string matching the action string format or an object with a typ")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                ActionObject::deserialize(MapAccessDeserializer::new(map)).map(Action::from)
             }
 
             fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
                 let mut parts = value.splitn(2, ':');
                 match (parts.next(), parts.next()) {
+                    (Some("choose-file"), Some(s)) => {
+                        let mut args = s.splitn(3, ':');
+                        match (args.next().map(str::parse), args.next(), args.next()) {
+                            (Some(Ok(max_bytes)), Some(types), Some(url)) if !url.is_empty() => {
+                                Ok(Action::ChooseFile {
+                                    accepted_types: types
+                                        .split(',')
+                                        .filter(|t| !t.is_empty())
+                                        .map(ToString::to_string)
+                                        .collect(),
+                                    max_bytes,
+                                    upload_url: url.to_string(),
+                                })
+                            }
+                            _ => Err(E::custom(format!("invalid action: {value:?}"))),
+                        }
+                    }
                     (Some("choose-photo"), Some(s)) if !s.is_empty() => {
                         Ok(Action::ChoosePhoto(s.to_string()))
                     }
@@ -78,7 +238,13 @@ impl<'de> Deserialize<'de> for Action {
                     (Some("nothing"), None) => Ok(Action::Nothing),
                     (Some("pop"), None) => Ok(Action::Pop),
                     (Some("push"), Some(s)) if !s.is_empty() => Ok(Action::Push(s.to_string())),
+                    (Some("refresh"), Some(s)) if !s.is_empty() => {
+                        Ok(Action::Refresh(s.to_string()))
+                    }
                     (Some("rpc"), Some(s)) if !s.is_empty() => Ok(Action::Rpc(s.to_string())),
+                    (Some("rpc-with-progress"), Some(s)) if !s.is_empty() => {
+                        Ok(Action::RpcWithProgress(s.to_string()))
+                    }
                     (Some("take-photo"), Some(s)) if !s.is_empty() => {
                         Ok(Action::TakePhoto(s.to_string()))
                     }
@@ -86,6 +252,40 @@ impl<'de> Deserialize<'de> for Action {
                 }
             }
         }
-        deserializer.deserialize_str(ActionVisitor {})
+        deserializer.deserialize_any(ActionVisitor {})
+    }
+}
+
+/// Regex that matches every string in the action string format.
+#[cfg(feature = "schema")]
+const ACTION_PATTERN: &str = "^(choose-file:[0-9]+:[^:]*:.+|choose-photo:.+|copy-to-clipboard:[\\s\\S]*|launch-url:.+|logout|nothing|pop|push:.+|refresh:.+|rpc:.+|rpc-with-progress:.+|take-photo:.+)$";
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Action {
+    fn schema_name() -> String {
+        "Action".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, SchemaObject, StringValidation, SubschemaValidation};
+        let string_schema = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some(ACTION_PATTERN.to_string()),
+                ..StringValidation::default()
+            })),
+            ..SchemaObject::default()
+        };
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    string_schema.into(),
+                    gen.subschema_for::<ActionObject>(),
+                ]),
+                ..SubschemaValidation::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
     }
 }
@@ -0,0 +1,44 @@
+use crate::internal::{Widget, WidgetList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Some rows of a `lazy-list` widget.
+/// This is the body of the response to the client's request for more rows.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Chunk {
+    /// The client sends this to get the next chunk.  `None` means the list has no more rows.
+    #[serde(rename = "next-cursor")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub widgets: Vec<Widget>,
+}
+impl Chunk {
+    /// Makes a chunk that ends the list.
+    #[must_use]
+    pub fn new(widgets: impl Into<WidgetList>) -> Self {
+        Self {
+            next_cursor: None,
+            widgets: widgets.into().0,
+        }
+    }
+
+    /// Makes the client ask for more rows with `cursor` when the user scrolls to the end.
+    #[must_use]
+    pub fn with_next_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.next_cursor = Some(cursor.into());
+        self
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+impl From<Chunk> for Value {
+    fn from(src: Chunk) -> Self {
+        src.to_value()
+    }
+}
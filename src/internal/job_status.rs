@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The progress of a long-running RPC.
+/// See [`crate::session::ApplinSession::start_job`].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct JobStatus {
+    /// True when the job finished.  The client stops showing its progress
+    /// and performs the rest of the actions, unless the job has an error.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub done: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub id: String,
+    /// `None` shows an indeterminate progress bar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent: Option<u8>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+}
+impl JobStatus {
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            done: false,
+            error: None,
+            id: id.into(),
+            percent: None,
+            text: String::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_percent(mut self, percent: u8) -> Self {
+        self.percent = Some(percent);
+        self
+    }
+
+    #[must_use]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_done(mut self) -> Self {
+        self.done = true;
+        self
+    }
+
+    #[must_use]
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.done = true;
+        self.error = Some(error.into());
+        self
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+impl From<JobStatus> for Value {
+    fn from(src: JobStatus) -> Self {
+        src.to_value()
+    }
+}
@@ -1,11 +1,27 @@
 mod action_enum;
+mod chunk;
+mod job_status;
 mod opt_widget_list;
 mod page_enum;
+#[cfg(feature = "schema")]
+mod schema;
+mod tab;
+mod toast;
+mod update;
+mod validate;
 mod widget_enum;
 mod widget_list;
 
 pub use action_enum::*;
+pub use chunk::*;
+pub use job_status::*;
 pub use opt_widget_list::*;
 pub use page_enum::*;
+#[cfg(feature = "schema")]
+pub use schema::*;
+pub use tab::*;
+pub use toast::*;
+pub use update::*;
+pub use validate::*;
 pub use widget_enum::*;
 pub use widget_list::*;
@@ -1,10 +1,13 @@
-use crate::internal::Widget;
+use crate::internal::{Action, Tab, Widget};
 use crate::is_default;
+use crate::style::Theme;
+use crate::widget::{HAlignment, SheetDetent};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "typ")]
 pub enum Page {
     #[serde(rename = "alert-modal")]
@@ -32,10 +35,16 @@ pub enum Page {
         #[serde(rename = "poll-seconds")]
         #[serde(default, skip_serializing_if = "is_default")]
         poll_seconds: u32,
+        /// When the user pulls down on the page, the client calls this RPC path.
+        #[serde(rename = "pull-to-refresh")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pull_to_refresh: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         start: Option<Widget>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         stream: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        theme: Option<Theme>,
         title: String,
         widget: Widget,
     },
@@ -44,11 +53,37 @@ pub enum Page {
         #[serde(rename = "poll-seconds")]
         #[serde(default, skip_serializing_if = "is_default")]
         poll_seconds: u32,
+        /// When the user pulls down on the page, the client calls this RPC path.
+        #[serde(rename = "pull-to-refresh")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pull_to_refresh: Option<String>,
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         stream: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        theme: Option<Theme>,
         title: String,
         widget: Widget,
     },
+    /// A modal that slides up from the bottom.
+    #[serde(rename = "sheet-modal")]
+    Sheet {
+        /// Heights where the sheet rests.  Empty uses the client's default.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        detents: Vec<SheetDetent>,
+        title: String,
+        widget: Widget,
+    },
+    /// A tab bar.  Each tab shows a page and has its own stack of pages.
+    #[serde(rename = "tab-page")]
+    Tab {
+        /// The page key of the selected tab.  `None` selects the first tab.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        selected: Option<String>,
+        tabs: Vec<Tab>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        theme: Option<Theme>,
+        title: String,
+    },
 }
 impl Page {
     #[must_use]
@@ -56,6 +91,171 @@ impl Page {
     pub fn to_value(&self) -> Value {
         serde_json::to_value(self).unwrap()
     }
+
+    /// Returns the first protocol version that supports this page type.
+    #[must_use]
+    pub fn min_protocol_version(&self) -> u32 {
+        match self {
+            Page::Alert { .. } | Page::Drawer { .. } | Page::Nav { .. } | Page::Plain { .. } => 1,
+            Page::Sheet { .. } | Page::Tab { .. } => 2,
+        }
+    }
+
+    #[must_use]
+    pub fn title(&self) -> &str {
+        match self {
+            Page::Alert { title, .. }
+            | Page::Drawer { title, .. }
+            | Page::Nav { title, .. }
+            | Page::Plain { title, .. }
+            | Page::Sheet { title, .. }
+            | Page::Tab { title, .. } => title,
+        }
+    }
+
+    /// Returns the theme that the page sets, if any.
+    #[must_use]
+    pub fn theme(&self) -> Option<&Theme> {
+        match self {
+            Page::Alert { .. } | Page::Drawer { .. } | Page::Sheet { .. } => None,
+            Page::Nav { theme, .. } | Page::Plain { theme, .. } | Page::Tab { theme, .. } => {
+                theme.as_ref()
+            }
+        }
+    }
+
+    /// Returns the page's top-level widgets.
+    #[must_use]
+    pub fn widgets(&self) -> Vec<&Widget> {
+        match self {
+            Page::Alert { widgets, .. } | Page::Drawer { widgets, .. } => widgets.iter().collect(),
+            Page::Nav {
+                end, start, widget, ..
+            } => start
+                .iter()
+                .chain(std::iter::once(widget))
+                .chain(end.iter())
+                .collect(),
+            Page::Plain { widget, .. } | Page::Sheet { widget, .. } => vec![widget],
+            Page::Tab { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the page key of the tab that the client shows first.
+    #[must_use]
+    pub fn selected_tab(&self) -> Option<&str> {
+        match self {
+            Page::Tab { selected, tabs, .. } => selected
+                .as_deref()
+                .or_else(|| tabs.first().map(|tab| tab.page_key.as_str())),
+            _ => None,
+        }
+    }
+
+    /// Returns the page's top-level widgets.
+    #[must_use]
+    pub fn widgets_mut(&mut self) -> Vec<&mut Widget> {
+        match self {
+            Page::Alert { widgets, .. } | Page::Drawer { widgets, .. } => {
+                widgets.iter_mut().collect()
+            }
+            Page::Nav {
+                end, start, widget, ..
+            } => start
+                .iter_mut()
+                .chain(std::iter::once(widget))
+                .chain(end.iter_mut())
+                .collect(),
+            Page::Plain { widget, .. } | Page::Sheet { widget, .. } => vec![widget],
+            Page::Tab { .. } => Vec::new(),
+        }
+    }
+
+    /// Changes the page so a client with `protocol_version` can display it.
+    ///
+    /// When the client doesn't support tab pages,
+    /// this replaces the tab page with a nav page that has a button for each tab.
+    /// When it doesn't support sheets, this shows the sheet's widget in a nav page.
+    /// When the client doesn't support another page type,
+    /// this replaces the page with one that asks the user to update the app.
+    /// Otherwise, it removes the fields that the client doesn't support
+    /// and calls [`Widget::downgrade`] on the page's widgets.
+    pub fn downgrade(&mut self, protocol_version: u32) {
+        if protocol_version < self.min_protocol_version() {
+            if let Page::Tab { tabs, title, .. } = self {
+                *self = Page::Nav {
+                    end: None,
+                    poll_seconds: 0,
+                    pull_to_refresh: None,
+                    start: None,
+                    stream: false,
+                    theme: None,
+                    title: std::mem::take(title),
+                    widget: Widget::ColumnVariant {
+                        h_alignment: HAlignment::Start,
+                        spacing: 0,
+                        widgets: std::mem::take(tabs)
+                            .into_iter()
+                            .map(|tab| Widget::NavButtonVariant {
+                                text: tab.title,
+                                sub_text: None,
+                                photo_url: None,
+                                icon: None,
+                                actions: vec![Action::Push(tab.page_key)],
+                                badge_text: tab.badge_text,
+                            })
+                            .collect(),
+                    },
+                };
+                return;
+            }
+            if let Page::Sheet { title, widget, .. } = self {
+                *self = Page::Nav {
+                    end: None,
+                    poll_seconds: 0,
+                    pull_to_refresh: None,
+                    start: None,
+                    stream: false,
+                    theme: None,
+                    title: std::mem::take(title),
+                    widget: std::mem::take(widget),
+                };
+                self.downgrade(protocol_version);
+                return;
+            }
+            *self = Page::Plain {
+                poll_seconds: 0,
+                pull_to_refresh: None,
+                stream: false,
+                theme: None,
+                title: "Update Required".to_string(),
+                widget: Widget::TextVariant {
+                    text: "Please update the app.".to_string(),
+                },
+            };
+        } else {
+            // Version 2 added page themes and pull-to-refresh.
+            if protocol_version < 2 {
+                if let Page::Nav {
+                    pull_to_refresh,
+                    theme,
+                    ..
+                }
+                | Page::Plain {
+                    pull_to_refresh,
+                    theme,
+                    ..
+                } = self
+                {
+                    *pull_to_refresh = None;
+                    *theme = None;
+                }
+            }
+            for widget in self.widgets_mut() {
+                widget.downgrade(protocol_version);
+            }
+        }
+    }
 }
 impl From<Page> for Value {
     fn from(src: Page) -> Self {
@@ -67,8 +267,10 @@ impl Default for Page {
         Self::Nav {
             end: None,
             poll_seconds: 0,
+            pull_to_refresh: None,
             start: None,
             stream: false,
+            theme: None,
             title: "Default Page".to_string(),
             widget: Widget::EmptyVariant,
         }
@@ -0,0 +1,18 @@
+use crate::internal::Update;
+use schemars::gen::SchemaSettings;
+use serde_json::Value;
+
+/// Returns a [JSON Schema](https://json-schema.org/) (draft 7) for the Applin protocol.
+///
+/// The root schema describes the body of poll and RPC responses and stream messages.
+/// Its `definitions` describe `Page`, `Widget`, and `Action`.
+///
+/// The schema comes from the same types that the server serializes,
+/// so it always matches what the server sends.
+#[must_use]
+#[allow(clippy::missing_panics_doc)]
+pub fn json_schema() -> Value {
+    let gen = SchemaSettings::draft07().into_generator();
+    let root_schema = gen.into_root_schema_for::<Update>();
+    serde_json::to_value(root_schema).unwrap()
+}
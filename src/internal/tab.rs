@@ -0,0 +1,55 @@
+use crate::session::PageKey;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One tab of a `tab-page`.
+/// Each tab has its own stack of pages, starting with the page at `page_key`.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Tab {
+    #[serde(rename = "badge-text")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub badge_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(rename = "page-key")]
+    pub page_key: String,
+    pub title: String,
+}
+impl Tab {
+    #[must_use]
+    pub fn new(title: impl Into<String>, page_key: &PageKey) -> Self {
+        Self {
+            badge_text: None,
+            icon: None,
+            page_key: page_key.clone().into_inner(),
+            title: title.into(),
+        }
+    }
+
+    /// Shows `text` in a badge on the tab.
+    /// See [`crate::session::ApplinSession::set_tab_badge`] to change it from outside the page function.
+    #[must_use]
+    pub fn with_badge_text(mut self, text: impl Into<String>) -> Self {
+        self.badge_text = Some(text.into());
+        self
+    }
+
+    /// Shows the icon with `name` above the title.
+    #[must_use]
+    pub fn with_icon(mut self, name: impl Into<String>) -> Self {
+        self.icon = Some(name.into());
+        self
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+impl From<Tab> for Value {
+    fn from(src: Tab) -> Self {
+        src.to_value()
+    }
+}
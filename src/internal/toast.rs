@@ -0,0 +1,60 @@
+use crate::internal::Action;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A short message that the client shows briefly without blocking the user.
+/// Show one with [`crate::session::ApplinSession::show_toast`].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Toast {
+    /// The client performs these when the user taps the button.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<Action>,
+    #[serde(rename = "button-text")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub button_text: Option<String>,
+    /// How long the client shows the toast.  Zero uses the client's default.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub seconds: u32,
+    pub text: String,
+}
+impl Toast {
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            actions: Vec::new(),
+            button_text: None,
+            seconds: 0,
+            text: text.into(),
+        }
+    }
+
+    /// Adds a button that performs `actions`, like an "Undo" button that calls an RPC.
+    #[must_use]
+    pub fn with_button(
+        mut self,
+        text: impl Into<String>,
+        actions: impl IntoIterator<Item = Action>,
+    ) -> Self {
+        self.button_text = Some(text.into());
+        self.actions = actions.into_iter().collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_seconds(mut self, seconds: u32) -> Self {
+        self.seconds = seconds;
+        self
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+impl From<Toast> for Value {
+    fn from(src: Toast) -> Self {
+        src.to_value()
+    }
+}
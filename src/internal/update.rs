@@ -0,0 +1,76 @@
+use crate::internal::{JobStatus, Page, Toast};
+use crate::style::Theme;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// The body of a poll or RPC response, and of each stream message.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Update {
+    /// Page key to the new page.  `null` removes the key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pages: BTreeMap<String, Option<Page>>,
+    /// Var name to the new value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vars: Option<Map<String, Value>>,
+    /// The session's new theme.  An empty theme removes the session theme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    /// Tab page key to the key of the tab for the client to select,
+    /// even when the user selected another tab.
+    #[serde(rename = "select-tabs")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub select_tabs: BTreeMap<String, String>,
+    /// The key of a page for the client to push, like a `push` action.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub push: Option<String>,
+    /// Messages for the client to show briefly, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub toasts: Vec<Toast>,
+    /// The latest status of each long-running RPC that changed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jobs: Vec<JobStatus>,
+    /// The ID of the job that this RPC started.  See `rpc-with-progress`.
+    #[serde(rename = "started-job")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_job: Option<String>,
+}
+impl Update {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+            && self.vars.is_none()
+            && self.theme.is_none()
+            && self.select_tabs.is_empty()
+            && self.push.is_none()
+            && self.toasts.is_empty()
+            && self.jobs.is_empty()
+            && self.started_job.is_none()
+    }
+
+    /// Removes the fields that `protocol_version` does not support.
+    /// Version 2 added `theme`, `select-tabs`, `push`, `toasts`, `jobs`, and `started-job`.
+    /// Call [`Page::downgrade`] on the pages separately.
+    pub fn downgrade(&mut self, protocol_version: u32) {
+        if protocol_version < 2 {
+            self.theme = None;
+            self.select_tabs.clear();
+            self.push = None;
+            self.toasts.clear();
+            self.jobs.clear();
+            self.started_job = None;
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+impl From<Update> for Value {
+    fn from(src: Update) -> Self {
+        src.to_value()
+    }
+}
@@ -0,0 +1,639 @@
+use crate::internal::{Action, Page, Widget};
+use crate::style::{check_color_ref, check_icon_name};
+use crate::widget::{Choice, DateRange, Real32, SheetDetent, Span};
+use core::fmt::{Display, Formatter};
+use std::collections::{HashMap, HashSet};
+
+/// A problem found by [`Page::validate`] or [`Widget::validate`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Diagnostic {
+    /// A [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) to the problem
+    /// in the serialized page or widget.
+    pub path: String,
+    pub message: String,
+}
+impl Diagnostic {
+    #[must_use]
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{:?}: {}", self.path, self.message)
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Parent {
+    Form,
+    Modal,
+    NavStart,
+    Other,
+}
+
+/// The widgets that forms, and form sections inside forms, may contain.
+fn allowed_in_form(widget: &Widget) -> bool {
+    matches!(
+        widget,
+        Widget::CheckboxVariant { .. }
+            | Widget::DatePickerVariant { .. }
+            | Widget::DateRangePickerVariant { .. }
+            | Widget::EmptyVariant
+            | Widget::ErrorTextVariant { .. }
+            | Widget::FormButtonVariant { .. }
+            | Widget::FormSectionVariant { .. }
+            | Widget::MultiSelectVariant { .. }
+            | Widget::NumberFieldVariant { .. }
+            | Widget::PickerVariant { .. }
+            | Widget::RadioGroupVariant { .. }
+            | Widget::SliderVariant { .. }
+            | Widget::StepperVariant { .. }
+            | Widget::TextfieldVariant { .. }
+            | Widget::TextVariant { .. }
+            // Reported as a nested form.
+            | Widget::FormVariant { .. }
+    )
+}
+
+#[derive(Default)]
+struct Validator {
+    diagnostics: Vec<Diagnostic>,
+    /// Var name to the path of the first widget that uses it.
+    vars: HashMap<String, String>,
+    /// True while checking the descendants of a form.
+    in_form: bool,
+}
+impl Validator {
+    fn add(&mut self, path: &str, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::new(path, message));
+    }
+
+    fn check_actions(&mut self, path: &str, actions: &[Action]) {
+        self.check_action_list(&format!("{path}/actions"), actions);
+    }
+
+    fn check_action_list(&mut self, path: &str, actions: &[Action]) {
+        for (n, action) in actions.iter().enumerate() {
+            let arg = match action {
+                Action::ChooseFile {
+                    max_bytes,
+                    upload_url,
+                    ..
+                } => {
+                    if *max_bytes == 0 {
+                        self.add(
+                            &format!("{path}/{n}"),
+                            "choose-file max-bytes must be positive",
+                        );
+                    }
+                    upload_url
+                }
+                Action::Confirm { then, title, .. } => {
+                    if title.is_empty() {
+                        self.add(&format!("{path}/{n}"), "confirm title is empty");
+                    }
+                    self.check_action_list(&format!("{path}/{n}/then"), then);
+                    continue;
+                }
+                Action::RpcBranch {
+                    on_error,
+                    on_success,
+                    url,
+                } => {
+                    if url.is_empty() {
+                        self.add(&format!("{path}/{n}"), "rpc url is empty");
+                    }
+                    self.check_action_list(&format!("{path}/{n}/on-success"), on_success);
+                    self.check_action_list(&format!("{path}/{n}/on-error"), on_error);
+                    continue;
+                }
+                Action::ChoosePhoto(s)
+                | Action::LaunchUrl(s)
+                | Action::Push(s)
+                | Action::Refresh(s)
+                | Action::Rpc(s)
+                | Action::RpcWithProgress(s)
+                | Action::TakePhoto(s) => s,
+                Action::CopyToClipboard(..) | Action::Logout | Action::Nothing | Action::Pop => {
+                    continue
+                }
+            };
+            if arg.is_empty() {
+                self.add(
+                    &format!("{path}/{n}"),
+                    format!("action {} has an empty argument", action.to_value()),
+                );
+            }
+        }
+    }
+
+    fn check_var(&mut self, path: &str, var: &str) {
+        if var.is_empty() {
+            self.add(path, "var is empty");
+        } else if let Some(other_path) = self.vars.get(var) {
+            let message = format!("var {var:?} is also used by {other_path:?}");
+            self.add(path, message);
+        } else {
+            self.vars.insert(var.to_string(), path.to_string());
+        }
+    }
+
+    fn check_bounds<V: Copy + Display + Ord>(
+        &mut self,
+        path: &str,
+        name: &str,
+        initial: Option<V>,
+        min: Option<V>,
+        max: Option<V>,
+    ) {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                self.add(path, format!("min-{name} {min} is after max-{name} {max}"));
+            }
+        }
+        if let Some(initial) = initial {
+            if min.is_some_and(|min| initial < min) || max.is_some_and(|max| max < initial) {
+                self.add(path, format!("initial {name} {initial} is out of bounds"));
+            }
+        }
+    }
+
+    fn check_choices<'a>(
+        &mut self,
+        path: &str,
+        options: &[Choice],
+        initial_ids: impl IntoIterator<Item = &'a String>,
+    ) {
+        if options.is_empty() {
+            self.add(path, "options is empty");
+        }
+        let mut ids = HashSet::new();
+        for (n, option) in options.iter().enumerate() {
+            if option.id.is_empty() {
+                self.add(&format!("{path}/options/{n}"), "option id is empty");
+            } else if !ids.insert(&option.id) {
+                self.add(
+                    &format!("{path}/options/{n}"),
+                    format!("option id {:?} is used by another option", option.id),
+                );
+            }
+        }
+        for id in initial_ids {
+            if !ids.contains(id) {
+                self.add(path, format!("initial id {id:?} is not an option id"));
+            }
+        }
+    }
+
+    fn check_number(
+        &mut self,
+        path: &str,
+        initial: Option<&Real32>,
+        integer: bool,
+        min: Option<&Real32>,
+        max: Option<&Real32>,
+        step: Option<&Real32>,
+    ) {
+        let get = |opt: Option<&Real32>| opt.cloned().map(Real32::get);
+        let (initial, min, max, step) = (get(initial), get(min), get(max), get(step));
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                self.add(path, format!("min {min} is greater than max {max}"));
+            }
+        }
+        if step.is_some_and(|step| step <= 0.0) {
+            self.add(path, "step must be positive");
+        }
+        if integer {
+            for (name, opt) in [
+                ("initial-number", initial),
+                ("min", min),
+                ("max", max),
+                ("step", step),
+            ] {
+                if opt.is_some_and(|n| n.fract() != 0.0) {
+                    self.add(path, format!("{name} must be a whole number"));
+                }
+            }
+        }
+        if let Some(initial) = initial {
+            if min.is_some_and(|min| initial < min) || max.is_some_and(|max| max < initial) {
+                self.add(path, format!("initial-number {initial} is out of bounds"));
+            }
+        }
+    }
+
+    fn check_widgets(&mut self, path: &str, widgets: &[Widget], parent: Parent) {
+        for (n, widget) in widgets.iter().enumerate() {
+            self.check_widget(&format!("{path}/{n}"), widget, parent);
+        }
+    }
+
+    fn check_spans(&mut self, path: &str, spans: &[Span]) {
+        for (n, span) in spans.iter().enumerate() {
+            let span_path = format!("{path}/spans/{n}");
+            if span.text.is_empty() && span.spans.is_empty() {
+                self.add(&span_path, "span has no text");
+            }
+            if let Some(Err(e)) = span.color.as_deref().map(check_color_ref) {
+                self.add(&span_path, format!("span color: {e}"));
+            }
+            self.check_actions(&span_path, &span.actions);
+            self.check_spans(&span_path, &span.spans);
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn check_widget(&mut self, path: &str, widget: &Widget, parent: Parent) {
+        match widget {
+            Widget::BackButtonVariant { actions } => {
+                if parent != Parent::NavStart {
+                    self.add(path, "back-button is only allowed in the nav-page `start`");
+                }
+                self.check_actions(path, actions);
+            }
+            Widget::ButtonVariant { actions, icon, .. }
+            | Widget::FormButtonVariant { actions, icon, .. }
+            | Widget::NavButtonVariant { actions, icon, .. } => {
+                if !self.in_form && matches!(widget, Widget::FormButtonVariant { .. }) {
+                    self.add(path, "form-button is only allowed inside a form");
+                }
+                self.check_actions(path, actions);
+                if let Some(Err(e)) = icon.as_deref().map(check_icon_name) {
+                    self.add(path, e);
+                }
+            }
+            Widget::ProgressBarVariant { percent, .. } => {
+                if percent.is_some_and(|percent| percent > 100) {
+                    self.add(path, "progress-bar percent must be at most 100");
+                }
+            }
+            Widget::IconVariant { name, .. } => {
+                if let Err(e) = check_icon_name(name) {
+                    self.add(path, e);
+                }
+            }
+            Widget::CheckboxVariant { var, .. } => self.check_var(path, var),
+            Widget::ColumnVariant { widgets, .. }
+            | Widget::RowVariant { widgets, .. }
+            | Widget::StackVariant { widgets, .. } => {
+                self.check_widgets(&format!("{path}/widgets"), widgets, Parent::Other);
+            }
+            Widget::DatePickerVariant {
+                initial_date,
+                max_date,
+                min_date,
+                var,
+                ..
+            } => {
+                self.check_bounds(path, "date", *initial_date, *min_date, *max_date);
+                self.check_var(path, var);
+            }
+            Widget::DateRangePickerVariant {
+                initial_range,
+                max_date,
+                min_date,
+                var,
+                ..
+            } => {
+                let initial_start = initial_range.map(DateRange::start);
+                let initial_end = initial_range.map(DateRange::end);
+                self.check_bounds(path, "date", initial_start, *min_date, *max_date);
+                self.check_bounds(path, "date", initial_end, *min_date, *max_date);
+                self.check_var(path, var);
+            }
+            Widget::EmptyVariant
+            | Widget::ErrorDetailsVariant
+            | Widget::ErrorTextVariant { .. }
+            | Widget::SpacerVariant { .. }
+            | Widget::TextVariant { .. } => {}
+            Widget::FormVariant { widgets } => {
+                if self.in_form {
+                    self.add(path, "form is not allowed inside another form");
+                }
+                let outer_in_form = std::mem::replace(&mut self.in_form, true);
+                self.check_widgets(&format!("{path}/widgets"), widgets, Parent::Form);
+                self.in_form = outer_in_form;
+            }
+            // Outside forms, form sections group other widgets, like nav-buttons.
+            Widget::FormSectionVariant { widgets, .. } => {
+                let parent = if self.in_form {
+                    Parent::Form
+                } else {
+                    Parent::Other
+                };
+                self.check_widgets(&format!("{path}/widgets"), widgets, parent);
+            }
+            Widget::FrameVariant {
+                max_height,
+                max_width,
+                min_height,
+                min_width,
+                widget,
+                ..
+            } => {
+                if min_width
+                    .zip(*max_width)
+                    .is_some_and(|(min, max)| max < min)
+                {
+                    self.add(path, "frame min-width is greater than max-width");
+                }
+                if min_height
+                    .zip(*max_height)
+                    .is_some_and(|(min, max)| max < min)
+                {
+                    self.add(path, "frame min-height is greater than max-height");
+                }
+                self.check_widget(&format!("{path}/widget"), widget, parent);
+            }
+            Widget::PaddingVariant { widget, .. } => {
+                self.check_widget(&format!("{path}/widget"), widget, parent);
+            }
+            Widget::RichTextVariant { spans } => self.check_spans(path, spans),
+            Widget::StyledVariant { style, widget } => {
+                for problem in style.check() {
+                    self.add(path, problem);
+                }
+                self.check_widget(&format!("{path}/widget"), widget, parent);
+            }
+            Widget::GroupedRowTableVariant { row_groups, .. } => {
+                let mut num_columns = None;
+                for (g, group) in row_groups.iter().enumerate() {
+                    for (r, row) in group.iter().enumerate() {
+                        let row_path = format!("{path}/row-groups/{g}/{r}");
+                        match num_columns {
+                            None => num_columns = Some(row.len()),
+                            Some(n) if n != row.len() => self.add(
+                                &row_path,
+                                format!("row has {} columns, expected {n}", row.len()),
+                            ),
+                            Some(_) => {}
+                        }
+                        for (c, opt_cell) in row.iter().enumerate() {
+                            if let Some(cell) = opt_cell {
+                                self.check_widget(&format!("{row_path}/{c}"), cell, Parent::Other);
+                            }
+                        }
+                    }
+                }
+            }
+            Widget::ImageVariant {
+                aspect_ratio, url, ..
+            } => {
+                if aspect_ratio.clone().get() <= 0.0 {
+                    self.add(path, "image aspect-ratio must be positive");
+                }
+                if url.is_empty() {
+                    self.add(path, "image url is empty");
+                }
+            }
+            Widget::LazyListVariant { url, widgets, .. } => {
+                if url.is_empty() {
+                    self.add(path, "lazy-list url is empty");
+                }
+                self.check_widgets(&format!("{path}/widgets"), widgets, Parent::Other);
+            }
+            Widget::ModalButtonVariant { actions, .. } => {
+                if parent != Parent::Modal {
+                    self.add(
+                        path,
+                        "modal-button is only allowed in alert and drawer modals",
+                    );
+                }
+                self.check_actions(path, actions);
+            }
+            Widget::MultiSelectVariant {
+                initial_ids,
+                options,
+                var,
+                ..
+            } => {
+                self.check_choices(path, options, initial_ids);
+                self.check_var(path, var);
+            }
+            Widget::NumberFieldVariant {
+                initial_number,
+                integer,
+                max,
+                min,
+                var,
+                ..
+            } => {
+                self.check_number(
+                    path,
+                    initial_number.as_ref(),
+                    *integer,
+                    min.as_ref(),
+                    max.as_ref(),
+                    None,
+                );
+                self.check_var(path, var);
+            }
+            Widget::SliderVariant {
+                initial_number,
+                integer,
+                max,
+                min,
+                step,
+                var,
+                ..
+            } => {
+                self.check_number(
+                    path,
+                    initial_number.as_ref(),
+                    *integer,
+                    Some(min),
+                    Some(max),
+                    step.as_ref(),
+                );
+                self.check_var(path, var);
+            }
+            Widget::StepperVariant {
+                initial_number,
+                integer,
+                max,
+                min,
+                step,
+                var,
+                ..
+            } => {
+                self.check_number(
+                    path,
+                    initial_number.as_ref(),
+                    *integer,
+                    min.as_ref(),
+                    max.as_ref(),
+                    step.as_ref(),
+                );
+                self.check_var(path, var);
+            }
+            Widget::PickerVariant {
+                initial_id,
+                options,
+                var,
+                ..
+            }
+            | Widget::RadioGroupVariant {
+                initial_id,
+                options,
+                var,
+                ..
+            } => {
+                self.check_choices(path, options, initial_id);
+                self.check_var(path, var);
+            }
+            Widget::ScrollVariant { widget } => {
+                self.check_widget(&format!("{path}/widget"), widget, Parent::Other);
+            }
+            Widget::TextfieldVariant {
+                max_chars,
+                min_chars,
+                var,
+                ..
+            } => {
+                if min_chars > max_chars {
+                    self.add(
+                        path,
+                        format!(
+                            "textfield min-chars {min_chars} is greater than max-chars {max_chars}"
+                        ),
+                    );
+                }
+                self.check_var(path, var);
+            }
+            Widget::TimePickerVariant {
+                initial_time,
+                max_time,
+                min_time,
+                var,
+                ..
+            } => {
+                self.check_bounds(path, "time", *initial_time, *min_time, *max_time);
+                self.check_var(path, var);
+            }
+        }
+        // Back buttons and modal buttons have their own placement rules.
+        if parent == Parent::Form
+            && !allowed_in_form(widget)
+            && !matches!(
+                widget,
+                Widget::BackButtonVariant { .. } | Widget::ModalButtonVariant { .. }
+            )
+        {
+            let typ = widget.to_value()["typ"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            self.add(path, format!("{typ} is not allowed inside a form"));
+        }
+        if parent == Parent::Modal && !matches!(widget, Widget::ModalButtonVariant { .. }) {
+            self.add(
+                path,
+                "alert and drawer modals may contain only modal-button widgets",
+            );
+        }
+    }
+
+    fn check_page(&mut self, page: &Page) {
+        if let Page::Nav {
+            pull_to_refresh: Some(rpc_url),
+            ..
+        }
+        | Page::Plain {
+            pull_to_refresh: Some(rpc_url),
+            ..
+        } = page
+        {
+            if rpc_url.is_empty() {
+                self.add("", "pull-to-refresh is empty");
+            }
+        }
+        match page {
+            Page::Alert { widgets, .. } | Page::Drawer { widgets, .. } => {
+                self.check_widgets("/widgets", widgets, Parent::Modal);
+            }
+            Page::Nav {
+                end, start, widget, ..
+            } => {
+                if let Some(end) = end {
+                    self.check_widget("/end", end, Parent::Other);
+                }
+                if let Some(start) = start {
+                    self.check_widget("/start", start, Parent::NavStart);
+                }
+                self.check_widget("/widget", widget, Parent::Other);
+            }
+            Page::Plain { widget, .. } => self.check_widget("/widget", widget, Parent::Other),
+            Page::Sheet {
+                detents, widget, ..
+            } => {
+                let mut seen = HashSet::new();
+                for (n, detent) in detents.iter().enumerate() {
+                    if *detent == SheetDetent::Height(0) {
+                        self.add(&format!("/detents/{n}"), "sheet detent height is zero");
+                    } else if !seen.insert(detent) {
+                        self.add(&format!("/detents/{n}"), "sheet detent is repeated");
+                    }
+                }
+                self.check_widget("/widget", widget, Parent::Other);
+            }
+            Page::Tab { selected, tabs, .. } => {
+                if tabs.is_empty() {
+                    self.add("/tabs", "tab-page has no tabs");
+                }
+                let mut page_keys = HashSet::new();
+                for (n, tab) in tabs.iter().enumerate() {
+                    if let Some(Err(e)) = tab.icon.as_deref().map(check_icon_name) {
+                        self.add(&format!("/tabs/{n}"), e);
+                    }
+                    if tab.page_key.is_empty() {
+                        self.add(&format!("/tabs/{n}"), "tab page-key is empty");
+                    } else if !page_keys.insert(tab.page_key.as_str()) {
+                        self.add(
+                            &format!("/tabs/{n}"),
+                            format!("tab page-key {:?} is repeated", tab.page_key),
+                        );
+                    }
+                }
+                if let Some(selected) = selected {
+                    if !page_keys.contains(selected.as_str()) {
+                        self.add(
+                            "/selected",
+                            format!("selected tab {selected:?} is not a tab"),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Widget {
+    /// Checks the widget and its children for problems that clients handle poorly,
+    /// like table rows with different numbers of columns or widgets that share a var.
+    ///
+    /// Returns an empty list when it finds no problems.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator::default();
+        validator.check_widget("", self, Parent::Other);
+        validator.diagnostics
+    }
+}
+
+impl Page {
+    /// Checks the page and all of its widgets for problems.
+    /// See [`Widget::validate`].
+    ///
+    /// Returns an empty list when it finds no problems.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator::default();
+        validator.check_page(self);
+        validator.diagnostics
+    }
+}
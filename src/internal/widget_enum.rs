@@ -1,10 +1,12 @@
-use crate::internal::Action;
+use crate::internal::{downgrade_actions, Action};
 use crate::is_default;
-use crate::widget::{HAlignment, Real32};
+use crate::style::Style;
+use crate::widget::{Choice, Date, DateRange, HAlignment, Real32, Span, Time, VAlignment};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextfieldAllow {
     #[serde(rename = "all")]
     All,
@@ -24,6 +26,7 @@ impl Default for TextfieldAllow {
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TextfieldAutoCapitalize {
     #[serde(rename = "names")]
     Names,
@@ -37,6 +40,7 @@ impl Default for TextfieldAutoCapitalize {
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ImageDisposition {
     /// Size the image to fill the space, preserving aspect ratio.
     #[serde(rename = "cover")]
@@ -49,13 +53,22 @@ pub enum ImageDisposition {
     Stretch,
 }
 
+/// Clients see this text in place of widgets that they don't support.
+pub const UPDATE_APP_TEXT: &str = "Update the app to see this.";
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 #[must_use]
 fn is_u32_max(n: &u32) -> bool {
     *n == u32::MAX
 }
 
+#[must_use]
+fn u32_max() -> u32 {
+    u32::MAX
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "typ")]
 pub enum Widget {
     #[serde(rename = "back-button")]
@@ -65,6 +78,8 @@ pub enum Widget {
         text: String,
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         actions: Vec<Action>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        icon: Option<String>,
     },
     #[serde(rename = "checkbox")]
     CheckboxVariant {
@@ -85,6 +100,40 @@ pub enum Widget {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         widgets: Vec<Widget>,
     },
+    #[serde(rename = "date-picker")]
+    DatePickerVariant {
+        #[serde(rename = "initial-date")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_date: Option<Date>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        #[serde(rename = "max-date")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_date: Option<Date>,
+        #[serde(rename = "min-date")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_date: Option<Date>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        var: String,
+    },
+    #[serde(rename = "date-range-picker")]
+    DateRangePickerVariant {
+        #[serde(rename = "initial-range")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_range: Option<DateRange>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        #[serde(rename = "max-date")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_date: Option<Date>,
+        #[serde(rename = "min-date")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_date: Option<Date>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        var: String,
+    },
     #[serde(rename = "empty")]
     EmptyVariant,
     #[serde(rename = "error-details")]
@@ -100,6 +149,8 @@ pub enum Widget {
     FormButtonVariant {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         actions: Vec<Action>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        icon: Option<String>,
         #[serde(rename = "is-destructive")]
         #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         is_destructive: bool,
@@ -112,6 +163,24 @@ pub enum Widget {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         widgets: Vec<Widget>,
     },
+    #[serde(rename = "frame")]
+    FrameVariant {
+        #[serde(rename = "max-height")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_height: Option<u16>,
+        #[serde(rename = "max-width")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_width: Option<u16>,
+        #[serde(rename = "min-height")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_height: Option<u16>,
+        #[serde(rename = "min-width")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_width: Option<u16>,
+        #[serde(default, skip_serializing_if = "is_default")]
+        weight: u16,
+        widget: Box<Widget>,
+    },
     #[serde(rename = "grouped-row-table")]
     GroupedRowTableVariant {
         #[serde(rename = "row-groups", default, skip_serializing_if = "Vec::is_empty")]
@@ -119,6 +188,13 @@ pub enum Widget {
         #[serde(default, skip_serializing_if = "is_default")]
         spacing: u16,
     },
+    /// An icon from the set that clients ship.  See [`crate::style::ICON_NAMES`].
+    #[serde(rename = "icon")]
+    IconVariant {
+        name: String,
+        #[serde(default, skip_serializing_if = "is_default")]
+        size: u16,
+    },
     #[serde(rename = "image")]
     ImageVariant {
         #[serde(rename = "aspect-ratio")]
@@ -126,6 +202,20 @@ pub enum Widget {
         disposition: ImageDisposition,
         url: String,
     },
+    /// Shows `widgets` in a column.  When the user scrolls to the end, the client POSTs
+    /// `{"cursor": next_cursor}` to `url`, appends the widgets from the [`crate::internal::Chunk`]
+    /// in the response, and stops when it has no `next-cursor`.
+    #[serde(rename = "lazy-list")]
+    LazyListVariant {
+        #[serde(rename = "next-cursor")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        next_cursor: Option<String>,
+        #[serde(default, skip_serializing_if = "is_default")]
+        spacing: u16,
+        url: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        widgets: Vec<Widget>,
+    },
     #[serde(rename = "modal-button")]
     ModalButtonVariant {
         text: String,
@@ -141,6 +231,18 @@ pub enum Widget {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         actions: Vec<Action>,
     },
+    #[serde(rename = "multi-select")]
+    MultiSelectVariant {
+        #[serde(rename = "initial-ids")]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        initial_ids: Vec<String>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        options: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        var: String,
+    },
     #[serde(rename = "nav-button")]
     NavButtonVariant {
         text: String,
@@ -148,6 +250,8 @@ pub enum Widget {
         sub_text: Option<String>,
         #[serde(rename = "photo-url", default, skip_serializing_if = "Option::is_none")]
         photo_url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        icon: Option<String>,
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         actions: Vec<Action>,
         #[serde(
@@ -157,8 +261,139 @@ pub enum Widget {
         )]
         badge_text: Option<String>,
     },
+    #[serde(rename = "number-field")]
+    NumberFieldVariant {
+        #[serde(rename = "initial-number")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_number: Option<Real32>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        integer: bool,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<Real32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<Real32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        units: String,
+        var: String,
+    },
+    #[serde(rename = "padding")]
+    PaddingVariant {
+        #[serde(default, skip_serializing_if = "is_default")]
+        bottom: u16,
+        #[serde(default, skip_serializing_if = "is_default")]
+        end: u16,
+        #[serde(default, skip_serializing_if = "is_default")]
+        start: u16,
+        #[serde(default, skip_serializing_if = "is_default")]
+        top: u16,
+        widget: Box<Widget>,
+    },
+    #[serde(rename = "picker")]
+    PickerVariant {
+        #[serde(rename = "initial-id")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_id: Option<String>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        options: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        var: String,
+    },
+    /// Shows how much of a task is done.  A `None` percent shows an indeterminate bar.
+    #[serde(rename = "progress-bar")]
+    ProgressBarVariant {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        percent: Option<u8>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        text: String,
+    },
+    #[serde(rename = "radio-group")]
+    RadioGroupVariant {
+        #[serde(rename = "initial-id")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_id: Option<String>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        options: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        var: String,
+    },
+    #[serde(rename = "rich-text")]
+    RichTextVariant { spans: Vec<Span> },
+    #[serde(rename = "row")]
+    RowVariant {
+        #[serde(default, skip_serializing_if = "is_default")]
+        spacing: u16,
+        #[serde(default, rename = "v-alignment")]
+        v_alignment: VAlignment,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        widgets: Vec<Widget>,
+    },
     #[serde(rename = "scroll")]
     ScrollVariant { widget: Box<Widget> },
+    #[serde(rename = "slider")]
+    SliderVariant {
+        #[serde(rename = "initial-number")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_number: Option<Real32>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        integer: bool,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        max: Real32,
+        min: Real32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        step: Option<Real32>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        units: String,
+        var: String,
+    },
+    #[serde(rename = "spacer")]
+    SpacerVariant {
+        #[serde(rename = "min-size")]
+        #[serde(default, skip_serializing_if = "is_default")]
+        min_size: u16,
+    },
+    #[serde(rename = "stack")]
+    StackVariant {
+        #[serde(default, rename = "h-alignment")]
+        h_alignment: HAlignment,
+        #[serde(default, rename = "v-alignment")]
+        v_alignment: VAlignment,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        widgets: Vec<Widget>,
+    },
+    #[serde(rename = "styled")]
+    StyledVariant { style: Style, widget: Box<Widget> },
+    #[serde(rename = "stepper")]
+    StepperVariant {
+        #[serde(rename = "initial-number")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_number: Option<Real32>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        integer: bool,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<Real32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<Real32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        step: Option<Real32>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        units: String,
+        var: String,
+    },
     #[serde(rename = "textfield")]
     TextfieldVariant {
         #[serde(default, skip_serializing_if = "is_default")]
@@ -174,10 +409,10 @@ pub enum Widget {
         #[serde(default, skip_serializing_if = "String::is_empty")]
         label: String,
         #[serde(rename = "max-chars")]
-        #[serde(default, skip_serializing_if = "is_u32_max")]
+        #[serde(default = "u32_max", skip_serializing_if = "is_u32_max")]
         max_chars: u32,
         #[serde(rename = "max-lines")]
-        #[serde(default, skip_serializing_if = "is_u32_max")]
+        #[serde(default = "u32_max", skip_serializing_if = "is_u32_max")]
         max_lines: u32,
         #[serde(rename = "min-chars")]
         #[serde(default, skip_serializing_if = "is_default")]
@@ -188,6 +423,23 @@ pub enum Widget {
     },
     #[serde(rename = "text")]
     TextVariant { text: String },
+    #[serde(rename = "time-picker")]
+    TimePickerVariant {
+        #[serde(rename = "initial-time")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_time: Option<Time>,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        label: String,
+        #[serde(rename = "max-time")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_time: Option<Time>,
+        #[serde(rename = "min-time")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_time: Option<Time>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rpc: Option<String>,
+        var: String,
+    },
 }
 impl Widget {
     #[must_use]
@@ -195,6 +447,284 @@ impl Widget {
     pub fn to_value(&self) -> Value {
         serde_json::to_value(self).unwrap()
     }
+
+    /// Returns the first protocol version that supports this widget type.
+    #[must_use]
+    pub fn min_protocol_version(&self) -> u32 {
+        match self {
+            Widget::BackButtonVariant { .. }
+            | Widget::ButtonVariant { .. }
+            | Widget::CheckboxVariant { .. }
+            | Widget::ColumnVariant { .. }
+            | Widget::EmptyVariant
+            | Widget::ErrorDetailsVariant
+            | Widget::ErrorTextVariant { .. }
+            | Widget::FormVariant { .. }
+            | Widget::FormButtonVariant { .. }
+            | Widget::FormSectionVariant { .. }
+            | Widget::GroupedRowTableVariant { .. }
+            | Widget::ImageVariant { .. }
+            | Widget::ModalButtonVariant { .. }
+            | Widget::NavButtonVariant { .. }
+            | Widget::ScrollVariant { .. }
+            | Widget::TextfieldVariant { .. }
+            | Widget::TextVariant { .. } => 1,
+            Widget::DatePickerVariant { .. }
+            | Widget::DateRangePickerVariant { .. }
+            | Widget::FrameVariant { .. }
+            | Widget::IconVariant { .. }
+            | Widget::LazyListVariant { .. }
+            | Widget::MultiSelectVariant { .. }
+            | Widget::NumberFieldVariant { .. }
+            | Widget::PaddingVariant { .. }
+            | Widget::PickerVariant { .. }
+            | Widget::ProgressBarVariant { .. }
+            | Widget::RadioGroupVariant { .. }
+            | Widget::RichTextVariant { .. }
+            | Widget::RowVariant { .. }
+            | Widget::SliderVariant { .. }
+            | Widget::SpacerVariant { .. }
+            | Widget::StackVariant { .. }
+            | Widget::StepperVariant { .. }
+            | Widget::StyledVariant { .. }
+            | Widget::TimePickerVariant { .. } => 2,
+        }
+    }
+
+    /// Returns the widget's direct children.
+    #[must_use]
+    pub fn children(&self) -> Vec<&Widget> {
+        match self {
+            Widget::ColumnVariant { widgets, .. }
+            | Widget::FormVariant { widgets }
+            | Widget::FormSectionVariant { widgets, .. }
+            | Widget::LazyListVariant { widgets, .. }
+            | Widget::RowVariant { widgets, .. }
+            | Widget::StackVariant { widgets, .. } => widgets.iter().collect(),
+            Widget::GroupedRowTableVariant { row_groups, .. } => row_groups
+                .iter()
+                .flatten()
+                .flatten()
+                .filter_map(Option::as_ref)
+                .collect(),
+            Widget::FrameVariant { widget, .. }
+            | Widget::PaddingVariant { widget, .. }
+            | Widget::ScrollVariant { widget }
+            | Widget::StyledVariant { widget, .. } => vec![widget],
+            Widget::BackButtonVariant { .. }
+            | Widget::ButtonVariant { .. }
+            | Widget::CheckboxVariant { .. }
+            | Widget::DatePickerVariant { .. }
+            | Widget::DateRangePickerVariant { .. }
+            | Widget::EmptyVariant
+            | Widget::ErrorDetailsVariant
+            | Widget::ErrorTextVariant { .. }
+            | Widget::FormButtonVariant { .. }
+            | Widget::IconVariant { .. }
+            | Widget::ImageVariant { .. }
+            | Widget::ModalButtonVariant { .. }
+            | Widget::MultiSelectVariant { .. }
+            | Widget::NavButtonVariant { .. }
+            | Widget::NumberFieldVariant { .. }
+            | Widget::PickerVariant { .. }
+            | Widget::ProgressBarVariant { .. }
+            | Widget::RadioGroupVariant { .. }
+            | Widget::RichTextVariant { .. }
+            | Widget::SliderVariant { .. }
+            | Widget::SpacerVariant { .. }
+            | Widget::StepperVariant { .. }
+            | Widget::TextfieldVariant { .. }
+            | Widget::TextVariant { .. }
+            | Widget::TimePickerVariant { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the widget's direct children.
+    #[must_use]
+    pub fn children_mut(&mut self) -> Vec<&mut Widget> {
+        match self {
+            Widget::ColumnVariant { widgets, .. }
+            | Widget::FormVariant { widgets }
+            | Widget::FormSectionVariant { widgets, .. }
+            | Widget::LazyListVariant { widgets, .. }
+            | Widget::RowVariant { widgets, .. }
+            | Widget::StackVariant { widgets, .. } => widgets.iter_mut().collect(),
+            Widget::GroupedRowTableVariant { row_groups, .. } => row_groups
+                .iter_mut()
+                .flatten()
+                .flatten()
+                .filter_map(Option::as_mut)
+                .collect(),
+            Widget::FrameVariant { widget, .. }
+            | Widget::PaddingVariant { widget, .. }
+            | Widget::ScrollVariant { widget }
+            | Widget::StyledVariant { widget, .. } => vec![widget],
+            Widget::BackButtonVariant { .. }
+            | Widget::ButtonVariant { .. }
+            | Widget::CheckboxVariant { .. }
+            | Widget::DatePickerVariant { .. }
+            | Widget::DateRangePickerVariant { .. }
+            | Widget::EmptyVariant
+            | Widget::ErrorDetailsVariant
+            | Widget::ErrorTextVariant { .. }
+            | Widget::FormButtonVariant { .. }
+            | Widget::IconVariant { .. }
+            | Widget::ImageVariant { .. }
+            | Widget::ModalButtonVariant { .. }
+            | Widget::MultiSelectVariant { .. }
+            | Widget::NavButtonVariant { .. }
+            | Widget::NumberFieldVariant { .. }
+            | Widget::PickerVariant { .. }
+            | Widget::ProgressBarVariant { .. }
+            | Widget::RadioGroupVariant { .. }
+            | Widget::RichTextVariant { .. }
+            | Widget::SliderVariant { .. }
+            | Widget::SpacerVariant { .. }
+            | Widget::StepperVariant { .. }
+            | Widget::TextfieldVariant { .. }
+            | Widget::TextVariant { .. }
+            | Widget::TimePickerVariant { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the names of the variables that the widget and its descendants set.
+    #[must_use]
+    pub fn vars(&self) -> Vec<&str> {
+        let mut vars: Vec<&str> = self.var().into_iter().collect();
+        for child in self.children() {
+            vars.extend(child.vars());
+        }
+        vars
+    }
+
+    /// Returns the name of the variable that the widget sets, if any.
+    #[must_use]
+    pub fn var(&self) -> Option<&str> {
+        match self {
+            Widget::CheckboxVariant { var, .. }
+            | Widget::DatePickerVariant { var, .. }
+            | Widget::DateRangePickerVariant { var, .. }
+            | Widget::MultiSelectVariant { var, .. }
+            | Widget::NumberFieldVariant { var, .. }
+            | Widget::PickerVariant { var, .. }
+            | Widget::RadioGroupVariant { var, .. }
+            | Widget::SliderVariant { var, .. }
+            | Widget::StepperVariant { var, .. }
+            | Widget::TextfieldVariant { var, .. }
+            | Widget::TimePickerVariant { var, .. } => Some(var),
+            Widget::BackButtonVariant { .. }
+            | Widget::ButtonVariant { .. }
+            | Widget::ColumnVariant { .. }
+            | Widget::EmptyVariant
+            | Widget::ErrorDetailsVariant
+            | Widget::ErrorTextVariant { .. }
+            | Widget::FormVariant { .. }
+            | Widget::FormButtonVariant { .. }
+            | Widget::FormSectionVariant { .. }
+            | Widget::FrameVariant { .. }
+            | Widget::GroupedRowTableVariant { .. }
+            | Widget::IconVariant { .. }
+            | Widget::ImageVariant { .. }
+            | Widget::LazyListVariant { .. }
+            | Widget::ModalButtonVariant { .. }
+            | Widget::NavButtonVariant { .. }
+            | Widget::PaddingVariant { .. }
+            | Widget::ProgressBarVariant { .. }
+            | Widget::RichTextVariant { .. }
+            | Widget::RowVariant { .. }
+            | Widget::ScrollVariant { .. }
+            | Widget::SpacerVariant { .. }
+            | Widget::StackVariant { .. }
+            | Widget::StyledVariant { .. }
+            | Widget::TextVariant { .. } => None,
+        }
+    }
+
+    /// Replaces this widget and its descendants that `protocol_version` does not support
+    /// with text asking the user to update the app.
+    /// Buttons lose their icons, and their actions become `nothing` when the client
+    /// does not support one of them.
+    /// Clients that do not support styles, frames, or padding get the inner widget.
+    /// Clients that do not support rows, stacks, or lazy lists get a column
+    /// with the same widgets.
+    pub fn downgrade(&mut self, protocol_version: u32) {
+        if protocol_version < self.min_protocol_version() {
+            match self {
+                Widget::FrameVariant { widget, .. }
+                | Widget::PaddingVariant { widget, .. }
+                | Widget::StyledVariant { widget, .. } => {
+                    *self = std::mem::take(widget.as_mut());
+                    self.downgrade(protocol_version);
+                    return;
+                }
+                Widget::LazyListVariant {
+                    spacing, widgets, ..
+                }
+                | Widget::RowVariant {
+                    spacing, widgets, ..
+                } => {
+                    *self = Widget::ColumnVariant {
+                        h_alignment: HAlignment::Start,
+                        spacing: *spacing,
+                        widgets: std::mem::take(widgets),
+                    };
+                    self.downgrade(protocol_version);
+                    return;
+                }
+                Widget::StackVariant { widgets, .. } => {
+                    *self = Widget::ColumnVariant {
+                        h_alignment: HAlignment::Start,
+                        spacing: 0,
+                        widgets: std::mem::take(widgets),
+                    };
+                    self.downgrade(protocol_version);
+                    return;
+                }
+                Widget::ProgressBarVariant { percent, text } => {
+                    let text = match (*percent, text.is_empty()) {
+                        (Some(percent), true) => format!("{percent}%"),
+                        (Some(percent), false) => format!("{text} {percent}%"),
+                        (None, _) => std::mem::take(text),
+                    };
+                    *self = Widget::TextVariant { text };
+                    return;
+                }
+                // Icons and spacers decorate other widgets, so old clients can skip them.
+                Widget::IconVariant { .. } | Widget::SpacerVariant { .. } => {
+                    *self = Widget::EmptyVariant;
+                    return;
+                }
+                _ => {}
+            }
+            *self = Widget::TextVariant {
+                text: UPDATE_APP_TEXT.to_string(),
+            };
+        } else {
+            self.downgrade_fields(protocol_version);
+            for child in self.children_mut() {
+                child.downgrade(protocol_version);
+            }
+        }
+    }
+
+    /// Removes the fields and actions that `protocol_version` does not support.
+    fn downgrade_fields(&mut self, protocol_version: u32) {
+        match self {
+            Widget::ButtonVariant { actions, icon, .. }
+            | Widget::FormButtonVariant { actions, icon, .. }
+            | Widget::NavButtonVariant { actions, icon, .. } => {
+                // Version 2 added button icons.
+                if protocol_version < 2 {
+                    *icon = None;
+                }
+                downgrade_actions(actions, protocol_version);
+            }
+            Widget::BackButtonVariant { actions } | Widget::ModalButtonVariant { actions, .. } => {
+                downgrade_actions(actions, protocol_version);
+            }
+            _ => {}
+        }
+    }
 }
 impl From<Widget> for Value {
     fn from(src: Widget) -> Self {
@@ -0,0 +1,134 @@
+//! Lists that load more rows as the user scrolls.
+//!
+//! A page shows a [`LazyList`] with the first rows.
+//! When the user scrolls to the end, the client asks a [`LazyListLoader`] for more.
+use crate::error::{client_error, server_error};
+use crate::internal::Chunk;
+use crate::session::ApplinSession;
+use crate::widget::LazyList;
+use core::fmt::{Debug, Formatter};
+use serde::Deserialize;
+use servlin::{Request, Response};
+use std::sync::Arc;
+
+#[allow(clippy::module_name_repetitions)]
+pub type LoadMoreFn<T> = dyn 'static
+    + Send
+    + Sync
+    + Fn(&Arc<ApplinSession<T>>, &str) -> Result<Chunk, Box<dyn std::error::Error>>;
+
+/// The body of the client's request for more rows.
+#[derive(Deserialize)]
+struct LoadMoreRequest {
+    cursor: String,
+}
+
+/// Answers a lazy list's requests for more rows.
+///
+/// Make lists with [`LazyListLoader::list`]
+/// and route POST requests for the loader's path to [`LazyListLoader::handle`].
+///
+/// ```
+/// use applin::internal::{Chunk, Widget};
+/// use applin::lazy_list::LazyListLoader;
+/// use applin::widget::Text;
+///
+/// fn orders(start: usize) -> Chunk {
+///     let end = (start + 50).min(1000);
+///     let chunk = Chunk::new(
+///         (start..end)
+///             .map(|n| Text::new(format!("Order {n}")).into())
+///             .collect::<Vec<Widget>>(),
+///     );
+///     if end < 1000 {
+///         chunk.with_next_cursor(end.to_string())
+///     } else {
+///         chunk
+///     }
+/// }
+///
+/// let loader: LazyListLoader<()> = LazyListLoader::new("/orders/more", |_session, cursor| {
+///     Ok(orders(cursor.parse()?))
+/// });
+/// let list = loader.list(orders(0));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct LazyListLoader<T> {
+    load_more: Box<LoadMoreFn<T>>,
+    path: String,
+}
+impl<T: 'static + Send + Sync> LazyListLoader<T> {
+    /// Makes a loader that answers requests for `path` by calling `load_more`
+    /// with the cursor from the previous chunk.
+    ///
+    /// # Panics
+    /// Panics when `path` does not start with `/`.
+    #[must_use]
+    pub fn new(
+        path: impl Into<String>,
+        load_more: impl 'static
+            + Send
+            + Sync
+            + Fn(&Arc<ApplinSession<T>>, &str) -> Result<Chunk, Box<dyn std::error::Error>>,
+    ) -> Self {
+        let path = path.into();
+        assert!(path.starts_with('/'), "lazy list path must start with '/'");
+        Self {
+            load_more: Box::new(load_more),
+            path,
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Makes a list that shows `first_chunk` and loads the rest from this loader.
+    #[must_use]
+    pub fn list(&self, first_chunk: Chunk) -> LazyList {
+        LazyList::new(self.path.clone(), first_chunk)
+    }
+
+    /// Calls the `load_more` function with the request's cursor
+    /// and responds with the chunk.
+    /// In debug builds, this validates the chunk's widgets and prints any problems.
+    ///
+    /// # Errors
+    /// Returns an error response when the path is not the loader's path,
+    /// the request has no cursor, or the `load_more` function fails.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn handle(
+        &self,
+        session: &Arc<ApplinSession<T>>,
+        req: &Request,
+    ) -> Result<Response, Response> {
+        if req.url.path() != self.path {
+            return Err(Response::not_found_404());
+        }
+        let request: LoadMoreRequest = req
+            .json()
+            .map_err(|_| client_error("expected a JSON object with a cursor"))?;
+        let mut chunk = (self.load_more)(session, &request.cursor)
+            .map_err(|e| server_error(format!("error loading {:?}: {e}", self.path)))?;
+        if cfg!(debug_assertions) {
+            for (n, widget) in chunk.widgets.iter().enumerate() {
+                for diagnostic in widget.validate() {
+                    println!("WARN lazy list {:?} row {n} {diagnostic}", self.path);
+                }
+            }
+        }
+        let protocol_version = session.client_info().protocol_version;
+        for widget in &mut chunk.widgets {
+            widget.downgrade(protocol_version);
+        }
+        Ok(Response::json(200, chunk.to_value())
+            .unwrap()
+            .with_no_store())
+    }
+}
+impl<T> Debug for LazyListLoader<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "LazyListLoader{{path={:?}}}", self.path)
+    }
+}
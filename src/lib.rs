@@ -10,6 +10,7 @@
 //! Use an Applin client to access the app:
 //! - [applin-ios](https://github.com/mleonhard/applin-ios)
 //! - applin-android (Coming soon!)
+//! - The web client in [`web_client`], for development and demos
 //!
 //! # Features
 //! - `forbid(unsafe_code)`
@@ -197,10 +198,8 @@
 //! - Send keepalives.
 //! - Session: Schedule only one worker at a time per session.
 //! - Session: When not in an RPC, when building and an error or panic occurs, disconnect.
-//! - Action to refresh an image
 //! - Server to push refresh an image
 //! - Fail build on key collision, for large projects.
-//! - Allow customizing widget style.  See https://github.com/Lona/Lona .
 //! - Start Android implementation
 //!    - https://github.com/flipkart-incubator/proteus
 #![forbid(unsafe_code)]
@@ -208,9 +207,19 @@
 pub mod action;
 pub mod data;
 pub mod error;
+pub mod image_store;
 pub mod internal;
+pub mod lazy_list;
+pub mod preview;
+pub mod rules;
 pub mod session;
+pub mod style;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod upload;
+pub mod web_client;
 pub mod widget;
+pub mod wizard;
 
 pub mod reexports {
     pub use serde_json;
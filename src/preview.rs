@@ -0,0 +1,1114 @@
+//! Renders pages to static HTML, so you can see them without a phone.
+//!
+//! The HTML approximates the iOS client's layout.
+//! Buttons show their actions.
+use crate::error::{client_error, server_error};
+use crate::internal::{Action, ImageDisposition, Page, Tab, Widget};
+use crate::session::ApplinSession;
+use crate::style::{Style, Theme};
+use crate::widget::{Choice, DateRange, HAlignment, Real32, SheetDetent, Span, VAlignment};
+use serde_json::Value;
+use servlin::{Request, Response};
+use std::fmt::{Display, Write};
+use std::sync::Arc;
+
+const CSS: &str = include_str!("preview.css");
+
+fn h_flex(h_alignment: &HAlignment) -> &'static str {
+    match h_alignment {
+        HAlignment::Start => "flex-start",
+        HAlignment::Center => "center",
+        HAlignment::End => "flex-end",
+    }
+}
+
+fn v_flex(v_alignment: &VAlignment) -> &'static str {
+    match v_alignment {
+        VAlignment::Top => "flex-start",
+        VAlignment::Center => "center",
+        VAlignment::Bottom => "flex-end",
+    }
+}
+
+fn css_color(color: &str) -> String {
+    if color.starts_with('#') {
+        escape_html(color)
+    } else {
+        format!("var(--color-{})", escape_html(color))
+    }
+}
+
+/// Returns CSS custom property declarations for the theme's light-mode tokens,
+/// like `--color-accent: #007aff; --radius-small: 4px;`.
+#[must_use]
+pub fn theme_css_vars(theme: &Theme) -> String {
+    let mut out = String::new();
+    for (name, color) in &theme.colors {
+        let _ = write!(out, "--color-{name}: {}; ", color.light);
+    }
+    for (name, radius) in &theme.radii {
+        let _ = write!(out, "--radius-{name}: {radius}px; ");
+    }
+    for (name, size) in &theme.spacing {
+        let _ = write!(out, "--spacing-{name}: {size}px; ");
+    }
+    for (name, text_style) in &theme.text_styles {
+        let _ = write!(
+            out,
+            "--text-{name}-size: {}px; --text-{name}-weight: {}; ",
+            text_style.size,
+            text_style.weight.number()
+        );
+    }
+    escape_html(out.trim_end())
+}
+
+#[must_use]
+pub fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encodes the characters that could end a CSS `url('...')` value.
+#[must_use]
+pub fn escape_css_url(url: &str) -> String {
+    let mut out = String::with_capacity(url.len());
+    for c in url.chars() {
+        if matches!(c, '\'' | '"' | '(' | ')' | '\\') || c.is_whitespace() || c.is_control() {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                let _ = write!(out, "%{b:02X}");
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+struct Renderer<'x> {
+    link_prefix: Option<&'x str>,
+    out: String,
+}
+impl Renderer<'_> {
+    fn actions(&mut self, actions: &[Action]) {
+        if actions.is_empty() {
+            return;
+        }
+        self.out.push_str("<span class=\"actions\">");
+        for (n, action) in actions.iter().enumerate() {
+            if n > 0 {
+                self.out.push_str(", ");
+            }
+            let action_string = match action.to_value() {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+            match (action, self.link_prefix) {
+                (Action::Push(key), Some(prefix)) => {
+                    let _ = write!(
+                        self.out,
+                        "<a href=\"{}\">{}</a>",
+                        escape_html(&format!("{prefix}{key}")),
+                        escape_html(&action_string)
+                    );
+                }
+                _ => self.out.push_str(&escape_html(&action_string)),
+            }
+        }
+        self.out.push_str("</span>");
+    }
+
+    fn button(&mut self, class: &str, icon: Option<&String>, text: &str, actions: &[Action]) {
+        let _ = write!(self.out, "<button class=\"{class}\">");
+        if let Some(name) = icon {
+            self.icon(name, 0);
+        }
+        self.out.push_str(&escape_html(text));
+        self.actions(actions);
+        self.out.push_str("</button>");
+    }
+
+    /// Writes the icon's name, since previews do not have the icon set.
+    fn icon(&mut self, name: &str, size: u16) {
+        if size == 0 {
+            let _ = write!(
+                self.out,
+                "<span class=\"icon\">{}</span>",
+                escape_html(name)
+            );
+        } else {
+            let _ = write!(
+                self.out,
+                "<span class=\"icon\" style=\"font-size: {size}px;\">{}</span>",
+                escape_html(name)
+            );
+        }
+    }
+
+    /// Writes an input with optional value, bounds, and step.
+    fn input<V: Display>(
+        &mut self,
+        typ: &str,
+        value: Option<V>,
+        min: Option<V>,
+        max: Option<V>,
+        step: Option<V>,
+    ) {
+        let _ = write!(self.out, "<input type=\"{typ}\"");
+        for (name, opt) in [("value", value), ("min", min), ("max", max), ("step", step)] {
+            if let Some(v) = opt {
+                let _ = write!(self.out, " {name}=\"{v}\"");
+            }
+        }
+        self.out.push('>');
+    }
+
+    fn number_input(
+        &mut self,
+        typ: &str,
+        value: Option<&Real32>,
+        integer: bool,
+        min: Option<&Real32>,
+        max: Option<&Real32>,
+        step: Option<&Real32>,
+    ) {
+        let get = |opt: Option<&Real32>| opt.cloned().map(Real32::get);
+        let step = get(step).or(integer.then_some(1.0));
+        self.input(typ, get(value), get(min), get(max), step);
+    }
+
+    fn spans(&mut self, spans: &[Span]) {
+        for span in spans {
+            let tag = if span.actions.is_empty() { "span" } else { "a" };
+            let _ = write!(self.out, "<{tag}");
+            if !span.actions.is_empty() {
+                let titles: Vec<String> = span
+                    .actions
+                    .iter()
+                    .map(|action| action.to_value().as_str().unwrap_or_default().to_string())
+                    .collect();
+                let _ = write!(
+                    self.out,
+                    " class=\"link\" title=\"{}\"",
+                    escape_html(&titles.join(", "))
+                );
+                if let (Some(Action::Push(key)), Some(prefix)) =
+                    (span.actions.first(), self.link_prefix)
+                {
+                    let _ = write!(
+                        self.out,
+                        " href=\"{}\"",
+                        escape_html(&format!("{prefix}{key}"))
+                    );
+                }
+            }
+            self.out.push_str(" style=\"");
+            for (enabled, css) in [
+                (span.bold, "font-weight: bold; "),
+                (span.italic, "font-style: italic; "),
+                (span.monospace, "font-family: monospace; "),
+                (span.strikethrough, "text-decoration: line-through; "),
+            ] {
+                if enabled {
+                    self.out.push_str(css);
+                }
+            }
+            if let Some(color) = &span.color {
+                let _ = write!(self.out, "color: {}; ", css_color(color));
+            }
+            let _ = write!(self.out, "\">{}", escape_html(&span.text));
+            self.spans(&span.spans);
+            let _ = write!(self.out, "</{tag}>");
+        }
+    }
+
+    fn style(&mut self, style: &Style) {
+        if let Some(color) = &style.color {
+            let _ = write!(self.out, "color: {}; ", css_color(color));
+        }
+        if let Some(color) = &style.background {
+            let _ = write!(self.out, "background: {}; ", css_color(color));
+        }
+        if let Some(radius) = &style.corner_radius {
+            let _ = write!(
+                self.out,
+                "border-radius: var(--radius-{}); ",
+                escape_html(radius)
+            );
+        }
+        if let Some(spacing) = &style.padding {
+            let _ = write!(
+                self.out,
+                "padding: var(--spacing-{}); ",
+                escape_html(spacing)
+            );
+        }
+        if let Some(name) = &style.text_style {
+            let name = escape_html(name);
+            let _ = write!(
+                self.out,
+                "font-size: var(--text-{name}-size); font-weight: var(--text-{name}-weight); "
+            );
+        }
+    }
+
+    fn units(&mut self, units: &str) {
+        if !units.is_empty() {
+            let _ = write!(
+                self.out,
+                " <span class=\"units\">{}</span>",
+                escape_html(units)
+            );
+        }
+    }
+
+    fn picker_start(&mut self, var: &str, label: &str) {
+        let _ = write!(
+            self.out,
+            "<div class=\"picker\" title=\"var {}\">",
+            escape_html(var)
+        );
+        if !label.is_empty() {
+            let _ = write!(self.out, "<label>{}</label>", escape_html(label));
+        }
+    }
+
+    fn picker_end(&mut self, rpc: Option<&String>) {
+        if let Some(rpc) = rpc {
+            self.actions(&[Action::Rpc(rpc.clone())]);
+        }
+        self.out.push_str("</div>");
+    }
+
+    /// Writes a radio button or checkbox for each option.
+    fn choices(&mut self, typ: &str, var: &str, options: &[Choice], chosen: &[&String]) {
+        for option in options {
+            let _ = write!(
+                self.out,
+                "<label class=\"choice\"><input type=\"{typ}\" name=\"{}\" value=\"{}\"{}> {}</label>",
+                escape_html(var),
+                escape_html(&option.id),
+                if chosen.contains(&&option.id) {
+                    " checked"
+                } else {
+                    ""
+                },
+                escape_html(&option.text)
+            );
+        }
+    }
+
+    fn widgets(&mut self, widgets: &[Widget]) {
+        for widget in widgets {
+            self.widget(widget);
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn widget(&mut self, widget: &Widget) {
+        match widget {
+            Widget::BackButtonVariant { actions } => {
+                self.button("button", None, "‹ Back", actions);
+            }
+            Widget::ButtonVariant {
+                text,
+                actions,
+                icon,
+            } => self.button("button", icon.as_ref(), text, actions),
+            Widget::CheckboxVariant {
+                var,
+                initial_bool,
+                rpc,
+                text,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<label class=\"checkbox\" title=\"var {}\"><input type=\"checkbox\"{}> {}",
+                    escape_html(var),
+                    if *initial_bool { " checked" } else { "" },
+                    escape_html(text)
+                );
+                if let Some(rpc) = rpc {
+                    self.actions(&[Action::Rpc(rpc.clone())]);
+                }
+                self.out.push_str("</label>");
+            }
+            Widget::ColumnVariant {
+                h_alignment,
+                spacing,
+                widgets,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"column\" style=\"align-items: {}; gap: {spacing}px;\">",
+                    h_flex(h_alignment)
+                );
+                self.widgets(widgets);
+                self.out.push_str("</div>");
+            }
+            Widget::DatePickerVariant {
+                initial_date,
+                label,
+                max_date,
+                min_date,
+                rpc,
+                var,
+            } => {
+                self.picker_start(var, label);
+                self.input("date", *initial_date, *min_date, *max_date, None);
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::DateRangePickerVariant {
+                initial_range,
+                label,
+                max_date,
+                min_date,
+                rpc,
+                var,
+            } => {
+                self.picker_start(var, label);
+                self.input(
+                    "date",
+                    initial_range.map(DateRange::start),
+                    *min_date,
+                    *max_date,
+                    None,
+                );
+                self.out.push_str(" – ");
+                self.input(
+                    "date",
+                    initial_range.map(DateRange::end),
+                    *min_date,
+                    *max_date,
+                    None,
+                );
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::EmptyVariant => {}
+            Widget::ErrorDetailsVariant => self
+                .out
+                .push_str("<div class=\"error-details\">(error details)</div>"),
+            Widget::ErrorTextVariant { text } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"text error-text\">⚠️ {}</div>",
+                    escape_html(text)
+                );
+            }
+            Widget::FormVariant { widgets } => {
+                self.out.push_str("<div class=\"form\">");
+                self.widgets(widgets);
+                self.out.push_str("</div>");
+            }
+            Widget::FormButtonVariant {
+                actions,
+                icon,
+                is_destructive,
+                text,
+            } => {
+                let class = if *is_destructive {
+                    "button destructive"
+                } else {
+                    "button"
+                };
+                self.button(class, icon.as_ref(), text, actions);
+            }
+            Widget::FormSectionVariant { title, widgets } => {
+                self.out.push_str("<div class=\"form-section\">");
+                if let Some(title) = title {
+                    let _ = write!(
+                        self.out,
+                        "<div class=\"section-title\">{}</div>",
+                        escape_html(title)
+                    );
+                }
+                self.widgets(widgets);
+                self.out.push_str("</div>");
+            }
+            Widget::StyledVariant { style, widget } => {
+                self.out.push_str("<div class=\"styled\" style=\"");
+                self.style(style);
+                self.out.push_str("\">");
+                self.widget(widget);
+                self.out.push_str("</div>");
+            }
+            Widget::FrameVariant {
+                max_height,
+                max_width,
+                min_height,
+                min_width,
+                weight,
+                widget,
+            } => {
+                self.out.push_str("<div class=\"frame\" style=\"");
+                for (name, value) in [
+                    ("max-height", max_height),
+                    ("max-width", max_width),
+                    ("min-height", min_height),
+                    ("min-width", min_width),
+                ] {
+                    if let Some(value) = value {
+                        let _ = write!(self.out, "{name}: {value}px; ");
+                    }
+                }
+                if *weight > 0 {
+                    let _ = write!(self.out, "flex: {weight} 1 0;");
+                }
+                self.out.push_str("\">");
+                self.widget(widget);
+                self.out.push_str("</div>");
+            }
+            Widget::GroupedRowTableVariant {
+                row_groups,
+                spacing,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<table class=\"grouped-row-table\" style=\"border-spacing: {spacing}px;\">"
+                );
+                for row_group in row_groups {
+                    self.out.push_str("<tbody>");
+                    for row in row_group {
+                        self.out.push_str("<tr>");
+                        for cell in row {
+                            self.out.push_str("<td>");
+                            if let Some(widget) = cell {
+                                self.widget(widget);
+                            }
+                            self.out.push_str("</td>");
+                        }
+                        self.out.push_str("</tr>");
+                    }
+                    self.out.push_str("</tbody>");
+                }
+                self.out.push_str("</table>");
+            }
+            Widget::IconVariant { name, size } => self.icon(name, *size),
+            Widget::ImageVariant {
+                aspect_ratio,
+                disposition,
+                url,
+            } => {
+                let size = match disposition {
+                    ImageDisposition::Cover => "cover",
+                    ImageDisposition::Fit => "contain",
+                    ImageDisposition::Stretch => "100% 100%",
+                };
+                let _ = write!(
+                    self.out,
+                    "<div class=\"image\" style=\"aspect-ratio: {}; background-image: url('{}'); background-size: {size};\"></div>",
+                    aspect_ratio.clone().get(),
+                    escape_html(&escape_css_url(url))
+                );
+            }
+            Widget::LazyListVariant {
+                next_cursor,
+                spacing,
+                url,
+                widgets,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"column lazy-list\" style=\"gap: {spacing}px;\" title=\"{}\">",
+                    escape_html(url)
+                );
+                self.widgets(widgets);
+                if next_cursor.is_some() {
+                    self.out
+                        .push_str("<div class=\"lazy-list-loading\">Loading...</div>");
+                }
+                self.out.push_str("</div>");
+            }
+            Widget::ModalButtonVariant {
+                text,
+                is_cancel,
+                is_default,
+                is_destructive,
+                actions,
+            } => {
+                let mut class = "button modal-button".to_string();
+                if *is_default || *is_cancel {
+                    class.push_str(" default");
+                }
+                if *is_destructive {
+                    class.push_str(" destructive");
+                }
+                self.button(&class, None, text, actions);
+            }
+            Widget::MultiSelectVariant {
+                initial_ids,
+                label,
+                options,
+                rpc,
+                var,
+            } => {
+                self.picker_start(var, label);
+                let chosen: Vec<&String> = initial_ids.iter().collect();
+                self.choices("checkbox", var, options, &chosen);
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::NavButtonVariant {
+                text,
+                sub_text,
+                photo_url,
+                icon,
+                actions,
+                badge_text,
+            } => {
+                self.out.push_str("<div class=\"nav-button\">");
+                if let Some(url) = photo_url {
+                    let _ = write!(self.out, "<img src=\"{}\" alt=\"\">", escape_html(url));
+                }
+                if let Some(name) = icon {
+                    self.icon(name, 0);
+                }
+                let _ = write!(self.out, "<div class=\"labels\">{}", escape_html(text));
+                if let Some(sub_text) = sub_text {
+                    let _ = write!(
+                        self.out,
+                        "<div class=\"sub-text\">{}</div>",
+                        escape_html(sub_text)
+                    );
+                }
+                self.actions(actions);
+                self.out.push_str("</div>");
+                if let Some(badge_text) = badge_text {
+                    let _ = write!(
+                        self.out,
+                        "<span class=\"badge\">{}</span>",
+                        escape_html(badge_text)
+                    );
+                }
+                self.out.push_str("<span class=\"chevron\">›</span></div>");
+            }
+            Widget::NumberFieldVariant {
+                initial_number,
+                integer,
+                label,
+                max,
+                min,
+                rpc,
+                units,
+                var,
+            } => {
+                self.picker_start(var, label);
+                self.number_input(
+                    "number",
+                    initial_number.as_ref(),
+                    *integer,
+                    min.as_ref(),
+                    max.as_ref(),
+                    None,
+                );
+                self.units(units);
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::PaddingVariant {
+                bottom,
+                end,
+                start,
+                top,
+                widget,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"padding\" style=\"padding: {top}px {end}px {bottom}px {start}px;\">"
+                );
+                self.widget(widget);
+                self.out.push_str("</div>");
+            }
+            Widget::PickerVariant {
+                initial_id,
+                label,
+                options,
+                rpc,
+                var,
+            } => {
+                self.picker_start(var, label);
+                self.out.push_str("<select>");
+                if initial_id.is_none() {
+                    self.out.push_str("<option selected></option>");
+                }
+                for option in options {
+                    let _ = write!(
+                        self.out,
+                        "<option value=\"{}\"{}>{}</option>",
+                        escape_html(&option.id),
+                        if initial_id.as_ref() == Some(&option.id) {
+                            " selected"
+                        } else {
+                            ""
+                        },
+                        escape_html(&option.text)
+                    );
+                }
+                self.out.push_str("</select>");
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::RadioGroupVariant {
+                initial_id,
+                label,
+                options,
+                rpc,
+                var,
+            } => {
+                self.picker_start(var, label);
+                let chosen: Vec<&String> = initial_id.iter().collect();
+                self.choices("radio", var, options, &chosen);
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::RowVariant {
+                spacing,
+                v_alignment,
+                widgets,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"row\" style=\"align-items: {}; gap: {spacing}px;\">",
+                    v_flex(v_alignment)
+                );
+                self.widgets(widgets);
+                self.out.push_str("</div>");
+            }
+            Widget::ScrollVariant { widget } => {
+                self.out.push_str("<div class=\"scroll\">");
+                self.widget(widget);
+                self.out.push_str("</div>");
+            }
+            Widget::SliderVariant {
+                initial_number,
+                integer,
+                label,
+                max,
+                min,
+                rpc,
+                step,
+                units,
+                var,
+            } => {
+                self.picker_start(var, label);
+                self.number_input(
+                    "range",
+                    initial_number.as_ref(),
+                    *integer,
+                    Some(min),
+                    Some(max),
+                    step.as_ref(),
+                );
+                let value = initial_number.as_ref().unwrap_or(min).clone().get();
+                let _ = write!(self.out, " {value}");
+                self.units(units);
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::ProgressBarVariant { percent, text } => {
+                // Indeterminate bars show a full, striped bar.
+                match percent {
+                    Some(percent) => {
+                        let _ = write!(
+                            self.out,
+                            "<div class=\"progress-bar\"><div class=\"progress\" style=\"width: {percent}%;\"></div></div>"
+                        );
+                    }
+                    None => self.out.push_str(
+                        "<div class=\"progress-bar indeterminate\"><div class=\"progress\"></div></div>",
+                    ),
+                }
+                if !text.is_empty() {
+                    let _ = write!(
+                        self.out,
+                        "<div class=\"progress-text\">{}</div>",
+                        escape_html(text)
+                    );
+                }
+            }
+            Widget::SpacerVariant { min_size } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"spacer\" style=\"flex: 1 0 {min_size}px;\"></div>"
+                );
+            }
+            Widget::StackVariant {
+                h_alignment,
+                v_alignment,
+                widgets,
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"stack\" style=\"justify-items: {}; align-items: {};\">",
+                    h_flex(h_alignment),
+                    v_flex(v_alignment)
+                );
+                self.widgets(widgets);
+                self.out.push_str("</div>");
+            }
+            Widget::StepperVariant {
+                initial_number,
+                label,
+                min,
+                rpc,
+                step,
+                units,
+                var,
+                ..
+            } => {
+                self.picker_start(var, label);
+                let value = initial_number
+                    .as_ref()
+                    .or(min.as_ref())
+                    .map_or(0.0, |n| n.clone().get());
+                let _ = write!(
+                    self.out,
+                    "<span class=\"stepper\"><button>−</button> {value}"
+                );
+                self.units(units);
+                self.out.push_str(" <button>+</button></span>");
+                if let Some(step) = step {
+                    let _ = write!(
+                        self.out,
+                        "<span class=\"note\">step {}</span>",
+                        step.clone().get()
+                    );
+                }
+                self.picker_end(rpc.as_ref());
+            }
+            Widget::TextfieldVariant {
+                error,
+                initial_string,
+                label,
+                max_lines,
+                rpc,
+                var,
+                ..
+            } => {
+                let _ = write!(
+                    self.out,
+                    "<div class=\"textfield\" title=\"var {}\">",
+                    escape_html(var)
+                );
+                if !label.is_empty() {
+                    let _ = write!(self.out, "<label>{}</label>", escape_html(label));
+                }
+                if *max_lines == 1 {
+                    let _ = write!(
+                        self.out,
+                        "<input type=\"text\" value=\"{}\">",
+                        escape_html(initial_string)
+                    );
+                } else {
+                    let _ = write!(
+                        self.out,
+                        "<textarea rows=\"3\">{}</textarea>",
+                        escape_html(initial_string)
+                    );
+                }
+                if !error.is_empty() {
+                    let _ = write!(
+                        self.out,
+                        "<div class=\"error\">⚠️ {}</div>",
+                        escape_html(error)
+                    );
+                }
+                if let Some(rpc) = rpc {
+                    self.actions(&[Action::Rpc(rpc.clone())]);
+                }
+                self.out.push_str("</div>");
+            }
+            Widget::RichTextVariant { spans } => {
+                self.out.push_str("<div class=\"text rich-text\">");
+                self.spans(spans);
+                self.out.push_str("</div>");
+            }
+            Widget::TextVariant { text } => {
+                let _ = write!(self.out, "<div class=\"text\">{}</div>", escape_html(text));
+            }
+            Widget::TimePickerVariant {
+                initial_time,
+                label,
+                max_time,
+                min_time,
+                rpc,
+                var,
+            } => {
+                self.picker_start(var, label);
+                self.input("time", *initial_time, *min_time, *max_time, None);
+                self.picker_end(rpc.as_ref());
+            }
+        }
+    }
+
+    fn update_info(&mut self, poll_seconds: u32, pull_to_refresh: Option<&String>, stream: bool) {
+        if poll_seconds > 0 {
+            let _ = write!(
+                self.out,
+                "<div class=\"update-info\">polls every {poll_seconds} seconds</div>"
+            );
+        }
+        if let Some(rpc_url) = pull_to_refresh {
+            let _ = write!(
+                self.out,
+                "<div class=\"update-info\">pull to refresh calls {}</div>",
+                escape_html(rpc_url)
+            );
+        }
+        if stream {
+            self.out
+                .push_str("<div class=\"update-info\">receives updates by stream</div>");
+        }
+    }
+
+    fn modal(&mut self, class: &str, title: &str, text: Option<&String>, widgets: &[Widget]) {
+        let _ = write!(
+            self.out,
+            "<div class=\"modal-backdrop\"><div class=\"{class}\"><div class=\"title\">{}</div>",
+            escape_html(title)
+        );
+        if let Some(text) = text {
+            let _ = write!(
+                self.out,
+                "<div class=\"modal-text\">{}</div>",
+                escape_html(text)
+            );
+        }
+        self.widgets(widgets);
+        self.out.push_str("</div></div>");
+    }
+
+    fn page(&mut self, page: &Page, theme: &Theme) {
+        let theme = match page.theme() {
+            Some(page_theme) => theme.merged(page_theme),
+            None => theme.clone(),
+        };
+        let _ = write!(
+            self.out,
+            "<div class=\"phone\" style=\"{}\">",
+            theme_css_vars(&theme)
+        );
+        match page {
+            Page::Alert {
+                text,
+                title,
+                widgets,
+            } => self.modal("alert", title, text.as_ref(), widgets),
+            Page::Drawer {
+                text,
+                title,
+                widgets,
+            } => self.modal("drawer", title, text.as_ref(), widgets),
+            Page::Nav {
+                end,
+                poll_seconds,
+                pull_to_refresh,
+                start,
+                stream,
+                theme: _,
+                title,
+                widget,
+            } => {
+                self.out
+                    .push_str("<div class=\"nav-bar\"><div class=\"start\">");
+                if let Some(start) = start {
+                    self.widget(start);
+                }
+                let _ = write!(
+                    self.out,
+                    "</div><div class=\"title\">{}</div><div class=\"end\">",
+                    escape_html(title)
+                );
+                if let Some(end) = end {
+                    self.widget(end);
+                }
+                self.out.push_str("</div></div><div class=\"content\">");
+                self.widget(widget);
+                self.out.push_str("</div>");
+                self.update_info(*poll_seconds, pull_to_refresh.as_ref(), *stream);
+            }
+            Page::Plain {
+                poll_seconds,
+                pull_to_refresh,
+                stream,
+                theme: _,
+                title: _,
+                widget,
+            } => {
+                self.out.push_str("<div class=\"content\">");
+                self.widget(widget);
+                self.out.push_str("</div>");
+                self.update_info(*poll_seconds, pull_to_refresh.as_ref(), *stream);
+            }
+            Page::Sheet {
+                detents,
+                title,
+                widget,
+            } => {
+                let height = match detents.first() {
+                    Some(SheetDetent::Medium) => "50%".to_string(),
+                    Some(SheetDetent::Height(height)) => format!("{height}px"),
+                    Some(SheetDetent::Large) | None => "90%".to_string(),
+                };
+                let _ = write!(
+                    self.out,
+                    "<div class=\"modal-backdrop\"><div class=\"sheet\" style=\"height: {height}\"><div class=\"sheet-grabber\"></div><div class=\"title\">{}</div><div class=\"content\">",
+                    escape_html(title)
+                );
+                self.widget(widget);
+                self.out.push_str("</div></div></div>");
+            }
+            Page::Tab { tabs, .. } => {
+                let selected = page.selected_tab().unwrap_or_default();
+                let _ = write!(
+                    self.out,
+                    "<div class=\"content\"><div class=\"update-info\">selected tab shows {}</div></div>",
+                    escape_html(selected)
+                );
+                self.tab_bar(tabs, selected);
+            }
+        }
+        self.out.push_str("</div>");
+    }
+
+    fn tab_bar(&mut self, tabs: &[Tab], selected: &str) {
+        self.out.push_str("<div class=\"tab-bar\">");
+        for tab in tabs {
+            let class = if tab.page_key == selected {
+                "tab selected"
+            } else {
+                "tab"
+            };
+            match self.link_prefix {
+                Some(prefix) => {
+                    let _ = write!(
+                        self.out,
+                        "<a class=\"{class}\" href=\"{}\">",
+                        escape_html(&format!("{prefix}{}", tab.page_key))
+                    );
+                }
+                None => {
+                    let _ = write!(self.out, "<div class=\"{class}\">");
+                }
+            }
+            if let Some(icon) = &tab.icon {
+                self.out.push_str("<div class=\"tab-icon\">");
+                self.icon(icon, 0);
+                self.out.push_str("</div>");
+            }
+            self.out.push_str(&escape_html(&tab.title));
+            if let Some(badge_text) = &tab.badge_text {
+                let _ = write!(
+                    self.out,
+                    "<span class=\"badge\">{}</span>",
+                    escape_html(badge_text)
+                );
+            }
+            self.out.push_str(if self.link_prefix.is_some() {
+                "</a>"
+            } else {
+                "</div>"
+            });
+        }
+        self.out.push_str("</div>");
+    }
+}
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{CSS}</style></head>\n<body>{body}</body></html>\n",
+        escape_html(title)
+    )
+}
+
+/// Returns an HTML fragment showing the widget.
+/// Push actions link to `{link_prefix}{page_key}` when `link_prefix` is set.
+#[must_use]
+pub fn widget_to_html(widget: &Widget, link_prefix: Option<&str>) -> String {
+    let mut renderer = Renderer {
+        link_prefix,
+        out: String::new(),
+    };
+    renderer.widget(widget);
+    renderer.out
+}
+
+/// Returns an HTML document showing the page with the default theme.
+/// Push actions link to `{link_prefix}{page_key}` when `link_prefix` is set.
+#[must_use]
+pub fn page_to_html(page: &Page, link_prefix: Option<&str>) -> String {
+    page_to_html_with_theme(page, None, link_prefix)
+}
+
+/// Like [`page_to_html`], with `session_theme` tokens replacing the default tokens.
+#[must_use]
+pub fn page_to_html_with_theme(
+    page: &Page,
+    session_theme: Option<&Theme>,
+    link_prefix: Option<&str>,
+) -> String {
+    let theme = match session_theme {
+        Some(session_theme) => Theme::new().merged(session_theme),
+        None => Theme::new(),
+    };
+    let mut renderer = Renderer {
+        link_prefix,
+        out: String::new(),
+    };
+    renderer.page(page, &theme);
+    html_document(page.title(), &renderer.out)
+}
+
+/// Serves previews of every page in the session's `PageMap`.
+///
+/// Route requests for `prefix` and paths starting with `prefix` to this function.
+/// The `prefix` path shows links to every page.
+/// The path `{prefix}{page_key}` shows the page.
+///
+/// This builds pages with `session` without changing the pages that it sends to the client.
+/// Don't serve previews in production.
+///
+/// # Errors
+/// Returns an error when the request path does not start with `prefix`,
+/// the page is not found, or building fails.
+pub fn preview_response<T: 'static + Send + Sync>(
+    session: &Arc<ApplinSession<T>>,
+    prefix: &str,
+    req: &Request,
+) -> Result<Response, Response> {
+    let key = req
+        .url
+        .path()
+        .strip_prefix(prefix)
+        .ok_or_else(|| client_error(format!("path does not start with {prefix:?}")))?;
+    let mut pages = session
+        .build_all_pages()
+        .map_err(|e| server_error(format!("error building pages: {e}")))?;
+    if key.is_empty() {
+        let mut body = String::from("<div class=\"phone index\">");
+        for key in pages.keys() {
+            let _ = write!(
+                body,
+                "<a href=\"{}\">{}</a>",
+                escape_html(&format!("{prefix}{key}")),
+                escape_html(key)
+            );
+        }
+        body.push_str("</div>");
+        return Ok(Response::html(200, html_document("Pages", &body)));
+    }
+    let page = pages
+        .remove(key)
+        .ok_or_else(|| client_error(format!("unknown page key {key:?}")))?;
+    let theme = session.theme();
+    Ok(Response::html(
+        200,
+        page_to_html_with_theme(&page, theme.as_ref(), Some(prefix)),
+    ))
+}
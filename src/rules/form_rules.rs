@@ -0,0 +1,208 @@
+use crate::error::client_error;
+use crate::internal::Widget;
+use crate::rules::Rule;
+use crate::session::ApplinSession;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use servlin::{Request, Response};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Rules for the vars of a form.
+///
+/// Use the same rules to build the page and to handle the RPC:
+/// ```
+/// use applin::rules::{FormRules, Rule};
+/// use applin::widget::{Form, Textfield};
+/// use serde_json::json;
+/// use std::collections::BTreeMap;
+///
+/// fn signup_rules() -> FormRules {
+///     FormRules::new()
+///         .with_rule("name", Rule::required())
+///         .with_rules("email", [Rule::required(), Rule::email()])
+/// }
+///
+/// // In the page function, pass the errors from `session.field_errors("/signup")`.
+/// let errors = BTreeMap::from([("email".to_string(), "Required".to_string())]);
+/// let form = signup_rules().apply(
+///     &errors,
+///     Form::new((Textfield::new("name"), Textfield::new("email"))),
+/// );
+///
+/// // In the RPC handler, call `signup_rules().parse(&session, "/signup", req)?`.
+/// let vars = json!({"name": "Ann", "email": "ann@"});
+/// assert_eq!(
+///     BTreeMap::from([("email".to_string(), "Enter a valid email address".to_string())]),
+///     signup_rules().check(vars.as_object().unwrap()),
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FormRules {
+    fields: Vec<(String, Vec<Rule>)>,
+}
+impl FormRules {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `rule` for `var`.
+    /// Rules run in the order you add them and the first failure becomes the var's error.
+    #[must_use]
+    pub fn with_rule(mut self, var: impl Into<String>, rule: Rule) -> Self {
+        let var = var.into();
+        if let Some((_, rules)) = self.fields.iter_mut().find(|(v, _)| *v == var) {
+            rules.push(rule);
+        } else {
+            self.fields.push((var, vec![rule]));
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn with_rules(
+        mut self,
+        var: impl Into<String>,
+        rules: impl IntoIterator<Item = Rule>,
+    ) -> Self {
+        let var = var.into();
+        for rule in rules {
+            self = self.with_rule(var.clone(), rule);
+        }
+        self
+    }
+
+    pub fn vars(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|(var, _)| var.as_str())
+    }
+
+    #[must_use]
+    pub fn rules(&self, var: &str) -> &[Rule] {
+        self.fields
+            .iter()
+            .find(|(v, _)| v == var)
+            .map_or(&[], |(_, rules)| rules.as_slice())
+    }
+
+    /// Checks the vars that a client sent.
+    /// Returns a map from var name to error message, for vars that failed a rule.
+    #[must_use]
+    pub fn check(&self, vars: &Map<String, Value>) -> BTreeMap<String, String> {
+        let mut errors = BTreeMap::new();
+        for (var, rules) in &self.fields {
+            let value = vars.get(var).unwrap_or(&Value::Null);
+            if let Some(e) = rules.iter().find_map(|rule| rule.check(value).err()) {
+                errors.insert(var.clone(), e);
+            }
+        }
+        errors
+    }
+
+    /// Shows `errors` on the widgets that set the vars:
+    /// - Sets the `error` of textfields.
+    ///   This also sets textfield `min-chars` and `max-chars` from [`Rule::min_chars`] and [`Rule::max_chars`].
+    /// - Adds an [`crate::widget::ErrorText`] after other widgets in columns, forms, and form sections.
+    #[must_use]
+    pub fn apply(&self, errors: &BTreeMap<String, String>, widget: impl Into<Widget>) -> Widget {
+        let mut widget = widget.into();
+        self.apply_to(errors, &mut widget);
+        widget
+    }
+
+    fn apply_to(&self, errors: &BTreeMap<String, String>, widget: &mut Widget) {
+        if let Widget::TextfieldVariant {
+            error,
+            max_chars,
+            min_chars,
+            var,
+            ..
+        } = widget
+        {
+            let rules = self.rules(var);
+            if let Some(n) = rules.iter().find_map(Rule::max_chars_hint) {
+                *max_chars = (*max_chars).min(n);
+            }
+            if let Some(n) = rules.iter().find_map(Rule::min_chars_hint) {
+                *min_chars = (*min_chars).max(n);
+            }
+            if let Some(e) = errors.get(var.as_str()) {
+                error.clone_from(e);
+            }
+            return;
+        }
+        if let Widget::ColumnVariant { widgets, .. }
+        | Widget::FormVariant { widgets }
+        | Widget::FormSectionVariant { widgets, .. } = widget
+        {
+            let mut new_widgets = Vec::with_capacity(widgets.len());
+            for mut child in widgets.drain(..) {
+                self.apply_to(errors, &mut child);
+                let opt_error = match &child {
+                    Widget::TextfieldVariant { .. } => None,
+                    other => other.var().and_then(|var| errors.get(var)).cloned(),
+                };
+                new_widgets.push(child);
+                if let Some(text) = opt_error {
+                    new_widgets.push(Widget::ErrorTextVariant { text });
+                }
+            }
+            *widgets = new_widgets;
+            return;
+        }
+        for child in widget.children_mut() {
+            self.apply_to(errors, child);
+        }
+    }
+
+    /// Checks `vars` and saves the errors in the session as the errors of the page with `page_key`.
+    /// When the errors changed, this rebuilds the page.
+    ///
+    /// # Errors
+    /// When a var fails a rule, returns a `422` response that updates the client's pages,
+    /// so they show the errors.
+    /// The client skips the actions after the RPC.
+    /// See [`FormRules::apply`] and [`ApplinSession::field_errors`].
+    pub fn enforce<T: 'static + Send + Sync>(
+        &self,
+        session: &Arc<ApplinSession<T>>,
+        page_key: &str,
+        vars: &Map<String, Value>,
+    ) -> Result<(), Response> {
+        let errors = self.check(vars);
+        let failed = !errors.is_empty();
+        let mut field_errors = session.field_errors(page_key);
+        for var in self.vars() {
+            field_errors.remove(var);
+        }
+        field_errors.extend(errors);
+        session.set_field_errors(page_key, field_errors, session.rpc_context());
+        if failed {
+            return Err(session
+                .rejected_rpc_response()
+                .unwrap_or_else(|response| response));
+        }
+        Ok(())
+    }
+
+    /// Reads the vars from the request body and calls [`FormRules::enforce`].
+    /// When all vars pass, deserializes them into `V`.
+    ///
+    /// # Errors
+    /// When a var fails a rule, returns a `422` response that updates the client's pages,
+    /// so they show the errors.
+    ///
+    /// Returns an error response when the body is not a JSON object
+    /// or the vars do not deserialize into `V`.
+    pub fn parse<V: DeserializeOwned, T: 'static + Send + Sync>(
+        &self,
+        session: &Arc<ApplinSession<T>>,
+        page_key: &str,
+        req: &Request,
+    ) -> Result<V, Response> {
+        let vars: Map<String, Value> = req.json()?;
+        self.enforce(session, page_key, &vars)?;
+        serde_json::from_value(Value::Object(vars))
+            .map_err(|e| client_error(format!("error parsing vars: {e}")))
+    }
+}
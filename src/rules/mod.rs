@@ -0,0 +1,9 @@
+//! Declarative checks for the vars that forms send.
+//!
+//! Add [`Rule`]s to [`FormRules`], show the errors with [`FormRules::apply`],
+//! and check RPC vars with [`FormRules::parse`].
+mod form_rules;
+mod rule;
+
+pub use form_rules::*;
+pub use rule::*;
@@ -0,0 +1,203 @@
+use crate::widget::NumberRange;
+use core::fmt::{Debug, Formatter};
+use regex::Regex;
+use serde_json::Value;
+use std::sync::Arc;
+
+type CheckFn = dyn Fn(&Value) -> Result<(), String> + Send + Sync;
+
+#[derive(Clone)]
+enum Check {
+    Custom(Arc<CheckFn>),
+    Email,
+    MaxChars(u32),
+    MinChars(u32),
+    Range(NumberRange),
+    Regex(Regex),
+    Required,
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').count() > 1
+                && domain.split('.').all(|part| !part.is_empty())
+                && !s.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// A check on the value that the client sends for a var.
+/// Add rules to [`crate::rules::FormRules`].
+///
+/// Rules other than [`Rule::required`] and [`Rule::custom`] accept missing and empty values.
+#[derive(Clone)]
+pub struct Rule {
+    check: Check,
+    message: Option<String>,
+}
+impl Rule {
+    fn new(check: Check) -> Self {
+        Self {
+            check,
+            message: None,
+        }
+    }
+
+    /// Calls `f` with the value, or `null` when the client did not send the var.
+    #[must_use]
+    pub fn custom(f: impl 'static + Fn(&Value) -> Result<(), String> + Send + Sync) -> Self {
+        Self::new(Check::Custom(Arc::new(f)))
+    }
+
+    /// Accepts strings that look like email addresses.
+    #[must_use]
+    pub fn email() -> Self {
+        Self::new(Check::Email)
+    }
+
+    /// Accepts strings with at most `n` characters.
+    #[must_use]
+    pub fn max_chars(n: u32) -> Self {
+        Self::new(Check::MaxChars(n))
+    }
+
+    /// Accepts strings with at least `n` characters.
+    #[must_use]
+    pub fn min_chars(n: u32) -> Self {
+        Self::new(Check::MinChars(n))
+    }
+
+    /// Accepts numbers that `range` accepts.
+    #[must_use]
+    pub fn range(range: NumberRange) -> Self {
+        Self::new(Check::Range(range))
+    }
+
+    /// Accepts strings that match `pattern`.
+    /// Use `^` and `$` to match the whole string.
+    ///
+    /// # Panics
+    /// Panics when `pattern` is not a valid regular expression.
+    #[must_use]
+    pub fn regex(pattern: &str) -> Self {
+        Self::new(Check::Regex(Regex::new(pattern).unwrap()))
+    }
+
+    /// Rejects missing values, `null`, blank strings, and empty lists.
+    #[must_use]
+    pub fn required() -> Self {
+        Self::new(Check::Required)
+    }
+
+    /// Uses `message` instead of the rule's default error message.
+    #[must_use]
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    #[must_use]
+    pub fn max_chars_hint(&self) -> Option<u32> {
+        match self.check {
+            Check::MaxChars(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn min_chars_hint(&self) -> Option<u32> {
+        match self.check {
+            Check::MinChars(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn check_inner(&self, value: &Value) -> Result<(), String> {
+        if let Check::Custom(f) = &self.check {
+            return f(value);
+        }
+        if is_empty(value) {
+            return if matches!(self.check, Check::Required) {
+                Err("Required".to_string())
+            } else {
+                Ok(())
+            };
+        }
+        let as_str = || value.as_str().ok_or_else(|| "Must be text".to_string());
+        match &self.check {
+            Check::Custom(..) | Check::Required => Ok(()),
+            Check::Email => {
+                if is_email(as_str()?) {
+                    Ok(())
+                } else {
+                    Err("Enter a valid email address".to_string())
+                }
+            }
+            Check::MaxChars(n) => {
+                if as_str()?.chars().count() <= *n as usize {
+                    Ok(())
+                } else {
+                    Err(format!("Enter at most {n} characters"))
+                }
+            }
+            Check::MinChars(n) => {
+                if (*n as usize) <= as_str()?.chars().count() {
+                    Ok(())
+                } else {
+                    Err(format!("Enter at least {n} characters"))
+                }
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            Check::Range(range) => {
+                let n = value.as_f64().ok_or_else(|| "Enter a number".to_string())?;
+                range.check_var("Number", n as f32).map(|_| ())
+            }
+            Check::Regex(regex) => {
+                if regex.is_match(as_str()?) {
+                    Ok(())
+                } else {
+                    Err("Invalid format".to_string())
+                }
+            }
+        }
+    }
+
+    /// Checks `value`, the value of a var, or `null` when the client did not send it.
+    ///
+    /// # Errors
+    /// Returns the error message to show the user.
+    pub fn check(&self, value: &Value) -> Result<(), String> {
+        self.check_inner(value)
+            .map_err(|e| self.message.clone().unwrap_or(e))
+    }
+}
+impl Debug for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match &self.check {
+            Check::Custom(..) => write!(f, "Rule::Custom")?,
+            Check::Email => write!(f, "Rule::Email")?,
+            Check::MaxChars(n) => write!(f, "Rule::MaxChars({n})")?,
+            Check::MinChars(n) => write!(f, "Rule::MinChars({n})")?,
+            Check::Range(range) => write!(f, "Rule::Range({range:?})")?,
+            Check::Regex(regex) => write!(f, "Rule::Regex({:?})", regex.as_str())?,
+            Check::Required => write!(f, "Rule::Required")?,
+        }
+        if let Some(message) = &self.message {
+            write!(f, ".with_message({message:?})")?;
+        }
+        Ok(())
+    }
+}
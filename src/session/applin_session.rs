@@ -1,14 +1,19 @@
 use crate::data::{Context, Rebuilder};
-use crate::error::server_error;
-use crate::session::{PageMap, SessionCookie, SessionId};
+use crate::error::{client_error, server_error};
+use crate::internal::{JobStatus, Page, Toast, Update};
+use crate::session::{
+    ClientInfo, Extensions, Job, PageFn, PageKey, PageMap, SessionCookie, SessionId,
+};
+use crate::style::Theme;
 use core::fmt::{Debug, Formatter};
-use serde_json::{json, Value};
+use serde::Deserialize;
+use serde_json::{Map, Value};
 use servlin::reexport::safina_executor::Executor;
-use servlin::{Event, EventSender, Response};
-use std::collections::HashSet;
+use servlin::{Event, EventSender, Request, Response};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::{Deref, DerefMut};
 use std::sync::atomic::AtomicU64;
-use std::sync::atomic::Ordering::{Acquire, Release};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Release};
 use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
 use std::time::SystemTime;
 
@@ -23,6 +28,7 @@ fn epoch_seconds() -> u64 {
 pub enum PendingUpdate {
     KeySet,
     Key(String),
+    Theme,
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -40,16 +46,49 @@ impl<'x, T> DerefMut for SessionStateGuard<'x, T> {
     }
 }
 
+/// Tab selections, toasts, page pushes, and job statuses waiting for the next update.
+/// The session keeps them in its [`Extensions`].
+#[derive(Debug, Default)]
+pub struct PendingNotifications {
+    /// Tab page key to the key of the tab for the client to select.
+    pub select_tabs: BTreeMap<String, String>,
+    /// Toasts waiting for the next update, oldest first.
+    pub toasts: Vec<Toast>,
+    /// The page key that the next update tells the client to push.
+    pub push: Option<String>,
+    /// Job ID to the job's latest status that the client has not received.
+    pub jobs: BTreeMap<String, JobStatus>,
+}
+
+/// Page key to the errors from the last failed validation of the page's form.
+/// The errors map var name to error message.
+#[derive(Default)]
+struct FieldErrors(HashMap<String, BTreeMap<String, String>>);
+
+/// Tab selections and badges that the server set.
+#[derive(Default)]
+struct TabState {
+    /// Tab page key to the page key of the tab that the server selected.
+    selected: HashMap<String, String>,
+    /// Page key of a tab to the text of its badge.
+    badges: HashMap<String, String>,
+    /// Tab page key to the page keys of its tabs, from the last time we built the page.
+    tabs: HashMap<String, Vec<String>>,
+}
+
 #[allow(clippy::module_name_repetitions)]
 pub struct InnerSession<T> {
     pub page_map: PageMap<T>,
     pub rpc_updates: HashSet<PendingUpdate>,
+    /// Vars waiting for the next update.
+    pub vars: Map<String, Value>,
     pub sender: EventSender,
 }
 
 pub struct ApplinSession<T> {
     pub executor: Weak<Executor>,
     pub cookie: SessionCookie,
+    pub client_info: Mutex<ClientInfo>,
     #[allow(clippy::type_complexity)]
     pub page_map_fn: Box<
         dyn 'static
@@ -58,8 +97,13 @@ pub struct ApplinSession<T> {
             + Fn(Rebuilder<T>) -> Result<PageMap<T>, Box<dyn std::error::Error>>,
     >,
     pub last_contact_epoch_seconds: AtomicU64,
+    pub next_job_id: AtomicU64,
     pub scheduled_updates: Mutex<HashSet<PendingUpdate>>,
     pub value: Mutex<T>,
+    /// State of features like forms, wizards, tabs, toasts, and jobs.
+    /// Never lock `inner` while holding this lock.
+    pub extensions: Mutex<Extensions>,
+    pub theme: Mutex<Option<Theme>>,
     pub inner: Mutex<InnerSession<T>>,
 }
 impl<T: 'static + Send + Sync> ApplinSession<T> {
@@ -73,13 +117,18 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
         Arc::new(Self {
             executor,
             cookie: SessionCookie::new_random(),
+            client_info: Mutex::new(ClientInfo::default()),
             page_map_fn: Box::new(page_map_fn),
             last_contact_epoch_seconds: AtomicU64::new(epoch_seconds()),
+            next_job_id: AtomicU64::new(1),
             scheduled_updates: Mutex::new(HashSet::new()),
             value: Mutex::new(value),
+            extensions: Mutex::new(Extensions::new()),
+            theme: Mutex::new(None),
             inner: Mutex::new(InnerSession {
                 page_map: PageMap::new(),
                 rpc_updates: HashSet::from([PendingUpdate::KeySet]),
+                vars: Map::new(),
                 sender: EventSender::unconnected(),
             }),
         })
@@ -89,6 +138,22 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
         self.cookie.id()
     }
 
+    /// Returns the info from the client's most recent request.
+    #[must_use]
+    pub fn client_info(&self) -> ClientInfo {
+        self.client_info
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn set_client_info(&self, client_info: ClientInfo) {
+        *self
+            .client_info
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = client_info;
+    }
+
     pub fn is_fresh(&self) -> bool {
         epoch_seconds() - self.last_contact_epoch_seconds.load(Acquire) < 120
     }
@@ -112,6 +177,322 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
         SessionStateGuard(self.value.lock().unwrap_or_else(PoisonError::into_inner))
     }
 
+    pub fn lock_extensions(&self) -> MutexGuard<'_, Extensions> {
+        self.extensions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the error message for the var from the last failed validation
+    /// of the form on the page with `page_key`.
+    /// See [`crate::rules::FormRules`].
+    #[must_use]
+    pub fn field_error(&self, page_key: &str, var: &str) -> Option<String> {
+        self.lock_extensions()
+            .get::<FieldErrors>()?
+            .0
+            .get(page_key)
+            .and_then(|errors| errors.get(var))
+            .cloned()
+    }
+
+    /// Returns the errors from the last failed validation of the form on the page with `page_key`.
+    #[must_use]
+    pub fn field_errors(&self, page_key: &str) -> BTreeMap<String, String> {
+        self.lock_extensions()
+            .get::<FieldErrors>()
+            .and_then(|field_errors| field_errors.0.get(page_key))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces the field errors of the page with `page_key`
+    /// and rebuilds the page when they changed.
+    pub fn set_field_errors(
+        self: &Arc<Self>,
+        page_key: &str,
+        errors: BTreeMap<String, String>,
+        ctx: Context,
+    ) {
+        {
+            let mut extensions = self.lock_extensions();
+            let guard = &mut extensions.get_or_default::<FieldErrors>().0;
+            if guard
+                .get(page_key)
+                .map_or(errors.is_empty(), |old| *old == errors)
+            {
+                return;
+            }
+            if errors.is_empty() {
+                guard.remove(page_key);
+            } else {
+                guard.insert(page_key.to_string(), errors);
+            }
+        }
+        if self.lock_inner().page_map.contains_key(page_key) {
+            self.rebuild_value(page_key, ctx);
+        }
+    }
+
+    /// Returns the page key of the tab selected with [`ApplinSession::select_tab`].
+    #[must_use]
+    pub fn selected_tab(&self, tab_page_key: &str) -> Option<String> {
+        self.lock_extensions()
+            .get::<TabState>()?
+            .selected
+            .get(tab_page_key)
+            .cloned()
+    }
+
+    /// Makes the tab page at `tab_page_key` show the tab with `page_key`.
+    /// This replaces the page's [`crate::widget::TabPage::with_selected`] and rebuilds the page.
+    /// The client selects the tab even when the user selected another tab
+    /// since the server last selected it.
+    ///
+    /// When the session has no executor, the selection waits for the next RPC
+    /// or [`ApplinSession::send_pending_updates`] call.
+    pub fn select_tab(self: &Arc<Self>, tab_page_key: &str, page_key: &PageKey, ctx: Context) {
+        {
+            let mut extensions = self.lock_extensions();
+            extensions
+                .get_or_default::<TabState>()
+                .selected
+                .insert(tab_page_key.to_string(), page_key.to_string());
+            extensions
+                .get_or_default::<PendingNotifications>()
+                .select_tabs
+                .insert(tab_page_key.to_string(), page_key.to_string());
+        }
+        self.rebuild_value(tab_page_key, ctx);
+        self.schedule_notifications(ctx);
+    }
+
+    /// Returns the badge text set with [`ApplinSession::set_tab_badge`].
+    #[must_use]
+    pub fn tab_badge(&self, page_key: &str) -> Option<String> {
+        self.lock_extensions()
+            .get::<TabState>()?
+            .badges
+            .get(page_key)
+            .cloned()
+    }
+
+    /// Shows `text` in the badge of every tab with `page_key`, replacing the tab's
+    /// [`crate::internal::Tab::with_badge_text`].
+    /// `None` returns the tabs to their own badges.
+    /// When the badge changed, this rebuilds the tab pages with a tab for `page_key`.
+    pub fn set_tab_badge(self: &Arc<Self>, page_key: &PageKey, text: Option<String>, ctx: Context) {
+        let tab_page_keys: Vec<String> = {
+            let mut extensions = self.lock_extensions();
+            let tab_state = extensions.get_or_default::<TabState>();
+            if tab_state.badges.get(page_key.as_str()) == text.as_ref() {
+                return;
+            }
+            match text {
+                Some(text) => tab_state.badges.insert(page_key.to_string(), text),
+                None => tab_state.badges.remove(page_key.as_str()),
+            };
+            tab_state
+                .tabs
+                .iter()
+                .filter(|(_, tab_keys)| tab_keys.iter().any(|key| key == page_key.as_str()))
+                .map(|(tab_page_key, _)| tab_page_key.clone())
+                .collect()
+        };
+        let page_map_keys: HashSet<String> = self.lock_inner().page_map.keys().cloned().collect();
+        for key in tab_page_keys {
+            if page_map_keys.contains(&key) {
+                self.rebuild_value(key, ctx);
+            }
+        }
+    }
+
+    /// Applies the tab selection and badges that the server set with
+    /// [`ApplinSession::select_tab`] and [`ApplinSession::set_tab_badge`].
+    fn apply_tab_state(&self, key: &str, page: &mut Page) {
+        let Page::Tab { selected, tabs, .. } = page else {
+            return;
+        };
+        let mut extensions = self.lock_extensions();
+        let tab_state = extensions.get_or_default::<TabState>();
+        tab_state.tabs.insert(
+            key.to_string(),
+            tabs.iter().map(|tab| tab.page_key.clone()).collect(),
+        );
+        if let Some(page_key) = tab_state.selected.get(key) {
+            *selected = Some(page_key.clone());
+        }
+        for tab in tabs {
+            if let Some(text) = tab_state.badges.get(&tab.page_key) {
+                tab.badge_text = Some(text.clone());
+            }
+        }
+    }
+
+    /// Returns the theme set with [`ApplinSession::set_theme`].
+    #[must_use]
+    pub fn theme(&self) -> Option<Theme> {
+        self.theme
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sets the theme for all of the session's pages and sends it to the client.
+    /// Page themes replace its tokens.
+    /// `None` returns the client to its default theme.
+    ///
+    /// When the session has no executor, the update waits for the next poll, RPC,
+    /// or [`ApplinSession::send_pending_updates`] call.
+    pub fn set_theme(self: &Arc<Self>, theme: Option<Theme>, ctx: Context) {
+        {
+            let mut guard = self.theme.lock().unwrap_or_else(PoisonError::into_inner);
+            if *guard == theme {
+                return;
+            }
+            *guard = theme;
+        }
+        if self.rpc_context() == ctx {
+            self.lock_inner().rpc_updates.insert(PendingUpdate::Theme);
+        } else if let Some(executor) = self.executor.upgrade() {
+            let self_clone = self.clone();
+            executor.schedule_blocking(move || self_clone.send_theme());
+        } else {
+            self.lock_inner().rpc_updates.insert(PendingUpdate::Theme);
+        }
+    }
+
+    /// Returns an update with the session theme, or an empty theme when it has none.
+    fn theme_update(&self) -> Update {
+        Update {
+            theme: Some(self.theme().unwrap_or_else(Theme::empty)),
+            ..Update::default()
+        }
+    }
+
+    fn send_theme(&self) {
+        let mut update = self.theme_update();
+        update.downgrade(self.client_info().protocol_version);
+        if update.is_empty() {
+            return;
+        }
+        let json_string = update.to_value().to_string();
+        let mut inner = self.lock_inner();
+        if inner.sender.is_connected() {
+            inner.sender.send(Event::Message(json_string));
+        } else {
+            inner.rpc_updates.insert(PendingUpdate::Theme);
+        }
+    }
+
+    /// Shows `toast` on the client.
+    /// The client shows toasts one at a time, in order.
+    ///
+    /// When the session has no executor, the toast waits for the next RPC
+    /// or [`ApplinSession::send_pending_updates`] call.
+    pub fn show_toast(self: &Arc<Self>, toast: Toast, ctx: Context) {
+        self.lock_extensions()
+            .get_or_default::<PendingNotifications>()
+            .toasts
+            .push(toast);
+        self.schedule_notifications(ctx);
+    }
+
+    /// Makes the client push the page with `page_key`, like a `push` action.
+    /// This can show a [`crate::widget::SheetModal`] or an alert when a background task finishes.
+    ///
+    /// When the session has no executor, the push waits for the next RPC
+    /// or [`ApplinSession::send_pending_updates`] call.
+    pub fn show_page(self: &Arc<Self>, page_key: &PageKey, ctx: Context) {
+        self.lock_extensions()
+            .get_or_default::<PendingNotifications>()
+            .push = Some(page_key.to_string());
+        self.schedule_notifications(ctx);
+    }
+
+    fn schedule_notifications(self: &Arc<Self>, ctx: Context) {
+        // During an RPC, the response carries them.
+        if self.rpc_context() != ctx {
+            if let Some(executor) = self.executor.upgrade() {
+                let self_clone = self.clone();
+                executor.schedule_blocking(move || self_clone.send_notifications());
+            }
+        }
+    }
+
+    /// Sends `update` to the connected stream, downgraded for the client.
+    /// Returns false when the stream is not connected.
+    pub fn send_update(&self, mut update: Update) -> bool {
+        let mut inner = self.lock_inner();
+        if !inner.sender.is_connected() {
+            return false;
+        }
+        update.downgrade(self.client_info().protocol_version);
+        if !update.is_empty() {
+            inner
+                .sender
+                .send(Event::Message(update.to_value().to_string()));
+        }
+        true
+    }
+
+    fn send_notifications(&self) {
+        let mut inner = self.lock_inner();
+        if !inner.sender.is_connected() {
+            return;
+        }
+        let mut update = {
+            let mut extensions = self.lock_extensions();
+            let Some(notifications) = extensions.get_mut::<PendingNotifications>() else {
+                return;
+            };
+            Update {
+                select_tabs: std::mem::take(&mut notifications.select_tabs),
+                push: notifications.push.take(),
+                toasts: std::mem::take(&mut notifications.toasts),
+                ..Update::default()
+            }
+        };
+        update.downgrade(self.client_info().protocol_version);
+        if !update.is_empty() {
+            inner
+                .sender
+                .send(Event::Message(update.to_value().to_string()));
+        }
+    }
+
+    /// Starts a job for an [`crate::action::rpc_with_progress`] action
+    /// and returns the RPC response that tells the client the job's ID in `started-job`.
+    /// The client shows the job's progress until `job_fn` returns.
+    /// When `job_fn` panics, the job fails.
+    ///
+    /// `job_fn` reports progress with [`Job::set_progress`].
+    /// To send pages with the result, use [`Job::context`] when changing state.
+    ///
+    /// When the session has no executor, this runs the job before responding.
+    ///
+    /// # Errors
+    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
+    pub fn start_job<F>(self: &Arc<Self>, job_fn: F) -> Result<Response, Response>
+    where
+        F: 'static + Send + FnOnce(&Job<T>) -> Result<(), String>,
+    {
+        let id = self.next_job_id.fetch_add(1, AcqRel).to_string();
+        self.lock_extensions()
+            .get_or_default::<PendingNotifications>()
+            .jobs
+            .insert(id.clone(), JobStatus::new(&id));
+        let Some(executor) = self.executor.upgrade() else {
+            Job::new(self.clone(), &id, false).run(job_fn);
+            return self.update_response(200, Value::Null, Some(id));
+        };
+        // Respond before the job can send progress, so the client learns the job's ID first.
+        let response = self.update_response(200, Value::Null, Some(id.clone()));
+        let job = Job::new(self.clone(), id, true);
+        executor.schedule_blocking(move || job.run(job_fn));
+        response
+    }
+
     /// # Errors
     /// Returns an error when it cannot start the stream.
     pub fn stream(self: &Arc<Self>) -> Result<Response, Response> {
@@ -123,6 +504,10 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
             let mut inner_guard = self.lock_inner();
             inner_guard.page_map = PageMap::new();
             inner_guard.sender = sender;
+            if self.theme().is_some() && self.client_info().supports(2) {
+                let json_string = self.theme_update().to_value().to_string();
+                inner_guard.sender.send(Event::Message(json_string));
+            }
         }
         // TODO: Send the client an opaque version ID
         //       and skip rebuilding all if it matches.
@@ -136,24 +521,23 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
     /// Returns an error when we fail to build the new key set or fail to build the value for a key.
     pub fn build_page_map(
         self: &Arc<Self>,
-    ) -> Result<serde_json::Map<String, Value>, Box<dyn std::error::Error>> {
+    ) -> Result<BTreeMap<String, Option<Page>>, Box<dyn std::error::Error>> {
         let rebuilder = Rebuilder::PageMap(Arc::downgrade(self));
         let mut inner_guard = self.lock_inner();
         let result = (*self.page_map_fn)(rebuilder);
         let mut new_page_map = result?;
-        let mut diff = serde_json::Map::new();
+        let mut diff = BTreeMap::new();
         // Removed keys.
         for key in inner_guard.page_map.keys() {
             if !new_page_map.contains_key(key) {
-                diff.insert(key.to_string(), Value::Null);
+                diff.insert(key.to_string(), None);
             }
         }
         // Added keys.
-        for (key, value_fn) in new_page_map.iter() {
+        for (key, page_fn) in new_page_map.iter() {
             if !inner_guard.page_map.contains_key(key) {
-                let rebuilder = Rebuilder::Page(Arc::downgrade(self), key.to_string());
-                let value = (*value_fn)(rebuilder)?;
-                diff.insert(key.to_string(), value);
+                let page = self.call_page_fn(key, page_fn)?;
+                diff.insert(key.to_string(), Some(page));
             }
         }
         std::mem::swap(&mut inner_guard.page_map, &mut new_page_map);
@@ -164,25 +548,69 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
     /// Returns an error when we fail to build the new key set or fail to build the value for a key.
     #[allow(clippy::missing_panics_doc)]
     pub fn build_page_map_and_send(self: &Arc<Self>) -> Result<(), Box<dyn std::error::Error>> {
-        let diff = self.build_page_map()?;
-        let update = json!({ "pages": diff });
+        let update = Update {
+            pages: self.build_page_map()?,
+            ..Update::default()
+        };
         let json_string = serde_json::to_string(&update).unwrap();
         //dbg!(&json_string);
         self.lock_inner().sender.send(Event::Message(json_string));
         Ok(())
     }
 
+    /// Builds every page in a new `PageMap` from the session's page map function.
+    /// This does not change the pages that the next poll, RPC, or stream update sends.
+    ///
     /// # Errors
-    /// Returns an error when we build the value for the key.
-    pub fn build_value(self: &Arc<Self>, key: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    /// Returns an error when we fail to build the `PageMap` or one of its pages.
+    pub fn build_all_pages(
+        self: &Arc<Self>,
+    ) -> Result<BTreeMap<String, Page>, Box<dyn std::error::Error>> {
+        let page_map = (*self.page_map_fn)(Rebuilder::PageMap(Arc::downgrade(self)))?;
+        let mut pages = BTreeMap::new();
+        for (key, page_fn) in page_map.iter() {
+            let page = self
+                .call_page_fn(key, page_fn)
+                .map_err(|e| format!("error building key {key:?}: {e}"))?;
+            pages.insert(key.to_string(), page);
+        }
+        Ok(pages)
+    }
+
+    /// Calls the page function.
+    /// In debug builds, this validates the page and prints any problems.
+    fn call_page_fn(
+        self: &Arc<Self>,
+        key: &str,
+        page_fn: &PageFn<T>,
+    ) -> Result<Page, Box<dyn std::error::Error>> {
         let rebuilder = Rebuilder::Page(Arc::downgrade(self), key.to_string());
+        let mut page = (*page_fn)(rebuilder)?;
+        self.apply_tab_state(key, &mut page);
+        if cfg!(debug_assertions) {
+            for diagnostic in page.validate() {
+                println!("WARN page {key:?} {diagnostic}");
+            }
+        }
+        page.downgrade(self.client_info().protocol_version);
+        Ok(page)
+    }
+
+    /// # Errors
+    /// Returns an error when we fail to build the page for the key.
+    pub fn build_page(self: &Arc<Self>, key: &str) -> Result<Page, Box<dyn std::error::Error>> {
         let inner_guard = self.lock_inner();
-        let value_fn = inner_guard
+        let page_fn = inner_guard
             .page_map
             .get(key)
             .ok_or_else(|| format!("key {key:?} not found"))?;
-        (*value_fn)(rebuilder)
-        // TODO: Warn if page has multiple widgets using the same var name.
+        self.call_page_fn(key, page_fn)
+    }
+
+    /// # Errors
+    /// Returns an error when we build the value for the key.
+    pub fn build_value(self: &Arc<Self>, key: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        Ok(self.build_page(key)?.to_value())
     }
 
     /// # Errors
@@ -191,9 +619,12 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
         self: &Arc<Self>,
         key: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let value = self.build_value(key)?;
-        let json_obj = json!({"pages": { key: value }});
-        let json_string = json_obj.to_string();
+        let page = self.build_page(key)?;
+        let update = Update {
+            pages: BTreeMap::from([(key.to_string(), Some(page))]),
+            ..Update::default()
+        };
+        let json_string = update.to_value().to_string();
         //dbg!(&json_string);
         let mut inner = self.lock_inner();
         if inner.sender.is_connected() {
@@ -206,20 +637,24 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
         Ok(())
     }
 
+    /// When the session has no executor, the update waits for the next poll, RPC,
+    /// or [`ApplinSession::send_pending_updates`] call.
     #[allow(clippy::missing_panics_doc)]
     pub fn rebuild_page_map(self: &Arc<Self>, ctx: Context) {
         if self.rpc_context() == ctx || !self.lock_inner().sender.is_connected() {
             self.lock_inner().rpc_updates.insert(PendingUpdate::KeySet);
-        } else {
+        } else if let Some(executor) = self.executor.upgrade() {
             let self_clone = self.clone();
-            if let Some(executor) = self.executor.upgrade() {
-                executor.schedule_blocking(move || {
-                    self_clone.build_page_map_and_send().unwrap();
-                });
-            }
+            executor.schedule_blocking(move || {
+                self_clone.build_page_map_and_send().unwrap();
+            });
+        } else {
+            self.lock_inner().rpc_updates.insert(PendingUpdate::KeySet);
         }
     }
 
+    /// When the session has no executor, the update waits for the next poll, RPC,
+    /// or [`ApplinSession::send_pending_updates`] call.
     #[allow(clippy::missing_panics_doc)]
     pub fn rebuild_value(self: &Arc<Self>, key: impl AsRef<str>, ctx: Context) {
         let key = key.as_ref().to_string();
@@ -229,65 +664,165 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
                 .insert(PendingUpdate::Key(key));
             return;
         }
-        let self_clone = self.clone();
         if let Some(executor) = self.executor.upgrade() {
+            let self_clone = self.clone();
             executor.schedule_blocking(move || {
                 self_clone.build_value_and_send(&key).unwrap();
             });
+        } else {
+            self.lock_inner()
+                .rpc_updates
+                .insert(PendingUpdate::Key(key));
         }
     }
 
-    /// # Errors
-    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
-    #[allow(clippy::missing_panics_doc)]
-    pub fn rpc_response(self: &Arc<Self>) -> Result<Response, Response> {
-        self.rpc_response_with_vars(Value::Null)
+    /// Rebuilds the pages with `keys` and includes them in the RPC response.
+    /// Use this in pull-to-refresh handlers.
+    /// It skips keys that are not in the session's page map.
+    pub fn refresh<S: AsRef<str>>(self: &Arc<Self>, keys: impl IntoIterator<Item = S>) {
+        for key in keys {
+            if self.lock_inner().page_map.contains_key(key.as_ref()) {
+                self.rebuild_value(key, self.rpc_context());
+            }
+        }
     }
 
+    /// Handles the request that a client sends for a `refresh` action with a page key.
+    /// Clients POST a body like `{"key": "/orders"}` to their refresh path, usually `/refresh`.
+    /// The response has the rebuilt page.
+    ///
     /// # Errors
-    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
-    #[allow(clippy::missing_panics_doc)]
-    pub fn rpc_response_with_vars<V: serde::Serialize>(
-        self: &Arc<Self>,
-        vars: V,
-    ) -> Result<Response, Response> {
-        let mut pending_updates = HashSet::new();
-        {
-            self.last_contact_epoch_seconds
-                .store(epoch_seconds(), Release);
-            std::mem::swap(&mut self.lock_inner().rpc_updates, &mut pending_updates);
+    /// Returns an error when the request has no key, the session has no page with the key,
+    /// or we fail to build the update.
+    pub fn refresh_response(self: &Arc<Self>, req: &Request) -> Result<Response, Response> {
+        #[derive(Deserialize)]
+        struct RefreshRequest {
+            key: String,
+        }
+        let request: RefreshRequest = req
+            .json()
+            .map_err(|_| client_error("expected a JSON object with a page key"))?;
+        if !self.lock_inner().page_map.contains_key(&request.key) {
+            return Err(client_error(format!("unknown page key {:?}", request.key)));
         }
+        self.refresh([request.key]);
+        self.rpc_response()
+    }
+
+    /// Builds the pages and theme that changed since the last poll or RPC.
+    fn build_pending_update(self: &Arc<Self>) -> Result<Update, Box<dyn std::error::Error>> {
+        let mut pending_updates = HashSet::new();
+        std::mem::swap(&mut self.lock_inner().rpc_updates, &mut pending_updates);
         //dbg!(&pending_updates);
+        let mut update = if pending_updates.remove(&PendingUpdate::Theme) {
+            self.theme_update()
+        } else {
+            Update::default()
+        };
         let mut diff = if pending_updates.remove(&PendingUpdate::KeySet) {
             self.build_page_map()
-                .map_err(|e| server_error(format!("error building keys: {e}")))?
+                .map_err(|e| format!("error building keys: {e}"))?
         } else {
-            serde_json::Map::new()
+            BTreeMap::new()
         };
         for pending_update in pending_updates {
             let key = match pending_update {
-                PendingUpdate::KeySet => unreachable!(),
+                PendingUpdate::KeySet | PendingUpdate::Theme => unreachable!(),
                 PendingUpdate::Key(key) => key,
             };
             if diff.contains_key(&key) {
                 // Skip deleted keys.
                 continue;
             }
-            let value = self
-                .build_value(&key)
-                .map_err(|e| server_error(format!("error building key {key:?}: {e}")))?;
-            diff.insert(key, value);
+            let page = self
+                .build_page(&key)
+                .map_err(|e| format!("error building key {key:?}: {e}"))?;
+            diff.insert(key, Some(page));
         }
         //dbg!(&diff);
-        let mut obj = serde_json::Map::new();
-        if !diff.is_empty() {
-            obj.insert("pages".to_string(), diff.into());
+        update.pages = diff;
+        let mut inner = self.lock_inner();
+        if !inner.vars.is_empty() {
+            update.vars = Some(std::mem::take(&mut inner.vars));
         }
-        let vars = serde_json::value::to_value(vars).unwrap();
-        if vars != Value::Null {
-            obj.insert("vars".to_string(), vars);
+        if let Some(notifications) = self.lock_extensions().remove::<PendingNotifications>() {
+            update.select_tabs = notifications.select_tabs;
+            update.push = notifications.push;
+            update.toasts = notifications.toasts;
+            update.jobs = notifications.jobs.into_values().collect();
         }
-        Ok(Response::json(200, Value::Object(obj))
+        drop(inner);
+        update.downgrade(self.client_info().protocol_version);
+        Ok(update)
+    }
+
+    /// Sends waiting updates to the connected stream.
+    /// Sessions without an executor use this instead of background tasks.
+    ///
+    /// # Errors
+    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn send_pending_updates(self: &Arc<Self>) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.lock_inner().sender.is_connected() {
+            return Ok(());
+        }
+        let update = self.build_pending_update()?;
+        if !update.is_empty() {
+            let json_string = serde_json::to_string(&update).unwrap();
+            self.lock_inner().sender.send(Event::Message(json_string));
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn rpc_response(self: &Arc<Self>) -> Result<Response, Response> {
+        self.rpc_response_with_vars(Value::Null)
+    }
+
+    /// # Errors
+    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
+    pub fn rpc_response_with_vars<V: serde::Serialize>(
+        self: &Arc<Self>,
+        vars: V,
+    ) -> Result<Response, Response> {
+        self.update_response(200, vars, None)
+    }
+
+    /// Makes a `422 Unprocessable Content` response with the pages that changed.
+    /// Clients show the pages and skip the rest of the actions.
+    /// Use this when the request has input that the user must fix.
+    ///
+    /// # Errors
+    /// Returns an error when we fail to build the new key set or fail to build the value for a key.
+    pub fn rejected_rpc_response(self: &Arc<Self>) -> Result<Response, Response> {
+        self.update_response(422, Value::Null, None)
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    fn update_response<V: serde::Serialize>(
+        self: &Arc<Self>,
+        code: u16,
+        vars: V,
+        started_job: Option<String>,
+    ) -> Result<Response, Response> {
+        self.last_contact_epoch_seconds
+            .store(epoch_seconds(), Release);
+        let mut update = self
+            .build_pending_update()
+            .map_err(|e| server_error(e.to_string()))?;
+        match serde_json::value::to_value(vars).unwrap() {
+            Value::Null => {}
+            Value::Object(map) => update.vars.get_or_insert_with(Map::new).extend(map),
+            other => {
+                return Err(server_error(format!(
+                    "vars must be a JSON object, got: {other}"
+                )))
+            }
+        }
+        update.started_job = started_job;
+        Ok(Response::json(code, update.to_value())
             .unwrap()
             .with_set_cookie(self.cookie.to_cookie())
             .with_no_store())
@@ -299,6 +834,9 @@ impl<T: 'static + Send + Sync> ApplinSession<T> {
         // TODO: Send the client an opaque version ID
         //       and skip rebuilding all if it matches.
         self.rebuild_page_map(self.rpc_context());
+        if self.theme().is_some() {
+            self.lock_inner().rpc_updates.insert(PendingUpdate::Theme);
+        }
         let response = self.rpc_response()?;
         Ok(response.with_set_cookie(self.cookie.to_cookie()))
     }
@@ -0,0 +1,114 @@
+use crate::error::client_error;
+use servlin::internal::escape_and_elide;
+use servlin::{AsciiString, Request, Response};
+
+/// The newest protocol version that this server supports.
+///
+/// Version 2 adds:
+/// - the `date-picker`, `date-range-picker`, `frame`, `icon`, `lazy-list`, `multi-select`,
+///   `number-field`, `padding`, `picker`, `progress-bar`, `radio-group`, `rich-text`, `row`,
+///   `slider`, `spacer`, `stack`, `stepper`, `styled`, and `time-picker` widgets
+/// - button, form-button, and nav-button `icon`
+/// - the `sheet-modal` and `tab-page` pages, and page `theme` and `pull-to-refresh`
+/// - the `choose-file`, `confirm`, `refresh`, and `rpc-with-progress` actions,
+///   and `rpc` actions with `on-success` and `on-error`
+/// - update `theme`, `select-tabs`, `push`, `toasts`, `jobs`, and `started-job`
+///
+/// [`crate::internal::Page::downgrade`] and [`crate::internal::Update::downgrade`]
+/// change pages and updates for older clients.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+pub const APP_VERSION_HEADER: &str = "applin-app-version";
+pub const PROTOCOL_VERSION_HEADER: &str = "applin-protocol-version";
+
+/// Information the client sends about itself.
+///
+/// Users often don't update their apps.
+/// Use this to send older clients only widgets that they support.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ClientInfo {
+    /// The protocol version the client supports.
+    /// Clients that don't send the `applin-protocol-version` header support version 1.
+    pub protocol_version: u32,
+    /// The app's version, from the `applin-app-version` header.
+    pub app_version: Option<String>,
+}
+impl ClientInfo {
+    /// # Errors
+    /// Returns an error when `protocol_version` is not a positive integer.
+    pub fn parse(
+        protocol_version: Option<&str>,
+        app_version: Option<&str>,
+    ) -> Result<Self, String> {
+        let protocol_version = match protocol_version {
+            None => 1,
+            Some(s) => match s.trim().parse::<u32>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    return Err(format!(
+                        "invalid protocol version: {}",
+                        escape_and_elide(s.as_bytes(), 20)
+                    ))
+                }
+            },
+        };
+        Ok(Self {
+            protocol_version,
+            app_version: app_version.map(|s| s.trim().to_string()),
+        })
+    }
+
+    /// Reads the `applin-protocol-version` header,
+    /// or the query parameter with the same name.
+    /// Browsers cannot add headers to `EventSource` stream requests.
+    ///
+    /// Returns `None` when the request has no protocol version or app version.
+    ///
+    /// # Errors
+    /// Returns an error when the request has a malformed `applin-protocol-version` value.
+    pub fn from_req_option(req: &Request) -> Result<Option<Self>, Response> {
+        let query_version = req
+            .url
+            .query_pairs()
+            .find(|(name, _)| name == PROTOCOL_VERSION_HEADER)
+            .map(|(_, value)| value.into_owned());
+        let protocol_version = req
+            .headers
+            .get_only(PROTOCOL_VERSION_HEADER)
+            .map(AsciiString::as_str)
+            .or(query_version.as_deref());
+        let app_version = req
+            .headers
+            .get_only(APP_VERSION_HEADER)
+            .map(AsciiString::as_str);
+        if protocol_version.is_none() && app_version.is_none() {
+            return Ok(None);
+        }
+        Self::parse(protocol_version, app_version)
+            .map(Some)
+            .map_err(|e| client_error(format!("error parsing {PROTOCOL_VERSION_HEADER:?}: {e}")))
+    }
+
+    /// Like [`ClientInfo::from_req_option`], but returns the default when the request
+    /// has no versions.
+    ///
+    /// # Errors
+    /// Returns an error when the request has a malformed `applin-protocol-version` value.
+    pub fn from_req(req: &Request) -> Result<Self, Response> {
+        Ok(Self::from_req_option(req)?.unwrap_or_default())
+    }
+
+    #[must_use]
+    pub fn supports(&self, protocol_version: u32) -> bool {
+        protocol_version <= self.protocol_version
+    }
+}
+impl Default for ClientInfo {
+    fn default() -> Self {
+        Self {
+            protocol_version: 1,
+            app_version: None,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use core::any::{Any, TypeId};
+use core::fmt::{Debug, Formatter};
+use std::collections::HashMap;
+
+/// Per-session state of features, keyed by type.
+///
+/// Features like forms, wizards, and tabs keep their state here,
+/// so they don't need their own fields in [`crate::session::ApplinSession`].
+/// Use a private type as the key, so other code cannot change the state.
+///
+/// ```
+/// use applin::session::Extensions;
+///
+/// #[derive(Default)]
+/// struct Visits(u32);
+///
+/// let mut extensions = Extensions::new();
+/// extensions.get_or_default::<Visits>().0 += 1;
+/// assert_eq!(1, extensions.get::<Visits>().unwrap().0);
+/// ```
+#[derive(Default)]
+pub struct Extensions(HashMap<TypeId, Box<dyn Any + Send>>);
+impl Extensions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    #[must_use]
+    pub fn get<E: 'static + Send>(&self) -> Option<&E> {
+        self.0
+            .get(&TypeId::of::<E>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    #[must_use]
+    pub fn get_mut<E: 'static + Send>(&mut self) -> Option<&mut E> {
+        self.0
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Returns the value of type `E`, inserting the default value first if needed.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_or_default<E: 'static + Send + Default>(&mut self) -> &mut E {
+        self.0
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::<E>::default())
+            .downcast_mut()
+            .unwrap()
+    }
+
+    /// Returns the old value.
+    pub fn insert<E: 'static + Send>(&mut self, value: E) -> Option<E> {
+        self.0
+            .insert(TypeId::of::<E>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    pub fn remove<E: 'static + Send>(&mut self) -> Option<E> {
+        self.0
+            .remove(&TypeId::of::<E>())
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "Extensions{{len={}}}", self.0.len())
+    }
+}
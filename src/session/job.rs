@@ -0,0 +1,125 @@
+use crate::data::Context;
+use crate::internal::JobStatus;
+use crate::internal::Update;
+use crate::session::{ApplinSession, PendingNotifications};
+use serde_json::{Map, Value};
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// A long-running RPC that reports its progress to the client.
+/// See [`ApplinSession::start_job`].
+pub struct Job<T> {
+    background: bool,
+    id: String,
+    session: Arc<ApplinSession<T>>,
+    /// Vars that a background job sends with its next progress report.
+    vars: Mutex<Map<String, Value>>,
+}
+impl<T: 'static + Send + Sync> Job<T> {
+    /// Makes a job.  A `background` job sends its progress over the session's stream.
+    #[must_use]
+    pub fn new(session: Arc<ApplinSession<T>>, id: impl Into<String>, background: bool) -> Self {
+        Self {
+            background,
+            id: id.into(),
+            session,
+            vars: Mutex::new(Map::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn session(&self) -> &Arc<ApplinSession<T>> {
+        &self.session
+    }
+
+    /// Returns the context to use when changing state for the job's result.
+    /// The client gets the rebuilt pages with the job's result,
+    /// or over the stream for a background job.
+    #[must_use]
+    pub fn context(&self) -> Context {
+        if self.background {
+            Context::Empty
+        } else {
+            self.session.rpc_context()
+        }
+    }
+
+    /// Tells the client how much of the job is done, from 0 to 100.
+    pub fn set_progress(&self, percent: u8, text: impl Into<String>) {
+        self.report(
+            JobStatus::new(&self.id)
+                .with_percent(percent.min(100))
+                .with_text(text),
+        );
+    }
+
+    /// Sets a var on the client.  The client gets it with the job's progress or result.
+    pub fn set_var(&self, name: impl Into<String>, value: impl Into<Value>) {
+        if self.background {
+            self.vars
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(name.into(), value.into());
+        } else {
+            self.session
+                .lock_inner()
+                .vars
+                .insert(name.into(), value.into());
+        }
+    }
+
+    /// Calls `job_fn` and finishes the job with its result.
+    /// When `job_fn` panics, the job fails with a server error.
+    pub fn run(&self, job_fn: impl FnOnce(&Job<T>) -> Result<(), String>) {
+        let result =
+            std::panic::catch_unwind(AssertUnwindSafe(|| job_fn(self))).unwrap_or_else(|panic| {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(ToString::to_string)
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                println!("WARN job {:?} panicked: {message}", self.id);
+                Err("Server error".to_string())
+            });
+        self.finish(result);
+    }
+
+    /// Tells the client that the job finished.
+    /// The client shows the error and skips the rest of its actions.
+    pub fn finish(&self, result: Result<(), String>) {
+        let status = JobStatus::new(&self.id).with_percent(100);
+        self.report(match result {
+            Ok(()) => status.with_done(),
+            Err(e) => status.with_error(e),
+        });
+    }
+
+    /// A background job sends only its own status and vars,
+    /// so a concurrent RPC's response keeps the updates that the RPC made.
+    fn report(&self, status: JobStatus) {
+        if self.background {
+            let vars =
+                std::mem::take(&mut *self.vars.lock().unwrap_or_else(PoisonError::into_inner));
+            let update = Update {
+                jobs: vec![status.clone()],
+                vars: (!vars.is_empty()).then(|| vars.clone()),
+                ..Update::default()
+            };
+            if self.session.send_update(update) {
+                return;
+            }
+            // Without a stream, the client gets them with its next update.
+            self.session.lock_inner().vars.extend(vars);
+        }
+        self.session
+            .lock_extensions()
+            .get_or_default::<PendingNotifications>()
+            .jobs
+            .insert(self.id.clone(), status);
+    }
+}
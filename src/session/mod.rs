@@ -1,4 +1,7 @@
 mod applin_session;
+mod client_info;
+mod extensions;
+mod job;
 mod page_key;
 mod page_map;
 mod server_instance_id;
@@ -8,6 +11,9 @@ mod session_set;
 mod sync_cookie;
 
 pub use applin_session::*;
+pub use client_info::*;
+pub use extensions::*;
+pub use job::*;
 pub use page_key::*;
 pub use page_map::*;
 pub use server_instance_id::*;
@@ -2,13 +2,12 @@ use crate::data::Rebuilder;
 use crate::internal::Page;
 use crate::session::PageKey;
 use core::fmt::{Debug, Formatter};
-use serde_json::Value;
 use std::collections::hash_map::{Iter, Keys};
 use std::collections::HashMap;
 
 #[allow(clippy::module_name_repetitions)]
 pub type PageFn<T> =
-    dyn 'static + Send + Sync + Fn(Rebuilder<T>) -> Result<Value, Box<dyn std::error::Error>>;
+    dyn 'static + Send + Sync + Fn(Rebuilder<T>) -> Result<Page, Box<dyn std::error::Error>>;
 
 /// A map of page key string to page-generator function.
 pub struct PageMap<T>(pub HashMap<String, Box<PageFn<T>>>);
@@ -26,7 +25,7 @@ impl<T> PageMap<T> {
     {
         self.0.insert(
             key.into(),
-            Box::new(move |rebuilder| page_fn(rebuilder).map(Into::into).map(Into::into)),
+            Box::new(move |rebuilder| page_fn(rebuilder).map(Into::into)),
         );
         self
     }
@@ -34,9 +33,9 @@ impl<T> PageMap<T> {
     #[must_use]
     #[allow(clippy::needless_pass_by_value)]
     pub fn with_static_page(mut self, key: impl Into<String>, page: impl Into<Page>) -> Self {
-        let value = page.into();
+        let page = page.into();
         self.0
-            .insert(key.into(), Box::new(move |_rebuilder| Ok(value.to_value())));
+            .insert(key.into(), Box::new(move |_rebuilder| Ok(page.clone())));
         self
     }
 
@@ -48,11 +47,7 @@ impl<T> PageMap<T> {
         let key = key.into();
         self.0.insert(
             key.clone(),
-            Box::new(move |rebuilder| {
-                page_fn(rebuilder)
-                    .map(Into::into)
-                    .map(|page: Page| page.to_value())
-            }),
+            Box::new(move |rebuilder| page_fn(rebuilder).map(Into::into)),
         );
         PageKey::new(key)
     }
@@ -60,12 +55,10 @@ impl<T> PageMap<T> {
     #[allow(clippy::needless_pass_by_value)]
     pub fn add_static_page(&mut self, key: impl Into<String>, page: impl Into<Page>) -> PageKey {
         let key = key.into();
-        let value = page.into();
+        let page = page.into();
         // TODO: Warn if key already exists.
-        self.0.insert(
-            key.clone(),
-            Box::new(move |_rebuilder| Ok(value.to_value())),
-        );
+        self.0
+            .insert(key.clone(), Box::new(move |_rebuilder| Ok(page.clone())));
         PageKey::new(key)
     }
 
@@ -7,7 +7,7 @@ use servlin::{AsciiString, Cookie, Request, Response};
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
-const SESSION_COOKIE_NAME: &str = "session";
+pub const SESSION_COOKIE_NAME: &str = "session";
 
 // TODONT: Do not derive `Ord` or `PartialOrd`.  They would let
 //         data structure operations leak `secret` via timing.
@@ -57,14 +57,17 @@ impl SessionCookie {
         self.secret
     }
 
+    /// Returns the cookie's value.
     #[must_use]
     #[allow(clippy::missing_panics_doc)]
+    pub fn to_ascii_string(&self) -> AsciiString {
+        AsciiString::try_from(format!("{}-{}", self.id, self.secret)).unwrap()
+    }
+
+    #[must_use]
     pub fn to_cookie(&self) -> Cookie {
-        Cookie::new(
-            SESSION_COOKIE_NAME,
-            AsciiString::try_from(format!("{}-{}", self.id, self.secret)).unwrap(),
-        )
-        .with_secure(false) // So we can test at http://127.0.0.1/.
+        Cookie::new(SESSION_COOKIE_NAME, self.to_ascii_string()).with_secure(false)
+        // So we can test at http://127.0.0.1/.
     }
 }
 impl TryFrom<&str> for SessionCookie {
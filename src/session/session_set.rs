@@ -1,5 +1,5 @@
 use crate::data::Rebuilder;
-use crate::session::{ApplinSession, PageMap, SessionCookie, SessionId};
+use crate::session::{ApplinSession, ClientInfo, PageMap, SessionCookie, SessionId};
 use servlin::reexport::safina_executor::Executor;
 use servlin::{Request, Response};
 use std::collections::HashMap;
@@ -23,6 +23,18 @@ impl<T: 'static + Send + Sync> SessionSet<T> {
         }
     }
 
+    /// Makes a set whose sessions have no executor.
+    /// Their updates wait for the next poll, RPC, or
+    /// [`ApplinSession::send_pending_updates`] call.
+    /// This is useful in tests.  See `applin::testing::Harness`.
+    #[must_use]
+    pub fn without_executor() -> Self {
+        Self {
+            executor: Weak::new(),
+            set: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
     fn read_lock(&self) -> RwLockReadGuard<HashMap<SessionId, Arc<ApplinSession<T>>>> {
         self.set.read().unwrap_or_else(PoisonError::into_inner)
     }
@@ -31,12 +43,20 @@ impl<T: 'static + Send + Sync> SessionSet<T> {
         self.set.write().unwrap_or_else(PoisonError::into_inner)
     }
 
+    /// Also records the request's [`ClientInfo`] in the session,
+    /// when the request has versions.
+    ///
     /// # Errors
-    /// Returns an error when the request has the session cookie but we fail to parse it.
+    /// Returns an error when the request has the session cookie but we fail to parse it,
+    /// or when the request has a malformed `applin-protocol-version` header.
     pub fn get_opt(&self, req: &Request) -> Result<Option<Arc<ApplinSession<T>>>, Response> {
         if let Some(cookie) = SessionCookie::from_req_option(req)? {
             if let Some(session) = self.read_lock().get(&cookie.id()).cloned() {
                 if cookie == session.cookie {
+                    // Image, upload, and other requests have no versions.
+                    if let Some(client_info) = ClientInfo::from_req_option(req)? {
+                        session.set_client_info(client_info);
+                    }
                     return Ok(Some(session));
                 }
             }
@@ -93,8 +113,11 @@ impl<T: 'static + Send + Sync> SessionSet<T> {
         if let Some(session) = self.get_opt(req)? {
             Ok(session)
         } else {
+            let client_info = ClientInfo::from_req(req)?;
             let value = new_value_fn();
-            Ok(self.new_session(page_map_fn, value))
+            let session = self.new_session(page_map_fn, value);
+            session.set_client_info(client_info);
+            Ok(session)
         }
     }
 }
@@ -0,0 +1,134 @@
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An sRGB color.
+/// On the wire, it is a hex string like `#0a84ff`, or `#0a84ff80` with alpha.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+impl Color {
+    #[must_use]
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    #[must_use]
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    #[must_use]
+    pub fn r(self) -> u8 {
+        self.r
+    }
+
+    #[must_use]
+    pub fn g(self) -> u8 {
+        self.g
+    }
+
+    #[must_use]
+    pub fn b(self) -> u8 {
+        self.b
+    }
+
+    #[must_use]
+    pub fn a(self) -> u8 {
+        self.a
+    }
+}
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)?;
+        if self.a != 255 {
+            write!(f, "{:02x}", self.a)?;
+        }
+        Ok(())
+    }
+}
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("expected a color like #0a84ff, got {s:?}");
+        let hex = s.strip_prefix('#').ok_or_else(err)?;
+        if !(hex.len() == 6 || hex.len() == 8) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let byte = |n: usize| u8::from_str_radix(&hex[n..n + 2], 16).map_err(|_| err());
+        Ok(Self {
+            r: byte(0)?,
+            g: byte(2)?,
+            b: byte(4)?,
+            a: if hex.len() == 8 { byte(6)? } else { 255 },
+        })
+    }
+}
+impl From<Color> for String {
+    fn from(src: Color) -> Self {
+        src.to_string()
+    }
+}
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Color {
+    fn schema_name() -> String {
+        "Color".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, SchemaObject, StringValidation};
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^#([0-9a-fA-F]{6}|[0-9a-fA-F]{8})$".to_string()),
+                ..StringValidation::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// A theme color, with variants for light and dark mode.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ThemeColor {
+    pub light: Color,
+    pub dark: Color,
+}
+impl ThemeColor {
+    #[must_use]
+    pub fn new(light: Color, dark: Color) -> Self {
+        Self { light, dark }
+    }
+
+    /// Makes a color that is the same in light and dark mode.
+    #[must_use]
+    pub fn same(color: Color) -> Self {
+        Self {
+            light: color,
+            dark: color,
+        }
+    }
+}
+impl From<Color> for ThemeColor {
+    fn from(src: Color) -> Self {
+        Self::same(src)
+    }
+}
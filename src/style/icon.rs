@@ -0,0 +1,72 @@
+/// The version of the icon set in [`ICON_NAMES`].
+/// Clients ship the icon set and show a placeholder for names that their version lacks.
+pub const ICON_SET_VERSION: u32 = 1;
+
+/// The names of the icons that clients ship, sorted.
+pub const ICON_NAMES: &[&str] = &[
+    "add",
+    "alert",
+    "arrow-back",
+    "arrow-down",
+    "arrow-forward",
+    "arrow-up",
+    "bell",
+    "bookmark",
+    "calendar",
+    "camera",
+    "cart",
+    "chat",
+    "check",
+    "chevron-down",
+    "chevron-left",
+    "chevron-right",
+    "chevron-up",
+    "clock",
+    "close",
+    "copy",
+    "download",
+    "edit",
+    "filter",
+    "flag",
+    "folder",
+    "gear",
+    "heart",
+    "help",
+    "home",
+    "info",
+    "link",
+    "list",
+    "location",
+    "lock",
+    "mail",
+    "menu",
+    "minus",
+    "more",
+    "pause",
+    "person",
+    "phone",
+    "photo",
+    "play",
+    "refresh",
+    "search",
+    "share",
+    "star",
+    "trash",
+    "unlock",
+    "upload",
+    "warning",
+];
+
+/// Returns an error when `name` is not in [`ICON_NAMES`].
+///
+/// # Errors
+/// Returns an error describing the problem.
+pub fn check_icon_name(name: &str) -> Result<(), String> {
+    if ICON_NAMES.binary_search(&name).is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "unknown icon {name:?}, expected a name from icon set version {ICON_SET_VERSION}"
+        ))
+    }
+}
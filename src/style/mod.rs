@@ -0,0 +1,20 @@
+//! Colors, icons, text styles, and themes.
+//!
+//! A [`Theme`] defines named tokens: colors with light and dark variants,
+//! text styles, corner radii, and spacing.
+//! Set a theme for a session with [`crate::session::ApplinSession::set_theme`]
+//! or for one page with `with_theme` on the page builder.
+//! Override a widget's style with its `with_style` method.
+//!
+//! Icons come from a set that clients ship.  See [`ICON_NAMES`].
+mod color;
+mod icon;
+mod text_style;
+mod theme;
+mod widget_style;
+
+pub use color::*;
+pub use icon::*;
+pub use text_style::*;
+pub use theme::*;
+pub use widget_style::*;
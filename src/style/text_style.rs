@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum FontWeight {
+    #[default]
+    #[serde(rename = "regular")]
+    Regular,
+    #[serde(rename = "medium")]
+    Medium,
+    #[serde(rename = "semibold")]
+    Semibold,
+    #[serde(rename = "bold")]
+    Bold,
+}
+impl FontWeight {
+    /// The CSS font weight, like `400` for regular.
+    #[must_use]
+    pub fn number(self) -> u16 {
+        match self {
+            FontWeight::Regular => 400,
+            FontWeight::Medium => 500,
+            FontWeight::Semibold => 600,
+            FontWeight::Bold => 700,
+        }
+    }
+}
+
+/// A font size, in points, and a weight.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TextStyle {
+    pub size: u16,
+    #[serde(default)]
+    pub weight: FontWeight,
+}
+impl TextStyle {
+    #[must_use]
+    pub fn new(size: u16, weight: FontWeight) -> Self {
+        Self { size, weight }
+    }
+}
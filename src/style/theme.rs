@@ -0,0 +1,170 @@
+use crate::style::{Color, FontWeight, TextStyle, ThemeColor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Returns true when `name` is a valid token name, like `secondary-text`.
+#[must_use]
+pub fn is_token_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    bytes.next().is_some_and(|b| b.is_ascii_lowercase())
+        && bytes.all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-'))
+}
+
+fn check_token_name(name: &str) -> String {
+    assert!(is_token_name(name), "invalid theme token name {name:?}");
+    name.to_string()
+}
+
+/// Named colors, text styles, corner radii, and spacing sizes.
+///
+/// Widget styles refer to tokens by name.
+/// Clients merge the session theme over their defaults, then the page theme over that.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Theme {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<String, ThemeColor>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub radii: BTreeMap<String, u16>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub spacing: BTreeMap<String, u16>,
+    #[serde(
+        default,
+        rename = "text-styles",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub text_styles: BTreeMap<String, TextStyle>,
+}
+impl Theme {
+    /// Makes a theme with the standard tokens:
+    /// - colors: `accent`, `background`, `surface`, `text`, `secondary-text`, `error`
+    /// - text styles: `title`, `body`, `caption`
+    /// - radii and spacing: `small`, `medium`, `large`
+    #[must_use]
+    pub fn new() -> Self {
+        Self::empty()
+            .with_color(
+                "accent",
+                ThemeColor::new(Color::rgb(0x00, 0x7a, 0xff), Color::rgb(0x0a, 0x84, 0xff)),
+            )
+            .with_color(
+                "background",
+                ThemeColor::new(Color::rgb(0xff, 0xff, 0xff), Color::rgb(0x00, 0x00, 0x00)),
+            )
+            .with_color(
+                "surface",
+                ThemeColor::new(Color::rgb(0xf2, 0xf2, 0xf7), Color::rgb(0x1c, 0x1c, 0x1e)),
+            )
+            .with_color(
+                "text",
+                ThemeColor::new(Color::rgb(0x00, 0x00, 0x00), Color::rgb(0xff, 0xff, 0xff)),
+            )
+            .with_color(
+                "secondary-text",
+                ThemeColor::new(Color::rgb(0x8a, 0x8a, 0x8e), Color::rgb(0x98, 0x98, 0x9f)),
+            )
+            .with_color(
+                "error",
+                ThemeColor::new(Color::rgb(0xff, 0x3b, 0x30), Color::rgb(0xff, 0x45, 0x3a)),
+            )
+            .with_text_style("title", TextStyle::new(22, FontWeight::Bold))
+            .with_text_style("body", TextStyle::new(17, FontWeight::Regular))
+            .with_text_style("caption", TextStyle::new(12, FontWeight::Regular))
+            .with_radius("small", 4)
+            .with_radius("medium", 8)
+            .with_radius("large", 16)
+            .with_spacing("small", 4)
+            .with_spacing("medium", 8)
+            .with_spacing("large", 16)
+    }
+
+    /// Makes a theme with no tokens.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            colors: BTreeMap::new(),
+            radii: BTreeMap::new(),
+            spacing: BTreeMap::new(),
+            text_styles: BTreeMap::new(),
+        }
+    }
+
+    /// # Panics
+    /// Panics when `name` is not lowercase letters, digits, and dashes, starting with a letter.
+    #[must_use]
+    pub fn with_color(mut self, name: impl AsRef<str>, color: impl Into<ThemeColor>) -> Self {
+        self.colors
+            .insert(check_token_name(name.as_ref()), color.into());
+        self
+    }
+
+    /// # Panics
+    /// Panics when `name` is not lowercase letters, digits, and dashes, starting with a letter.
+    #[must_use]
+    pub fn with_text_style(mut self, name: impl AsRef<str>, style: TextStyle) -> Self {
+        self.text_styles
+            .insert(check_token_name(name.as_ref()), style);
+        self
+    }
+
+    /// # Panics
+    /// Panics when `name` is not lowercase letters, digits, and dashes, starting with a letter.
+    #[must_use]
+    pub fn with_radius(mut self, name: impl AsRef<str>, radius: u16) -> Self {
+        self.radii.insert(check_token_name(name.as_ref()), radius);
+        self
+    }
+
+    /// # Panics
+    /// Panics when `name` is not lowercase letters, digits, and dashes, starting with a letter.
+    #[must_use]
+    pub fn with_spacing(mut self, name: impl AsRef<str>, size: u16) -> Self {
+        self.spacing.insert(check_token_name(name.as_ref()), size);
+        self
+    }
+
+    #[must_use]
+    pub fn color(&self, name: &str) -> Option<ThemeColor> {
+        self.colors.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn text_style(&self, name: &str) -> Option<TextStyle> {
+        self.text_styles.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn radius(&self, name: &str) -> Option<u16> {
+        self.radii.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn spacing(&self, name: &str) -> Option<u16> {
+        self.spacing.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+            && self.radii.is_empty()
+            && self.spacing.is_empty()
+            && self.text_styles.is_empty()
+    }
+
+    /// Returns a copy of this theme with the tokens from `other` added.
+    /// Tokens in `other` replace tokens with the same name.
+    #[must_use]
+    pub fn merged(&self, other: &Theme) -> Self {
+        let mut result = self.clone();
+        result.colors.extend(other.colors.clone());
+        result.radii.extend(other.radii.clone());
+        result.spacing.extend(other.spacing.clone());
+        result.text_styles.extend(other.text_styles.clone());
+        result
+    }
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
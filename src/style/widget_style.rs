@@ -0,0 +1,142 @@
+use crate::style::{is_token_name, Color};
+use serde::{Deserialize, Serialize};
+
+/// Returns an error when `value` is neither a theme color name nor a color like `#0a84ff`.
+///
+/// # Errors
+/// Returns an error describing the problem.
+pub fn check_color_ref(value: &str) -> Result<(), String> {
+    if value.starts_with('#') {
+        value.parse::<Color>().map(|_| ())
+    } else if is_token_name(value) {
+        Ok(())
+    } else {
+        Err(format!(
+            "expected a theme color name or a color like #0a84ff, got {value:?}"
+        ))
+    }
+}
+
+/// Returns an error when `value` is not a theme token name.
+///
+/// # Errors
+/// Returns an error describing the problem.
+pub fn check_token_ref(value: &str) -> Result<(), String> {
+    if is_token_name(value) {
+        Ok(())
+    } else {
+        Err(format!("expected a theme token name, got {value:?}"))
+    }
+}
+
+/// Style overrides for one widget.
+///
+/// Colors are theme color names, like `accent`, or literal colors, like `#0a84ff`.
+/// The other fields are names of theme tokens.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Style {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(
+        default,
+        rename = "corner-radius",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub corner_radius: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding: Option<String>,
+    #[serde(
+        default,
+        rename = "text-style",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub text_style: Option<String>,
+}
+impl Style {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    /// Panics when `color` is neither a theme color name nor a color like `#0a84ff`.
+    #[must_use]
+    pub fn with_background(mut self, color: impl Into<String>) -> Self {
+        let color = color.into();
+        check_color_ref(&color).unwrap();
+        self.background = Some(color);
+        self
+    }
+
+    /// Sets the text color.
+    ///
+    /// # Panics
+    /// Panics when `color` is neither a theme color name nor a color like `#0a84ff`.
+    #[must_use]
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        let color = color.into();
+        check_color_ref(&color).unwrap();
+        self.color = Some(color);
+        self
+    }
+
+    /// # Panics
+    /// Panics when `radius` is not a theme token name.
+    #[must_use]
+    pub fn with_corner_radius(mut self, radius: impl Into<String>) -> Self {
+        let radius = radius.into();
+        check_token_ref(&radius).unwrap();
+        self.corner_radius = Some(radius);
+        self
+    }
+
+    /// # Panics
+    /// Panics when `spacing` is not a theme token name.
+    #[must_use]
+    pub fn with_padding(mut self, spacing: impl Into<String>) -> Self {
+        let spacing = spacing.into();
+        check_token_ref(&spacing).unwrap();
+        self.padding = Some(spacing);
+        self
+    }
+
+    /// # Panics
+    /// Panics when `text_style` is not a theme token name.
+    #[must_use]
+    pub fn with_text_style(mut self, text_style: impl Into<String>) -> Self {
+        let text_style = text_style.into();
+        check_token_ref(&text_style).unwrap();
+        self.text_style = Some(text_style);
+        self
+    }
+
+    /// Returns the problems with the color and token references.
+    #[must_use]
+    pub fn check(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, value, check) in [
+            (
+                "background",
+                &self.background,
+                check_color_ref as fn(&str) -> _,
+            ),
+            ("color", &self.color, check_color_ref),
+            ("corner-radius", &self.corner_radius, check_token_ref),
+            ("padding", &self.padding, check_token_ref),
+            ("text-style", &self.text_style, check_token_ref),
+        ] {
+            if let Some(Err(e)) = value.as_deref().map(check) {
+                problems.push(format!("style {name}: {e}"));
+            }
+        }
+        problems
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,1120 @@
+use crate::internal::{Action, Chunk, JobStatus, Page, Tab, Toast, Update, Widget};
+use crate::style::Theme;
+use crate::testing::TestClient;
+use crate::widget::{Choice, Date, DateRange, NumberRange, Real32, Span, Time};
+use core::fmt::{Display, Formatter};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+/// An error from [`Connection::rpc`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RpcError {
+    /// The request failed.
+    Failed(String),
+    /// The server rejected the request's input with status `422`.
+    /// The update has the pages that show the errors.
+    Rejected(Box<Update>),
+}
+impl Display for RpcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            RpcError::Failed(e) => write!(f, "{e}"),
+            RpcError::Rejected(_) => write!(f, "422 rejected"),
+        }
+    }
+}
+impl From<String> for RpcError {
+    fn from(e: String) -> Self {
+        RpcError::Failed(e)
+    }
+}
+
+/// The way an [`AppClient`] talks to the server.
+pub trait Connection {
+    /// # Errors
+    /// Returns an error when the request fails.
+    fn poll(&self) -> Result<Update, String>;
+
+    /// # Errors
+    /// Returns an error when the request fails or the server rejects it.
+    fn rpc(&self, path: &str, vars: &Map<String, Value>) -> Result<Update, RpcError>;
+
+    /// Gets the rows of a lazy list that come after `cursor`.
+    ///
+    /// # Errors
+    /// Returns an error when the request fails.
+    /// The default implementation always returns an error.
+    fn load_more(&self, url: &str, cursor: &str) -> Result<Chunk, String> {
+        Err(format!("connection cannot load {url:?} cursor {cursor:?}"))
+    }
+
+    /// Gets the page with `key` again.
+    ///
+    /// # Errors
+    /// Returns an error when the request fails.
+    /// The default implementation always returns an error.
+    fn refresh(&self, key: &str) -> Result<Update, String> {
+        Err(format!("connection cannot refresh {key:?}"))
+    }
+}
+
+fn value_to_update(value: Value) -> Result<Update, String> {
+    serde_json::from_value(value).map_err(|e| format!("error parsing update: {e}"))
+}
+
+impl Connection for TestClient {
+    fn poll(&self) -> Result<Update, String> {
+        let value = TestClient::poll(self).map_err(|(code, body)| format!("{code} {body}"))?;
+        value_to_update(value)
+    }
+
+    fn rpc(&self, path: &str, vars: &Map<String, Value>) -> Result<Update, RpcError> {
+        let value =
+            self.post_json(path, vars).map_err(|(code, body)| {
+                match serde_json::from_str(&body) {
+                    Ok(update) if code == 422 => RpcError::Rejected(Box::new(update)),
+                    _ => RpcError::Failed(format!("{code} {body}")),
+                }
+            })?;
+        Ok(value_to_update(value)?)
+    }
+
+    fn load_more(&self, url: &str, cursor: &str) -> Result<Chunk, String> {
+        let value = self
+            .post_json(url, json!({ "cursor": cursor }))
+            .map_err(|(code, body)| format!("{code} {body}"))?;
+        serde_json::from_value(value).map_err(|e| format!("error parsing chunk: {e}"))
+    }
+
+    fn refresh(&self, key: &str) -> Result<Update, String> {
+        let value = self
+            .post_json("/refresh", json!({ "key": key }))
+            .map_err(|(code, body)| format!("{code} {body}"))?;
+        value_to_update(value)
+    }
+}
+
+fn descendants<'x>(widget: &'x Widget, out: &mut Vec<&'x Widget>) {
+    out.push(widget);
+    for child in widget.children() {
+        descendants(child, out);
+    }
+}
+
+fn widget_texts(widget: &Widget) -> Vec<&str> {
+    match widget {
+        Widget::ButtonVariant { text, .. }
+        | Widget::CheckboxVariant { text, .. }
+        | Widget::ErrorTextVariant { text }
+        | Widget::FormButtonVariant { text, .. }
+        | Widget::ModalButtonVariant { text, .. }
+        | Widget::TextVariant { text } => vec![text],
+        Widget::ProgressBarVariant { text, .. } => {
+            if text.is_empty() {
+                Vec::new()
+            } else {
+                vec![text]
+            }
+        }
+        Widget::FormSectionVariant { title, .. } => title.iter().map(String::as_str).collect(),
+        Widget::NavButtonVariant {
+            text,
+            sub_text,
+            badge_text,
+            ..
+        } => std::iter::once(text)
+            .chain(sub_text.iter())
+            .chain(badge_text.iter())
+            .map(String::as_str)
+            .collect(),
+        Widget::TextfieldVariant { label, error, .. } => [label, error]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .map(String::as_str)
+            .collect(),
+        Widget::MultiSelectVariant { label, options, .. }
+        | Widget::PickerVariant { label, options, .. }
+        | Widget::RadioGroupVariant { label, options, .. } => std::iter::once(label)
+            .filter(|s| !s.is_empty())
+            .chain(options.iter().map(|option| &option.text))
+            .map(String::as_str)
+            .collect(),
+        Widget::DatePickerVariant { label, .. }
+        | Widget::DateRangePickerVariant { label, .. }
+        | Widget::NumberFieldVariant { label, .. }
+        | Widget::SliderVariant { label, .. }
+        | Widget::StepperVariant { label, .. }
+        | Widget::TimePickerVariant { label, .. } => {
+            if label.is_empty() {
+                Vec::new()
+            } else {
+                vec![label]
+            }
+        }
+        Widget::BackButtonVariant { .. }
+        | Widget::ColumnVariant { .. }
+        | Widget::EmptyVariant
+        | Widget::ErrorDetailsVariant
+        | Widget::FormVariant { .. }
+        | Widget::FrameVariant { .. }
+        | Widget::GroupedRowTableVariant { .. }
+        | Widget::IconVariant { .. }
+        | Widget::ImageVariant { .. }
+        | Widget::LazyListVariant { .. }
+        | Widget::PaddingVariant { .. }
+        | Widget::RichTextVariant { .. }
+        | Widget::RowVariant { .. }
+        | Widget::ScrollVariant { .. }
+        | Widget::SpacerVariant { .. }
+        | Widget::StackVariant { .. }
+        | Widget::StyledVariant { .. } => Vec::new(),
+    }
+}
+
+/// Appends `chunk` to the lazy list with `url` and `cursor`.
+fn extend_lazy_list(widget: &mut Widget, url: &str, cursor: &str, chunk: &mut Option<Chunk>) {
+    if let Widget::LazyListVariant {
+        next_cursor,
+        url: list_url,
+        widgets,
+        ..
+    } = widget
+    {
+        if list_url == url && next_cursor.as_deref() == Some(cursor) {
+            if let Some(chunk) = chunk.take() {
+                widgets.extend(chunk.widgets);
+                *next_cursor = chunk.next_cursor;
+            }
+            return;
+        }
+    }
+    for child in widget.children_mut() {
+        extend_lazy_list(child, url, cursor, chunk);
+    }
+}
+
+fn check_choice_ids(var: &str, options: &[Choice], ids: &[&str]) -> Result<(), String> {
+    for id in ids {
+        if !options.iter().any(|option| option.id == *id) {
+            return Err(format!("{var:?} has no option {id:?}"));
+        }
+    }
+    Ok(())
+}
+
+fn number_range(integer: bool, min: Option<&Real32>, max: Option<&Real32>) -> NumberRange {
+    let mut range = NumberRange::new();
+    if integer {
+        range = range.with_integer();
+    }
+    if let Some(min) = min {
+        range = range.with_min(min.clone().get());
+    }
+    if let Some(max) = max {
+        range = range.with_max(max.clone().get());
+    }
+    range
+}
+
+/// Returns the number as JSON, like the client sends it.
+#[allow(clippy::cast_possible_truncation)]
+fn number_value(n: f32, integer: bool) -> Value {
+    if integer {
+        Value::from(n as i64)
+    } else {
+        Value::from(f64::from(n))
+    }
+}
+
+/// Returns the widget's var, its initial value, and its RPC path.
+fn widget_var(widget: &Widget) -> Option<(&str, Value, Option<&String>)> {
+    fn to_value(opt: Option<impl ToString>) -> Value {
+        opt.map_or(Value::Null, |v| Value::String(v.to_string()))
+    }
+    fn to_value_list(ids: &[String]) -> Value {
+        Value::Array(ids.iter().cloned().map(Value::String).collect())
+    }
+    match widget {
+        Widget::CheckboxVariant {
+            var,
+            initial_bool,
+            rpc,
+            ..
+        } => Some((var, Value::Bool(*initial_bool), rpc.as_ref())),
+        Widget::DatePickerVariant {
+            var,
+            initial_date,
+            rpc,
+            ..
+        } => Some((var, to_value(*initial_date), rpc.as_ref())),
+        Widget::DateRangePickerVariant {
+            var,
+            initial_range,
+            rpc,
+            ..
+        } => Some((var, to_value(*initial_range), rpc.as_ref())),
+        Widget::MultiSelectVariant {
+            var,
+            initial_ids,
+            rpc,
+            ..
+        } => Some((var, to_value_list(initial_ids), rpc.as_ref())),
+        Widget::NumberFieldVariant {
+            var,
+            initial_number,
+            integer,
+            rpc,
+            ..
+        } => Some((
+            var,
+            initial_number
+                .clone()
+                .map_or(Value::Null, |n| number_value(n.get(), *integer)),
+            rpc.as_ref(),
+        )),
+        Widget::PickerVariant {
+            var,
+            initial_id,
+            rpc,
+            ..
+        }
+        | Widget::RadioGroupVariant {
+            var,
+            initial_id,
+            rpc,
+            ..
+        } => Some((var, to_value(initial_id.as_ref()), rpc.as_ref())),
+        Widget::SliderVariant {
+            var,
+            initial_number,
+            integer,
+            min,
+            rpc,
+            ..
+        } => {
+            let n = initial_number.as_ref().unwrap_or(min).clone().get();
+            Some((var, number_value(n, *integer), rpc.as_ref()))
+        }
+        Widget::StepperVariant {
+            var,
+            initial_number,
+            integer,
+            min,
+            rpc,
+            ..
+        } => {
+            let n = initial_number
+                .as_ref()
+                .or(min.as_ref())
+                .map_or(0.0, |n| n.clone().get());
+            Some((var, number_value(n, *integer), rpc.as_ref()))
+        }
+        Widget::TextfieldVariant {
+            var,
+            initial_string,
+            rpc,
+            ..
+        } => Some((var, Value::String(initial_string.clone()), rpc.as_ref())),
+        Widget::TimePickerVariant {
+            var,
+            initial_time,
+            rpc,
+            ..
+        } => Some((var, to_value(*initial_time), rpc.as_ref())),
+        _ => None,
+    }
+}
+
+/// A `confirm` action that waits for the user.
+struct Confirmation {
+    title: String,
+    text: String,
+    /// The confirm action's `then` actions and the actions after it.
+    actions: Vec<Action>,
+}
+
+/// A simulated Applin client for testing apps without a phone.
+///
+/// It keeps a stack of pages, applies updates from the server,
+/// and performs actions like a real client.
+/// Tests can tap buttons, fill in textfields and checkboxes,
+/// and check what the user would see.
+///
+/// ```no_run
+/// use applin::testing::AppClient;
+/// # let url = String::new();
+/// let mut client = AppClient::connect(&url).unwrap();
+/// client.assert_text("Welcome");
+/// client.set_text("name", "Alice").unwrap();
+/// client.tap("Save").unwrap();
+/// client.assert_page("/saved");
+/// ```
+pub struct AppClient<C: Connection> {
+    connection: C,
+    pages: BTreeMap<String, Page>,
+    stack: Vec<String>,
+    /// Tab page key to the page key of the tab that the user selected.
+    selected_tabs: BTreeMap<String, String>,
+    /// Page key of a tab to the pages pushed on the tab.
+    tab_stacks: BTreeMap<String, Vec<String>>,
+    vars: Map<String, Value>,
+    clipboard: Option<String>,
+    launched_urls: Vec<String>,
+    theme: Option<Theme>,
+    toasts: Vec<Toast>,
+    confirmation: Option<Confirmation>,
+    job_statuses: Vec<JobStatus>,
+}
+impl AppClient<TestClient> {
+    /// Makes a [`TestClient`] and calls [`AppClient::new`].
+    ///
+    /// # Errors
+    /// Returns an error when the first poll fails.
+    pub fn connect(url: &impl ToString) -> Result<Self, String> {
+        Self::new(TestClient::new(url))
+    }
+}
+impl<C: Connection> AppClient<C> {
+    /// Polls the server and shows the `/` page.
+    ///
+    /// # Errors
+    /// Returns an error when the poll fails.
+    pub fn new(connection: C) -> Result<Self, String> {
+        let mut client = Self {
+            connection,
+            pages: BTreeMap::new(),
+            stack: vec!["/".to_string()],
+            selected_tabs: BTreeMap::new(),
+            tab_stacks: BTreeMap::new(),
+            vars: Map::new(),
+            clipboard: None,
+            launched_urls: Vec::new(),
+            theme: None,
+            toasts: Vec::new(),
+            confirmation: None,
+            job_statuses: Vec::new(),
+        };
+        client.poll()?;
+        Ok(client)
+    }
+
+    #[must_use]
+    pub fn connection(&self) -> &C {
+        &self.connection
+    }
+
+    /// # Errors
+    /// Returns an error when the poll fails.
+    pub fn poll(&mut self) -> Result<(), String> {
+        let update = self.connection.poll()?;
+        self.apply(update);
+        Ok(())
+    }
+
+    /// Adds and removes pages, sets vars, and sets the session theme.
+    /// Removed pages disappear from the stack.
+    /// When the server changes a tab page's selected tab or sends `select-tabs`, this selects it.
+    /// Then this saves the update's toasts and pushes its `push` page.
+    pub fn apply(&mut self, update: Update) {
+        if let Some(theme) = update.theme {
+            self.theme = (!theme.is_empty()).then_some(theme);
+        }
+        for (key, opt_page) in update.pages {
+            if let Some(page) = opt_page {
+                if let Page::Tab {
+                    selected: Some(selected),
+                    ..
+                } = &page
+                {
+                    let old_selected = match self.pages.get(&key) {
+                        Some(Page::Tab { selected, .. }) => selected.as_ref(),
+                        _ => None,
+                    };
+                    if old_selected != Some(selected) {
+                        self.selected_tabs.insert(key.clone(), selected.clone());
+                    }
+                }
+                self.pages.insert(key, page);
+            } else {
+                self.pages.remove(&key);
+                self.stack.retain(|k| k != &key);
+                self.selected_tabs.remove(&key);
+                self.tab_stacks.remove(&key);
+                for tab_stack in self.tab_stacks.values_mut() {
+                    tab_stack.retain(|k| k != &key);
+                }
+            }
+        }
+        if let Some(vars) = update.vars {
+            self.vars.extend(vars);
+        }
+        self.selected_tabs.extend(update.select_tabs);
+        self.toasts.extend(update.toasts);
+        self.job_statuses.extend(update.jobs);
+        if let Some(key) = update.push {
+            if let Err(e) = self.push_page(&key) {
+                println!("WARN {e}");
+            }
+        }
+    }
+
+    /// Returns the job statuses that the server sent, oldest first.
+    #[must_use]
+    pub fn job_statuses(&self) -> &[JobStatus] {
+        &self.job_statuses
+    }
+
+    /// Polls until the job with `id` finishes.
+    /// A finished status is final, even when an older status arrives later.
+    fn wait_for_job(&mut self, id: &str) -> Result<(), String> {
+        for _ in 0..1000 {
+            let done = self
+                .job_statuses
+                .iter()
+                .find(|status| status.id == id && status.done);
+            if let Some(status) = done {
+                return match &status.error {
+                    Some(e) => Err(format!("job {id:?} failed: {e}")),
+                    None => Ok(()),
+                };
+            }
+            std::thread::sleep(Duration::from_millis(10));
+            self.poll()?;
+        }
+        Err(format!("job {id:?} did not finish"))
+    }
+
+    /// Returns the toasts that the server sent, oldest first.
+    /// Tapping a toast's button removes it.
+    #[must_use]
+    pub fn toasts(&self) -> &[Toast] {
+        &self.toasts
+    }
+
+    /// Taps the button with `text` on the newest toast that has it
+    /// and performs its actions.
+    ///
+    /// # Errors
+    /// Returns an error when no toast has the button or an action fails.
+    pub fn tap_toast(&mut self, text: &str) -> Result<(), String> {
+        let n = self
+            .toasts
+            .iter()
+            .rposition(|toast| toast.button_text.as_deref() == Some(text))
+            .ok_or_else(|| format!("no toast has button {text:?}"))?;
+        let toast = self.toasts.remove(n);
+        self.run_actions(&toast.actions)
+    }
+
+    /// Returns the session theme from the server, if any.
+    #[must_use]
+    pub fn theme(&self) -> Option<&Theme> {
+        self.theme.as_ref()
+    }
+
+    /// Returns the keys of the pages on the stack.  The last one is visible.
+    /// Pages pushed on a tab are not on this stack.
+    #[must_use]
+    pub fn stack(&self) -> &[String] {
+        &self.stack
+    }
+
+    /// Returns the tab page on top of the stack and its key.
+    fn tab_page(&self) -> Option<(&str, &[Tab])> {
+        let key = self.stack.last()?;
+        match self.pages.get(key)? {
+            Page::Tab { tabs, .. } => Some((key.as_str(), tabs.as_slice())),
+            _ => None,
+        }
+    }
+
+    /// Returns the page key of the selected tab, when a tab page is on top of the stack.
+    #[must_use]
+    pub fn selected_tab(&self) -> Option<&str> {
+        let (key, tabs) = self.tab_page()?;
+        self.selected_tabs
+            .get(key)
+            .map(String::as_str)
+            .filter(|selected| tabs.iter().any(|tab| tab.page_key == *selected))
+            .or_else(|| self.pages.get(key)?.selected_tab())
+    }
+
+    /// Returns the key of the visible page.
+    /// When a tab page is on top of the stack, this is the top page of the selected tab.
+    #[must_use]
+    pub fn page_key(&self) -> Option<&str> {
+        match self.selected_tab() {
+            Some(tab) => Some(
+                self.tab_stacks
+                    .get(tab)
+                    .and_then(|tab_stack| tab_stack.last())
+                    .map_or(tab, String::as_str),
+            ),
+            None => self.stack.last().map(String::as_str),
+        }
+    }
+
+    /// Returns the visible page.
+    ///
+    /// # Errors
+    /// Returns an error when the stack is empty or the server has not sent the page.
+    pub fn page(&self) -> Result<&Page, String> {
+        let key = self.page_key().ok_or("page stack is empty")?;
+        self.pages
+            .get(key)
+            .ok_or_else(|| format!("server did not send page {key:?}"))
+    }
+
+    /// Returns the page for `key`, if the server sent it.
+    #[must_use]
+    pub fn get_page(&self, key: &str) -> Option<&Page> {
+        self.pages.get(key)
+    }
+
+    fn visible_widgets(&self) -> Result<Vec<&Widget>, String> {
+        let mut widgets = Vec::new();
+        for widget in self.page()?.widgets() {
+            descendants(widget, &mut widgets);
+        }
+        Ok(widgets)
+    }
+
+    /// Returns the title and all text on the visible page.
+    #[must_use]
+    pub fn texts(&self) -> Vec<String> {
+        let Ok(page) = self.page() else {
+            return Vec::new();
+        };
+        let mut texts = vec![page.title().to_string()];
+        if let Page::Alert {
+            text: Some(text), ..
+        }
+        | Page::Drawer {
+            text: Some(text), ..
+        } = page
+        {
+            texts.push(text.clone());
+        }
+        for widget in self.visible_widgets().unwrap_or_default() {
+            if let Widget::RichTextVariant { spans } = widget {
+                texts.push(spans.iter().map(Span::plain_text).collect());
+            }
+            texts.extend(widget_texts(widget).into_iter().map(ToString::to_string));
+        }
+        if let Some((_key, tabs)) = self.tab_page() {
+            for tab in tabs {
+                texts.push(tab.title.clone());
+                texts.extend(tab.badge_text.clone());
+            }
+        }
+        if let Some(confirmation) = &self.confirmation {
+            texts.push(confirmation.title.clone());
+            if !confirmation.text.is_empty() {
+                texts.push(confirmation.text.clone());
+            }
+        }
+        texts
+    }
+
+    /// Returns true when the visible page shows `text`.
+    #[must_use]
+    pub fn has_text(&self, text: &str) -> bool {
+        self.texts().iter().any(|t| t == text)
+    }
+
+    /// # Panics
+    /// Panics when the visible page does not show `text`.
+    #[track_caller]
+    pub fn assert_text(&self, text: &str) {
+        assert!(
+            self.has_text(text),
+            "page {:?} does not show {text:?}, it shows {:?}",
+            self.page_key(),
+            self.texts()
+        );
+    }
+
+    /// # Panics
+    /// Panics when the visible page shows `text`.
+    #[track_caller]
+    pub fn assert_no_text(&self, text: &str) {
+        assert!(
+            !self.has_text(text),
+            "page {:?} shows {text:?}",
+            self.page_key()
+        );
+    }
+
+    /// # Panics
+    /// Panics when the visible page's key is not `key`.
+    #[track_caller]
+    pub fn assert_page(&self, key: &str) {
+        assert_eq!(Some(key), self.page_key(), "stack {:?}", self.stack);
+    }
+
+    /// Returns the value of the var, or its widget's initial value.
+    #[must_use]
+    pub fn var(&self, name: &str) -> Option<Value> {
+        if let Some(value) = self.vars.get(name) {
+            return Some(value.clone());
+        }
+        self.visible_widgets()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(widget_var)
+            .find_map(|(var, initial, _rpc)| (var == name).then_some(initial))
+    }
+
+    /// The vars of the widgets on the visible page.  The client sends these with RPCs.
+    fn page_vars(&self) -> Map<String, Value> {
+        let mut vars = Map::new();
+        for widget in self.visible_widgets().unwrap_or_default() {
+            if let Some((var, initial, _rpc)) = widget_var(widget) {
+                let value = self.vars.get(var).cloned().unwrap_or(initial);
+                vars.insert(var.to_string(), value);
+            }
+        }
+        vars
+    }
+
+    /// Returns the text copied by the most recent `copy_to_clipboard` action.
+    #[must_use]
+    pub fn clipboard(&self) -> Option<&str> {
+        self.clipboard.as_deref()
+    }
+
+    /// Returns the URLs that `launch_url` actions opened, oldest first.
+    #[must_use]
+    pub fn launched_urls(&self) -> &[String] {
+        &self.launched_urls
+    }
+
+    /// Taps the button or rich text link with `text` on the visible page
+    /// and performs its actions.
+    /// Tapping a modal button also closes the modal.
+    ///
+    /// # Errors
+    /// Returns an error when the page has no such button, has more than one,
+    /// a confirmation is showing, or an action fails.
+    pub fn tap(&mut self, text: &str) -> Result<(), String> {
+        if let Some((title, _text)) = self.confirmation() {
+            return Err(format!("confirmation {title:?} is showing"));
+        }
+        let mut matches: Vec<(bool, Vec<Action>)> = self
+            .visible_widgets()?
+            .into_iter()
+            .flat_map(|widget| match widget {
+                Widget::ButtonVariant {
+                    text: t, actions, ..
+                }
+                | Widget::FormButtonVariant {
+                    text: t, actions, ..
+                }
+                | Widget::NavButtonVariant {
+                    text: t, actions, ..
+                } if t == text => vec![(false, actions.clone())],
+                Widget::ModalButtonVariant {
+                    text: t, actions, ..
+                } if t == text => vec![(true, actions.clone())],
+                Widget::RichTextVariant { spans } => spans
+                    .iter()
+                    .flat_map(Span::links)
+                    .filter(|(t, _)| t == text)
+                    .map(|(_, actions)| (false, actions.to_vec()))
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        let (is_modal, actions) = match matches.len() {
+            0 => {
+                return Err(format!(
+                    "page {:?} has no button {text:?}, it shows {:?}",
+                    self.page_key(),
+                    self.texts()
+                ))
+            }
+            1 => matches.remove(0),
+            n => {
+                return Err(format!(
+                    "page {:?} has {n} buttons {text:?}",
+                    self.page_key()
+                ))
+            }
+        };
+        if is_modal {
+            self.stack.pop();
+        }
+        self.run_actions(&actions)
+    }
+
+    /// Scrolls to the end of the first lazy list on the visible page that has more rows,
+    /// and appends the rows that the server sends.
+    /// Returns false when no list has more rows.
+    ///
+    /// # Errors
+    /// Returns an error when the request fails.
+    pub fn load_more(&mut self) -> Result<bool, String> {
+        let Some((url, cursor)) =
+            self.visible_widgets()?
+                .into_iter()
+                .find_map(|widget| match widget {
+                    Widget::LazyListVariant {
+                        next_cursor: Some(cursor),
+                        url,
+                        ..
+                    } => Some((url.clone(), cursor.clone())),
+                    _ => None,
+                })
+        else {
+            return Ok(false);
+        };
+        let chunk = self
+            .connection
+            .load_more(&url, &cursor)
+            .map_err(|e| format!("loading more from {url:?} failed: {e}"))?;
+        let key = self.page_key().ok_or("page stack is empty")?.to_string();
+        if let Some(page) = self.pages.get_mut(&key) {
+            let mut opt_chunk = Some(chunk);
+            for widget in page.widgets_mut() {
+                extend_lazy_list(widget, &url, &cursor, &mut opt_chunk);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Taps the tab with `title` on the tab page on top of the stack.
+    /// Tapping the selected tab returns to the tab's first page.
+    ///
+    /// # Errors
+    /// Returns an error when the top page is not a tab page or it has no such tab.
+    pub fn select_tab(&mut self, title: &str) -> Result<(), String> {
+        let (key, tabs) = self
+            .tab_page()
+            .ok_or_else(|| format!("page {:?} is not a tab page", self.stack.last()))?;
+        let tab = tabs
+            .iter()
+            .find(|tab| tab.title == title)
+            .ok_or_else(|| format!("tab page {key:?} has no tab {title:?}"))?;
+        let (key, page_key) = (key.to_string(), tab.page_key.clone());
+        if self.selected_tab() == Some(page_key.as_str()) {
+            self.tab_stacks.remove(&page_key);
+        }
+        self.selected_tabs.insert(key, page_key);
+        Ok(())
+    }
+
+    fn push_page(&mut self, key: &str) -> Result<(), String> {
+        let Some(page) = self.pages.get(key) else {
+            return Err(format!("cannot push unknown page {key:?}"));
+        };
+        let is_modal = matches!(
+            page,
+            Page::Alert { .. } | Page::Drawer { .. } | Page::Sheet { .. }
+        );
+        match self.selected_tab().map(ToString::to_string) {
+            // Modals cover the tab bar.
+            Some(tab) if !is_modal => {
+                self.tab_stacks
+                    .entry(tab)
+                    .or_default()
+                    .push(key.to_string());
+            }
+            _ => self.stack.push(key.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Taps the back button.
+    /// When the page has no `BackButton`, this pops the page.
+    ///
+    /// # Errors
+    /// Returns an error when an action fails.
+    pub fn tap_back(&mut self) -> Result<(), String> {
+        let opt_actions = self
+            .visible_widgets()?
+            .into_iter()
+            .find_map(|widget| match widget {
+                Widget::BackButtonVariant { actions } => Some(actions.clone()),
+                _ => None,
+            });
+        match opt_actions {
+            Some(actions) => self.run_actions(&actions),
+            None => self.run_action(&Action::Pop),
+        }
+    }
+
+    /// Finds the widget on the visible page with `var` and one of the types in `kinds`.
+    fn var_widget(&self, kinds: &[&str], var: &str) -> Result<&Widget, String> {
+        self.visible_widgets()?
+            .into_iter()
+            .filter(|widget| kinds.iter().any(|kind| widget.to_value()["typ"] == *kind))
+            .find(|widget| widget_var(widget).is_some_and(|(v, _initial, _rpc)| v == var))
+            .ok_or_else(|| {
+                format!(
+                    "page {:?} has no {} {var:?}",
+                    self.page_key(),
+                    kinds.join(" or ")
+                )
+            })
+    }
+
+    /// Sets the var of a widget on the visible page and calls the widget's RPC, if any.
+    fn set_var(&mut self, kinds: &[&str], var: &str, value: Value) -> Result<(), String> {
+        let widget = self.var_widget(kinds, var)?;
+        let rpc = widget_var(widget).and_then(|(_var, _initial, rpc)| rpc.cloned());
+        self.vars.insert(var.to_string(), value);
+        match rpc {
+            Some(path) => self.run_action(&Action::Rpc(path)),
+            None => Ok(()),
+        }
+    }
+
+    /// Types `value` into the textfield with `var` and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such textfield or the RPC fails.
+    pub fn set_text(&mut self, var: &str, value: impl Into<String>) -> Result<(), String> {
+        self.set_var(&["textfield"], var, Value::String(value.into()))
+    }
+
+    /// Sets the checkbox with `var` and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such checkbox or the RPC fails.
+    pub fn set_checkbox(&mut self, var: &str, value: bool) -> Result<(), String> {
+        self.set_var(&["checkbox"], var, Value::Bool(value))
+    }
+
+    /// Chooses the option with `id` in the radio group or picker with `var`,
+    /// and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such widget,
+    /// the widget has no option with `id`, or the RPC fails.
+    pub fn set_choice(&mut self, var: &str, id: &str) -> Result<(), String> {
+        const KINDS: [&str; 2] = ["radio-group", "picker"];
+        if let Widget::PickerVariant { options, .. } | Widget::RadioGroupVariant { options, .. } =
+            self.var_widget(&KINDS, var)?
+        {
+            check_choice_ids(var, options, &[id])?;
+        }
+        self.set_var(&KINDS, var, Value::String(id.to_string()))
+    }
+
+    /// Chooses the options with `ids` in the multi-select with `var`,
+    /// and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such multi-select,
+    /// the widget lacks an option in `ids`, or the RPC fails.
+    pub fn set_choices(&mut self, var: &str, ids: &[&str]) -> Result<(), String> {
+        if let Widget::MultiSelectVariant { options, .. } =
+            self.var_widget(&["multi-select"], var)?
+        {
+            check_choice_ids(var, options, ids)?;
+        }
+        let value = ids
+            .iter()
+            .map(|id| Value::String((*id).to_string()))
+            .collect();
+        self.set_var(&["multi-select"], var, Value::Array(value))
+    }
+
+    /// Sets the number in the slider, stepper, or number field with `var`,
+    /// and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such widget,
+    /// the widget does not accept `value`, or the RPC fails.
+    pub fn set_number(&mut self, var: &str, value: f32) -> Result<(), String> {
+        const KINDS: [&str; 3] = ["slider", "stepper", "number-field"];
+        let range = match self.var_widget(&KINDS, var)? {
+            Widget::SliderVariant {
+                integer, max, min, ..
+            } => number_range(*integer, Some(min), Some(max)),
+            Widget::NumberFieldVariant {
+                integer, max, min, ..
+            }
+            | Widget::StepperVariant {
+                integer, max, min, ..
+            } => number_range(*integer, min.as_ref(), max.as_ref()),
+            _ => NumberRange::new(),
+        };
+        range.check_var(var, value)?;
+        self.set_var(&KINDS, var, number_value(value, range.is_integer()))
+    }
+
+    /// Chooses `date` in the date picker with `var` and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such date picker or the RPC fails.
+    pub fn set_date(&mut self, var: &str, date: Date) -> Result<(), String> {
+        self.set_var(&["date-picker"], var, Value::String(date.to_string()))
+    }
+
+    /// Chooses `range` in the date range picker with `var` and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such date range picker or the RPC fails.
+    pub fn set_date_range(&mut self, var: &str, range: DateRange) -> Result<(), String> {
+        self.set_var(
+            &["date-range-picker"],
+            var,
+            Value::String(range.to_string()),
+        )
+    }
+
+    /// Chooses `time` in the time picker with `var` and calls its RPC, if any.
+    ///
+    /// # Errors
+    /// Returns an error when the visible page has no such time picker or the RPC fails.
+    pub fn set_time(&mut self, var: &str, time: Time) -> Result<(), String> {
+        self.set_var(&["time-picker"], var, Value::String(time.to_string()))
+    }
+
+    /// Returns the title and text of the `confirm` action that is waiting for the user.
+    #[must_use]
+    pub fn confirmation(&self) -> Option<(&str, &str)> {
+        self.confirmation
+            .as_ref()
+            .map(|c| (c.title.as_str(), c.text.as_str()))
+    }
+
+    /// Confirms the waiting `confirm` action and performs the rest of its actions.
+    ///
+    /// # Errors
+    /// Returns an error when no confirmation is waiting or an action fails.
+    pub fn accept_confirmation(&mut self) -> Result<(), String> {
+        let confirmation = self
+            .confirmation
+            .take()
+            .ok_or("no confirmation is showing")?;
+        self.run_actions(&confirmation.actions)
+    }
+
+    /// Cancels the waiting `confirm` action and skips the rest of its actions.
+    ///
+    /// # Errors
+    /// Returns an error when no confirmation is waiting.
+    pub fn cancel_confirmation(&mut self) -> Result<(), String> {
+        self.confirmation
+            .take()
+            .map(|_| ())
+            .ok_or_else(|| "no confirmation is showing".to_string())
+    }
+
+    /// Performs the actions in order, stopping at the first error.
+    /// A `confirm` action stops and waits for [`AppClient::accept_confirmation`].
+    ///
+    /// # Errors
+    /// Returns an error when an action fails.
+    pub fn run_actions(&mut self, actions: &[Action]) -> Result<(), String> {
+        let mut queue: VecDeque<Action> = actions.iter().cloned().collect();
+        while let Some(action) = queue.pop_front() {
+            match action {
+                Action::Confirm { text, then, title } => {
+                    let mut actions = then;
+                    actions.extend(queue);
+                    self.confirmation = Some(Confirmation {
+                        title,
+                        text,
+                        actions,
+                    });
+                    return Ok(());
+                }
+                Action::RpcBranch {
+                    on_error,
+                    on_success,
+                    url,
+                } => match self.call_rpc(&url) {
+                    Ok(()) => {
+                        for action in on_success.into_iter().rev() {
+                            queue.push_front(action);
+                        }
+                    }
+                    Err(e) if on_error.is_empty() => return Err(e),
+                    Err(_) => queue = on_error.into(),
+                },
+                action => self.run_action(&action)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls the RPC with the visible page's vars.
+    /// When the server rejects the input, this shows the pages it sent and returns an error.
+    fn send_rpc(&mut self, path: &str) -> Result<Update, String> {
+        let vars = self.page_vars();
+        match self.connection.rpc(path, &vars) {
+            Ok(update) => Ok(update),
+            Err(RpcError::Rejected(update)) => {
+                self.apply(*update);
+                Err(format!("rpc {path:?} rejected"))
+            }
+            Err(RpcError::Failed(e)) => Err(format!("rpc {path:?} failed: {e}")),
+        }
+    }
+
+    fn call_rpc(&mut self, path: &str) -> Result<(), String> {
+        let update = self.send_rpc(path)?;
+        self.apply(update);
+        Ok(())
+    }
+
+    /// # Errors
+    /// Returns an error when the action fails or this client does not support it.
+    pub fn run_action(&mut self, action: &Action) -> Result<(), String> {
+        match action {
+            Action::ChooseFile { .. } | Action::ChoosePhoto(_) | Action::TakePhoto(_) => {
+                return Err(format!("action not supported: {}", action.to_value()))
+            }
+            Action::Confirm { .. } | Action::RpcBranch { .. } => {
+                return self.run_actions(std::slice::from_ref(action))
+            }
+            Action::CopyToClipboard(s) => self.clipboard = Some(s.clone()),
+            Action::LaunchUrl(url) => self.launched_urls.push(url.clone()),
+            Action::Logout => {
+                self.vars.clear();
+                self.stack = vec!["/".to_string()];
+                self.selected_tabs.clear();
+                self.tab_stacks.clear();
+            }
+            Action::Nothing => {}
+            Action::Pop => {
+                let tab = self.selected_tab().map(ToString::to_string);
+                if let Some(tab_stack) = tab.and_then(|tab| self.tab_stacks.get_mut(&tab)) {
+                    if tab_stack.pop().is_some() {
+                        return Ok(());
+                    }
+                }
+                if self.stack.len() < 2 {
+                    return Err("cannot pop the last page".to_string());
+                }
+                self.stack.pop();
+            }
+            Action::Push(key) => self.push_page(key)?,
+            Action::Refresh(target) => {
+                // This client shows no images, so it only refreshes pages.
+                if self.pages.contains_key(target) {
+                    let update = self
+                        .connection
+                        .refresh(target)
+                        .map_err(|e| format!("refresh {target:?} failed: {e}"))?;
+                    self.apply(update);
+                }
+            }
+            Action::Rpc(path) => self.call_rpc(path)?,
+            Action::RpcWithProgress(path) => {
+                let update = self.send_rpc(path)?;
+                let id = update
+                    .started_job
+                    .clone()
+                    .ok_or_else(|| format!("rpc {path:?} started no job"))?;
+                self.apply(update);
+                self.wait_for_job(&id)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+use crate::data::Rebuilder;
+use crate::error::server_error;
+use crate::internal::{Chunk, Update};
+use crate::session::{
+    ApplinSession, ClientInfo, PageMap, SessionSet, APP_VERSION_HEADER, PROTOCOL_VERSION,
+    PROTOCOL_VERSION_HEADER, SESSION_COOKIE_NAME,
+};
+use crate::testing::{Connection, RpcError};
+use serde_json::{json, Map, Value};
+use servlin::reexport::safina_sync::Receiver;
+use servlin::{
+    AsciiString, ContentType, Event, HeaderList, Request, RequestBody, Response, ResponseBody,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, PoisonError, RwLock, Weak};
+use url::Url;
+
+/// Parses the update and checks its pages with [`crate::internal::Page::validate`].
+fn parse_update(bytes: &[u8]) -> Result<Update, String> {
+    let update: Update = serde_json::from_slice(bytes).map_err(|e| {
+        format!(
+            "error parsing update: {e}: {}",
+            String::from_utf8_lossy(bytes)
+        )
+    })?;
+    let problems: Vec<String> = update
+        .pages
+        .iter()
+        .filter_map(|(key, opt_page)| Some((key, opt_page.as_ref()?)))
+        .flat_map(|(key, page)| {
+            page.validate()
+                .into_iter()
+                .map(move |diagnostic| format!("page {key:?} {diagnostic}"))
+        })
+        .collect();
+    if problems.is_empty() {
+        Ok(update)
+    } else {
+        Err(problems.join("\n"))
+    }
+}
+
+fn response_to_update(response: Response) -> Result<Update, String> {
+    let ResponseBody::Vec(bytes) = response.body else {
+        return Err(format!("{} response has unexpected body", response.code));
+    };
+    if response.code != 200 {
+        return Err(format!(
+            "{} {}",
+            response.code,
+            String::from_utf8_lossy(&bytes)
+        ));
+    }
+    parse_update(&bytes)
+}
+
+/// Drives an [`ApplinSession`] without a server or sockets.
+///
+/// Make the session with [`crate::session::SessionSet::without_executor`].
+/// Then updates happen on the test's thread, so tests are fast and deterministic.
+///
+/// Updates with pages that fail [`crate::internal::Page::validate`] are errors,
+/// so tests catch mistakes like misspelled icon names.
+///
+/// ```
+/// use applin::session::{PageMap, SessionSet};
+/// use applin::testing::Harness;
+/// use applin::widget::{NavPage, Text};
+///
+/// let sessions = SessionSet::without_executor();
+/// let session = sessions.new_session(
+///     |_| Ok(PageMap::new().with_static_page("/", NavPage::new("Home", Text::new("hi")))),
+///     (),
+/// );
+/// let harness = Harness::new(session);
+/// assert_eq!(1, harness.poll().unwrap().pages.len());
+/// assert!(harness.poll().unwrap().is_empty());
+/// ```
+pub struct Harness<T: 'static + Send + Sync> {
+    session: Arc<ApplinSession<T>>,
+    client_info: ClientInfo,
+    #[allow(clippy::type_complexity)]
+    handler: Option<Box<dyn Fn(Request) -> Result<Response, Response>>>,
+    receiver: Mutex<Option<Receiver<Event>>>,
+}
+impl<T: 'static + Send + Sync> Harness<T> {
+    /// Pretends to be a client with the newest protocol version.
+    #[must_use]
+    pub fn new(session: Arc<ApplinSession<T>>) -> Self {
+        let client_info = ClientInfo {
+            protocol_version: PROTOCOL_VERSION,
+            app_version: None,
+        };
+        session.set_client_info(client_info.clone());
+        Self {
+            session,
+            client_info,
+            handler: None,
+            receiver: Mutex::new(None),
+        }
+    }
+
+    /// Makes a session without an executor, and a harness for it.
+    #[must_use]
+    pub fn with_pages<F>(page_map_fn: F, value: T) -> Self
+    where
+        F: 'static
+            + Send
+            + Sync
+            + Fn(Rebuilder<T>) -> Result<PageMap<T>, Box<dyn std::error::Error>>,
+    {
+        Self::new(SessionSet::without_executor().new_session(page_map_fn, value))
+    }
+
+    /// Sets a request handler that gets the request's session.
+    /// It looks up the session like [`SessionSet::get`].
+    #[must_use]
+    pub fn with_session_handler(
+        self,
+        handler: impl 'static + Fn(&Arc<ApplinSession<T>>, &Request) -> Result<Response, Response>,
+    ) -> Self {
+        let sessions = SessionSet {
+            executor: Weak::new(),
+            set: Arc::new(RwLock::new(HashMap::from([(
+                self.session.cookie.id(),
+                Arc::clone(&self.session),
+            )]))),
+        };
+        self.with_handler(move |req| handler(&sessions.get(&req)?, &req))
+    }
+
+    /// Sets the request handler that [`Harness::call`] and [`Harness::rpc`] use.
+    /// It should look up the session in the same `SessionSet` that made the harness's session.
+    #[must_use]
+    pub fn with_handler(
+        mut self,
+        handler: impl 'static + Fn(Request) -> Result<Response, Response>,
+    ) -> Self {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Pretends to be a client with `client_info`.
+    #[must_use]
+    pub fn with_client_info(mut self, client_info: ClientInfo) -> Self {
+        self.session.set_client_info(client_info.clone());
+        self.client_info = client_info;
+        self
+    }
+
+    #[must_use]
+    pub fn session(&self) -> &Arc<ApplinSession<T>> {
+        &self.session
+    }
+
+    /// Makes a request like the client sends, with the session cookie.
+    /// When `body` is not `null`, the request has it as a JSON body.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn request(&self, method: &str, path: &str, body: &Value) -> Request {
+        let mut headers = HeaderList::new();
+        headers.add(
+            PROTOCOL_VERSION_HEADER,
+            AsciiString::try_from(self.client_info.protocol_version.to_string()).unwrap(),
+        );
+        if let Some(app_version) = &self.client_info.app_version {
+            headers.add(
+                APP_VERSION_HEADER,
+                AsciiString::try_from(app_version.as_str()).unwrap(),
+            );
+        }
+        let (content_type, bytes) = if body.is_null() {
+            (ContentType::None, Vec::new())
+        } else {
+            (ContentType::Json, serde_json::to_vec(body).unwrap())
+        };
+        Request {
+            remote_addr: SocketAddr::from(([127, 0, 0, 1], 1)),
+            method: method.to_string(),
+            url: Url::parse(&format!("http://127.0.0.1{path}")).unwrap(),
+            headers,
+            cookies: HashMap::from([(
+                SESSION_COOKIE_NAME.to_string(),
+                self.session.cookie.to_ascii_string(),
+            )]),
+            content_type,
+            expect_continue: false,
+            chunked: false,
+            content_length: Some(bytes.len() as u64),
+            body: RequestBody::Vec(bytes),
+        }
+    }
+
+    /// Sends a request to the handler.
+    ///
+    /// # Errors
+    /// Returns the handler's error response, or an error when the harness has no handler.
+    pub fn call(&self, method: &str, path: &str, body: &Value) -> Result<Response, Response> {
+        let handler = self
+            .handler
+            .as_ref()
+            .ok_or_else(|| server_error("Harness has no handler"))?;
+        handler(self.request(method, path, body))
+    }
+
+    /// Polls the session, like a client without a stream.
+    ///
+    /// # Errors
+    /// Returns an error when the session fails to build the update.
+    pub fn poll(&self) -> Result<Update, String> {
+        response_to_update(self.session.poll().unwrap_or_else(|r| r))
+    }
+
+    /// Connects a stream to the session.
+    /// Use [`Harness::events`] to get the messages.
+    ///
+    /// # Errors
+    /// Returns an error when the session fails to start the stream.
+    pub fn stream(&self) -> Result<(), String> {
+        let response = self
+            .session
+            .stream()
+            .map_err(|r| format!("error starting stream: {}", r.code))?;
+        match response.body {
+            ResponseBody::EventStream(receiver) => {
+                *self.receiver.lock().unwrap_or_else(PoisonError::into_inner) = Some(receiver);
+                Ok(())
+            }
+            _ => Err("stream response has no event stream".to_string()),
+        }
+    }
+
+    /// Sends the session's waiting updates and returns the stream messages
+    /// that the client has not yet received.
+    ///
+    /// # Errors
+    /// Returns an error when the session fails to build an update
+    /// or sends a malformed message.
+    pub fn events(&self) -> Result<Vec<Update>, String> {
+        self.session
+            .send_pending_updates()
+            .map_err(|e| e.to_string())?;
+        let receiver_guard = self.receiver.lock().unwrap_or_else(PoisonError::into_inner);
+        let receiver = receiver_guard.as_ref().ok_or("stream is not connected")?;
+        let mut updates = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            if let Event::Message(data) = event {
+                updates.push(parse_update(data.as_bytes())?);
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Posts `vars` to `path`, like a client performing an `rpc` action.
+    ///
+    /// # Errors
+    /// Returns an error when the handler returns an error or a malformed update.
+    /// Returns [`RpcError::Rejected`] when the handler rejects the vars with status `422`.
+    pub fn rpc(&self, path: &str, vars: &Value) -> Result<Update, RpcError> {
+        let response = self.call("POST", path, vars).unwrap_or_else(|r| r);
+        if response.code == 422 {
+            if let ResponseBody::Vec(bytes) = &response.body {
+                return Err(RpcError::Rejected(Box::new(parse_update(bytes)?)));
+            }
+        }
+        Ok(response_to_update(response)?)
+    }
+}
+impl<T: 'static + Send + Sync> Connection for Harness<T> {
+    fn poll(&self) -> Result<Update, String> {
+        Harness::poll(self)
+    }
+
+    fn rpc(&self, path: &str, vars: &Map<String, Value>) -> Result<Update, RpcError> {
+        Harness::rpc(self, path, &Value::Object(vars.clone()))
+    }
+
+    fn load_more(&self, url: &str, cursor: &str) -> Result<Chunk, String> {
+        let response = self
+            .call("POST", url, &json!({ "cursor": cursor }))
+            .unwrap_or_else(|r| r);
+        let ResponseBody::Vec(bytes) = response.body else {
+            return Err(format!("{} response has unexpected body", response.code));
+        };
+        if response.code != 200 {
+            return Err(format!(
+                "{} {}",
+                response.code,
+                String::from_utf8_lossy(&bytes)
+            ));
+        }
+        serde_json::from_slice(&bytes).map_err(|e| format!("error parsing chunk: {e}"))
+    }
+
+    fn refresh(&self, key: &str) -> Result<Update, String> {
+        let req = self.request("POST", "/refresh", &json!({ "key": key }));
+        response_to_update(self.session.refresh_response(&req).unwrap_or_else(|r| r))
+    }
+}
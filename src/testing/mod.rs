@@ -0,0 +1,12 @@
+//! Tools for testing Applin apps.
+//!
+//! Enable the `testing` feature to use this module.
+mod app_client;
+mod harness;
+mod snapshot;
+mod test_client;
+
+pub use app_client::*;
+pub use harness::*;
+pub use snapshot::*;
+pub use test_client::*;
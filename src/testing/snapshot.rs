@@ -0,0 +1,252 @@
+use crate::internal::Page;
+use crate::session::ApplinSession;
+use core::fmt::Write;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Set this environment variable to `1` to write snapshots instead of checking them.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "APPLIN_UPDATE_SNAPSHOTS";
+
+fn value_to_short_string(value: &Value) -> String {
+    let s = value.to_string();
+    if s.chars().count() > 60 {
+        format!("{}...", s.chars().take(57).collect::<String>())
+    } else {
+        s
+    }
+}
+
+fn escape_pointer_token(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
+fn diff_into(path: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected_map), Value::Object(actual_map)) => {
+            let keys: BTreeSet<&String> = expected_map.keys().chain(actual_map.keys()).collect();
+            for key in keys {
+                let child_path = format!("{path}/{}", escape_pointer_token(key));
+                match (expected_map.get(key), actual_map.get(key)) {
+                    (Some(e), Some(a)) => diff_into(&child_path, e, a, out),
+                    (Some(e), None) => {
+                        out.push(format!(
+                            "{child_path}: removed {}",
+                            value_to_short_string(e)
+                        ));
+                    }
+                    (None, Some(a)) => {
+                        out.push(format!("{child_path}: added {}", value_to_short_string(a)));
+                    }
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (Value::Array(expected_vec), Value::Array(actual_vec)) => {
+            for n in 0..expected_vec.len().max(actual_vec.len()) {
+                let child_path = format!("{path}/{n}");
+                match (expected_vec.get(n), actual_vec.get(n)) {
+                    (Some(e), Some(a)) => diff_into(&child_path, e, a, out),
+                    (Some(e), None) => {
+                        out.push(format!(
+                            "{child_path}: removed {}",
+                            value_to_short_string(e)
+                        ));
+                    }
+                    (None, Some(a)) => {
+                        out.push(format!("{child_path}: added {}", value_to_short_string(a)));
+                    }
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (e, a) if e == a => {}
+        (e, a) => out.push(format!(
+            "{path}: {} -> {}",
+            value_to_short_string(e),
+            value_to_short_string(a)
+        )),
+    }
+}
+
+/// Returns one line for each difference between the values.
+/// Each line starts with the JSON pointer of the difference.
+#[must_use]
+pub fn diff_values(expected: &Value, actual: &Value) -> Vec<String> {
+    let mut out = Vec::new();
+    diff_into("", expected, actual, &mut out);
+    out
+}
+
+/// Converts a page key to a file name.
+///
+/// `/` becomes `index`, `/a/b` becomes `a~b`.
+/// Other bytes that are not ASCII letters, numbers, `-`, or `_` become `%` and two hex digits,
+/// so different keys get different file names.
+/// The only exception is `/index`, which also becomes `index`.
+#[must_use]
+#[allow(clippy::missing_panics_doc)]
+pub fn page_key_to_file_name(key: &str) -> String {
+    let mut name = String::new();
+    for b in key.trim_start_matches('/').bytes() {
+        match b {
+            b'/' => name.push('~'),
+            b if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' => name.push(char::from(b)),
+            b => write!(name, "%{b:02X}").unwrap(),
+        }
+    }
+    if name.is_empty() {
+        "index".to_string()
+    } else {
+        name
+    }
+}
+
+/// Stores serialized pages in files and checks that pages still match them.
+///
+/// Commit the snapshot files.
+/// When a page changes on purpose, run the tests with `APPLIN_UPDATE_SNAPSHOTS=1`
+/// to rewrite the files, and review the changes.
+///
+/// ```no_run
+/// use applin::testing::Snapshots;
+/// # let session: std::sync::Arc<applin::session::ApplinSession<()>> = unimplemented!();
+/// Snapshots::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"))
+///     .check_session("home", &session)
+///     .unwrap();
+/// ```
+pub struct Snapshots {
+    dir: PathBuf,
+    update: bool,
+}
+impl Snapshots {
+    /// Uses snapshot files in `dir`.
+    /// Updates them when the `APPLIN_UPDATE_SNAPSHOTS` environment variable is `1`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            update: std::env::var(UPDATE_SNAPSHOTS_ENV_VAR).is_ok_and(|value| value == "1"),
+        }
+    }
+
+    #[must_use]
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Checks `value` against the snapshot file `{dir}/{name}.json`.
+    /// In update mode, this writes the file instead.
+    ///
+    /// # Errors
+    /// Returns an error listing the differences,
+    /// or when it fails to read or write the file.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn check_value(&self, name: &str, value: &Value) -> Result<(), String> {
+        let path = self.dir.join(format!("{name}.json"));
+        let display = path.display();
+        let contents = serde_json::to_string_pretty(value).unwrap() + "\n";
+        if self.update {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("error creating {}: {e}", parent.display()))?;
+            }
+            return std::fs::write(&path, contents)
+                .map_err(|e| format!("error writing {display}: {e}"));
+        }
+        let stored = match std::fs::read_to_string(&path) {
+            Ok(stored) => stored,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(format!(
+                    "snapshot {display} does not exist, run with {UPDATE_SNAPSHOTS_ENV_VAR}=1 to create it"
+                ))
+            }
+            Err(e) => return Err(format!("error reading {display}: {e}")),
+        };
+        let expected: Value =
+            serde_json::from_str(&stored).map_err(|e| format!("error parsing {display}: {e}"))?;
+        let diffs = diff_values(&expected, value);
+        if diffs.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{display} does not match:\n  {}\nRun with {UPDATE_SNAPSHOTS_ENV_VAR}=1 to update it.",
+                diffs.join("\n  ")
+            ))
+        }
+    }
+
+    /// # Errors
+    /// Returns an error when the page does not match the snapshot file `{dir}/{name}.json`.
+    pub fn check_page(&self, name: &str, page: &Page) -> Result<(), String> {
+        self.check_value(name, &page.to_value())
+    }
+
+    /// Builds every page in the session's `PageMap` and checks each one
+    /// against `{dir}/{name}/{file}.json`.  See [`page_key_to_file_name`].
+    ///
+    /// # Errors
+    /// Returns an error when building fails, a page has problems from [`Page::validate`],
+    /// a page does not match its snapshot, two page keys have the same file name,
+    /// or the directory has a snapshot for a key that is no longer in the `PageMap`.
+    /// In update mode, this deletes those snapshots instead.
+    pub fn check_session<T: 'static + Send + Sync>(
+        &self,
+        name: &str,
+        session: &Arc<ApplinSession<T>>,
+    ) -> Result<(), String> {
+        let pages = session
+            .build_all_pages()
+            .map_err(|e| format!("error building pages: {e}"))?;
+        let mut keys_by_file_name: BTreeMap<String, &str> = BTreeMap::new();
+        for key in pages.keys() {
+            let file_name = page_key_to_file_name(key);
+            if let Some(other) = keys_by_file_name.insert(file_name.clone(), key) {
+                return Err(format!(
+                    "page keys {other:?} and {key:?} both have snapshot file name {file_name:?}"
+                ));
+            }
+        }
+        let mut errors = Vec::new();
+        let mut file_names = BTreeSet::new();
+        for (file_name, key) in keys_by_file_name {
+            for diagnostic in pages[key].validate() {
+                errors.push(format!("page {key:?} {diagnostic}"));
+            }
+            if let Err(e) = self.check_page(&format!("{name}/{file_name}"), &pages[key]) {
+                errors.push(format!("page {key:?}: {e}"));
+            }
+            file_names.insert(format!("{file_name}.json"));
+        }
+        let dir = self.dir.join(name);
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if path.extension().is_none_or(|ext| ext != "json")
+                    || file_names.contains(&file_name)
+                {
+                    continue;
+                }
+                if self.update {
+                    std::fs::remove_file(&path)
+                        .map_err(|e| format!("error deleting {}: {e}", path.display()))?;
+                } else {
+                    errors.push(format!("{} has no page", path.display()));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+}
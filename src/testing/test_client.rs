@@ -1,4 +1,4 @@
-#![allow(dead_code)]
+use crate::session::{PROTOCOL_VERSION, PROTOCOL_VERSION_HEADER};
 use serde::Serialize;
 use servlin::reexport::safina_executor::Executor;
 use servlin::reexport::safina_sync::Receiver;
@@ -11,6 +11,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use ureq::Agent;
 
+/// Messages received on a stream.
 pub struct Messages(Mutex<Vec<serde_json::Value>>);
 impl Messages {
     /// # Panics
@@ -24,6 +25,10 @@ impl Messages {
 
 static CLIENT_ID_COUNT: AtomicU32 = AtomicU32::new(0);
 
+/// An HTTP client that sends requests like an Applin client.
+///
+/// It keeps the session cookie between requests
+/// and sends the newest protocol version.
 pub struct TestClient {
     pub id: u32,
     pub url: String,
@@ -79,6 +84,7 @@ impl TestClient {
         let url = self.url.clone() + path.as_ref();
         self.agent
             .get(&url)
+            .set(PROTOCOL_VERSION_HEADER, &PROTOCOL_VERSION.to_string())
             .call()
             .map_err(Self::ureq_err_to_tuple)?
             .into_json::<serde_json::Value>()
@@ -95,6 +101,7 @@ impl TestClient {
         let url = self.url.clone() + path.as_ref();
         self.agent
             .post(&url)
+            .set(PROTOCOL_VERSION_HEADER, &PROTOCOL_VERSION.to_string())
             .send_json(data)
             .map_err(Self::ureq_err_to_tuple)?
             .into_json::<serde_json::Value>()
@@ -110,6 +117,7 @@ impl TestClient {
         let response = self
             .agent
             .get(&url)
+            .set(PROTOCOL_VERSION_HEADER, &PROTOCOL_VERSION.to_string())
             .call()
             .map_err(Self::ureq_err_to_tuple)?;
         assert_eq!("text/event-stream", response.content_type());
@@ -138,6 +146,9 @@ impl TestClient {
     }
 }
 
+/// Starts an HTTP server on a random port on 127.0.0.1.
+/// Returns the server's URL and a receiver that gets a value when the server stops.
+///
 /// # Panics
 /// Panics when it fails to start the server.
 pub fn start_for_test<F>(executor: &Arc<Executor>, req_handler: F) -> (String, Receiver<()>)
@@ -152,6 +163,6 @@ where
                 .spawn(move |req| req_handler(req).unwrap_or_else(|r| r)),
         )
         .unwrap();
-    let url = format!("http://{addr }");
+    let url = format!("http://{addr}");
     (url, receiver)
 }
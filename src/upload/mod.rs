@@ -0,0 +1,4 @@
+//! Receiving files from clients.
+mod upload_handler;
+
+pub use upload_handler::*;
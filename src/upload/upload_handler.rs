@@ -0,0 +1,254 @@
+use crate::action::choose_file;
+use crate::data::random_positive_nonzero_i64;
+use crate::error::server_error;
+use crate::internal::Action;
+use servlin::{Request, Response};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// A file that a client uploaded.
+///
+/// The file is in the [`UploadHandler`]'s directory.
+/// Dropping the `Upload` deletes the file, unless you moved it with [`Upload::persist`].
+#[derive(Debug)]
+pub struct Upload {
+    content_type: String,
+    len: u64,
+    path: Option<PathBuf>,
+    sha256: String,
+}
+impl Upload {
+    /// The MIME type that the client sent, like `image/png`.
+    #[must_use]
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn path(&self) -> &Path {
+        self.path.as_ref().unwrap()
+    }
+
+    /// The SHA-256 digest of the contents, as 64 lowercase hex digits.
+    #[must_use]
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// # Errors
+    /// Returns an error when it fails to read the file.
+    pub fn read_to_vec(&self) -> Result<Vec<u8>, std::io::Error> {
+        std::fs::read(self.path())
+    }
+
+    /// Moves the file to `dest` and keeps it.
+    ///
+    /// # Errors
+    /// Returns an error when it fails to move the file.
+    pub fn persist(mut self, dest: impl AsRef<Path>) -> Result<(), std::io::Error> {
+        std::fs::rename(self.path(), dest)?;
+        self.path = None;
+        Ok(())
+    }
+}
+impl Drop for Upload {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ignored = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn type_matches(accepted: &str, content_type: &str) -> bool {
+    let content_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match accepted.strip_suffix("/*") {
+        Some(prefix) => content_type
+            .split_once('/')
+            .is_some_and(|(t, _)| t == prefix.to_ascii_lowercase()),
+        None => content_type == accepted.to_ascii_lowercase(),
+    }
+}
+
+/// Receives files that clients upload with [`crate::action::choose_file`],
+/// [`crate::action::choose_photo`], or [`crate::action::take_photo`].
+///
+/// ```no_run
+/// use applin::upload::UploadHandler;
+/// use servlin::{Request, Response};
+///
+/// fn upload_handler(req: &Request) -> Result<Response, Response> {
+///     let handler = UploadHandler::new("/var/app/uploads")
+///         .with_accepted_types(["application/pdf"])
+///         .with_max_bytes(10 * 1024 * 1024);
+///     let upload = handler.handle(req)?;
+///     println!("received {} bytes, sha256={}", upload.len(), upload.sha256());
+///     let dest = format!("/var/app/files/{}.pdf", upload.sha256());
+///     upload
+///         .persist(dest)
+///         .map_err(|e| Response::text(500, e.to_string()))?;
+///     Ok(Response::new(200))
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UploadHandler {
+    accepted_types: Vec<String>,
+    dir: PathBuf,
+    max_bytes: u64,
+}
+impl UploadHandler {
+    /// Makes a handler that writes files to `dir`.
+    /// It accepts all file types, up to 10 MiB.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            accepted_types: Vec::new(),
+            dir: dir.into(),
+            max_bytes: 10 * 1024 * 1024,
+        }
+    }
+
+    /// Accepts only files with these MIME types, like `image/png` or `image/*`.
+    #[must_use]
+    pub fn with_accepted_types<S: Into<String>>(
+        mut self,
+        types: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.accepted_types = types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    #[must_use]
+    pub fn accepted_types(&self) -> &[String] {
+        &self.accepted_types
+    }
+
+    #[must_use]
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Makes a `choose-file` action with this handler's limits.
+    #[must_use]
+    pub fn choose_file_action(&self, upload_url: impl Into<String>) -> Action {
+        choose_file(upload_url, self.accepted_types.clone(), self.max_bytes)
+    }
+
+    /// Returns true when the handler accepts files with `content_type`.
+    #[must_use]
+    pub fn accepts(&self, content_type: &str) -> bool {
+        self.accepted_types.is_empty()
+            || self
+                .accepted_types
+                .iter()
+                .any(|accepted| type_matches(accepted, content_type))
+    }
+
+    /// Checks the request and saves its body.
+    ///
+    /// # Errors
+    /// Returns a response that tells the server to read the body and call the handler again,
+    /// when the server has not read the body yet.
+    ///
+    /// Returns an error response when the request is not a POST,
+    /// the body is too large, the content type is not accepted,
+    /// or it fails to save the file.
+    pub fn handle(&self, req: &Request) -> Result<Upload, Response> {
+        if req.method != "POST" {
+            return Err(Response::method_not_allowed_405(&["POST"]));
+        }
+        let content_type = req.content_type.as_str().to_string();
+        if !self.accepts(&content_type) {
+            return Err(Response::text(
+                415,
+                format!("File type {content_type:?} is not accepted."),
+            ));
+        }
+        if self.max_bytes < req.body.len() {
+            return Err(too_large(self.max_bytes));
+        }
+        if req.body.is_pending() {
+            return Err(Response::get_body_and_reprocess(self.max_bytes));
+        }
+        let reader = req.body.reader().map_err(|e| server_error(e.to_string()))?;
+        self.save(&content_type, reader)
+    }
+
+    /// Saves the bytes from `reader` to a new file in the directory,
+    /// hashing them as it goes.
+    ///
+    /// # Errors
+    /// Returns an error response when the content type is not accepted,
+    /// `reader` has more than the maximum bytes, or it fails to save the file.
+    pub fn save(&self, content_type: &str, mut reader: impl Read) -> Result<Upload, Response> {
+        if !self.accepts(content_type) {
+            return Err(Response::text(
+                415,
+                format!("File type {content_type:?} is not accepted."),
+            ));
+        }
+        let path = self
+            .dir
+            .join(format!("upload-{}.tmp", random_positive_nonzero_i64()));
+        let mut file = File::create(&path).map_err(|e| {
+            server_error(format!(
+                "error creating upload file {}: {e}",
+                path.display()
+            ))
+        })?;
+        let mut upload = Upload {
+            content_type: content_type.to_string(),
+            len: 0,
+            path: Some(path),
+            sha256: String::new(),
+        };
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(server_error(format!("error reading upload: {e}"))),
+            };
+            upload.len += n as u64;
+            if self.max_bytes < upload.len {
+                return Err(too_large(self.max_bytes));
+            }
+            hasher.update(&buf[..n]);
+            file.write_all(&buf[..n])
+                .map_err(|e| server_error(format!("error writing upload file: {e}")))?;
+        }
+        file.sync_all()
+            .map_err(|e| server_error(format!("error writing upload file: {e}")))?;
+        upload.sha256 = format!("{:x}", hasher.finalize());
+        Ok(upload)
+    }
+}
+
+fn too_large(max_bytes: u64) -> Response {
+    Response::text(413, format!("File is larger than {max_bytes} bytes."))
+}
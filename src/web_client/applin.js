@@ -0,0 +1,1386 @@
+// Applin web client.
+// Speaks the same protocol as the iOS client:
+// - GET the poll path for an update,
+// - receive updates on the stream path with server-sent events,
+// - POST vars to RPC paths and apply the update in the response.
+"use strict";
+
+(function () {
+  // The newest protocol version that this client supports.
+  const PROTOCOL_VERSION = 2;
+  // The client stops waiting for a job that sends no progress for this long.
+  const JOB_TIMEOUT_MS = 60 * 1000;
+  const config = document.currentScript.dataset;
+  const pollPath = config.pollPath || "/";
+  const streamPath = config.streamPath || "";
+  const refreshPath = config.refreshPath || "/refresh";
+  const defaultTheme = JSON.parse(config.defaultTheme || "{}");
+  const darkMode = window.matchMedia("(prefers-color-scheme: dark)");
+  const root = document.getElementById("applin");
+
+  // Icon set version 1.  Names that this client lacks show a placeholder.
+  const ICONS = {
+    add: "+",
+    alert: "❗",
+    "arrow-back": "←",
+    "arrow-down": "↓",
+    "arrow-forward": "→",
+    "arrow-up": "↑",
+    bell: "🔔",
+    bookmark: "🔖",
+    calendar: "📅",
+    camera: "📷",
+    cart: "🛒",
+    chat: "💬",
+    check: "✓",
+    "chevron-down": "⌄",
+    "chevron-left": "‹",
+    "chevron-right": "›",
+    "chevron-up": "⌃",
+    clock: "🕒",
+    close: "✕",
+    copy: "⧉",
+    download: "⤓",
+    edit: "✎",
+    filter: "⧩",
+    flag: "⚑",
+    folder: "📁",
+    gear: "⚙",
+    heart: "♥",
+    help: "?",
+    home: "⌂",
+    info: "ℹ",
+    link: "🔗",
+    list: "≡",
+    location: "📍",
+    lock: "🔒",
+    mail: "✉",
+    menu: "☰",
+    minus: "−",
+    more: "⋯",
+    pause: "⏸",
+    person: "👤",
+    phone: "📞",
+    photo: "🖼",
+    play: "▶",
+    refresh: "↻",
+    search: "🔍",
+    share: "⇪",
+    star: "★",
+    trash: "🗑",
+    unlock: "🔓",
+    upload: "⤒",
+    warning: "⚠",
+  };
+
+  const state = {
+    pages: {},
+    stack: ["/"],
+    // Tab page key to the page key of the tab that the user selected.
+    selectedTabs: {},
+    // Page key of a tab to the pages pushed on the tab.
+    tabStacks: {},
+    // Sheet page key to the index of its detent.
+    sheetDetents: {},
+    // Toasts waiting to show, and the visible toast.
+    toasts: [],
+    toast: null,
+    // Job ID to the job's latest status, and the ID of the job that the user waits for.
+    jobs: {},
+    activeJob: null,
+    vars: {},
+    theme: {},
+    eventSource: null,
+    pollTimer: null,
+    working: false,
+  };
+
+  function el(tag, className, text) {
+    const e = document.createElement(tag);
+    if (className) {
+      e.className = className;
+    }
+    if (text !== undefined && text !== null) {
+      e.textContent = text;
+    }
+    return e;
+  }
+
+  function showError(message) {
+    const backdrop = el("div", "modal-backdrop");
+    const alert = el("div", "alert");
+    alert.appendChild(el("div", "title", "Error"));
+    alert.appendChild(el("div", "modal-text", message));
+    const ok = el("button", "button modal-button default", "OK");
+    ok.onclick = () => backdrop.remove();
+    alert.appendChild(ok);
+    backdrop.appendChild(alert);
+    root.querySelector(".phone").appendChild(backdrop);
+  }
+
+  function applyUpdate(update) {
+    const pages = update.pages || {};
+    for (const key of Object.keys(pages)) {
+      if (pages[key] === null) {
+        delete state.pages[key];
+        state.stack = state.stack.filter((k) => k !== key);
+        delete state.selectedTabs[key];
+        delete state.tabStacks[key];
+        delete state.sheetDetents[key];
+        for (const tab of Object.keys(state.tabStacks)) {
+          state.tabStacks[tab] = state.tabStacks[tab].filter((k) => k !== key);
+        }
+      } else {
+        // Select the tab when the server changes the selection.
+        const old = state.pages[key];
+        if (pages[key].selected && (!old || old.selected !== pages[key].selected)) {
+          state.selectedTabs[key] = pages[key].selected;
+        }
+        state.pages[key] = pages[key];
+      }
+    }
+    Object.assign(state.vars, update.vars || {});
+    Object.assign(state.selectedTabs, update["select-tabs"] || {});
+    if (update.theme) {
+      state.theme = update.theme;
+    }
+    state.toasts.push(...(update.toasts || []));
+    // A finished status is final, even when an older status arrives later.
+    for (const job of update.jobs || []) {
+      if (!(state.jobs[job.id] && state.jobs[job.id].done)) {
+        state.jobs[job.id] = job;
+      }
+    }
+    if (update.push && state.pages[update.push]) {
+      pushPage(update.push);
+    }
+    showNextToast();
+    render();
+  }
+
+  // Shows toasts one at a time, each for its seconds or 4 seconds.
+  function showNextToast() {
+    if (state.toast || state.toasts.length === 0) {
+      return;
+    }
+    const toast = state.toasts.shift();
+    state.toast = toast;
+    setTimeout(() => {
+      if (state.toast === toast) {
+        dismissToast();
+      }
+    }, (toast.seconds || 4) * 1000);
+  }
+
+  function dismissToast() {
+    state.toast = null;
+    showNextToast();
+    render();
+  }
+
+  function toastElement(toast) {
+    const div = el("div", "toast");
+    div.appendChild(el("span", "", toast.text));
+    if (toast["button-text"]) {
+      const b = el("button", "", toast["button-text"]);
+      b.onclick = () => {
+        dismissToast();
+        doActions(toast.actions || []);
+      };
+      div.appendChild(b);
+    }
+    return div;
+  }
+
+  // Returns the CSS height of a sheet detent.
+  function detentHeight(detent) {
+    if (detent === "medium") {
+      return "50%";
+    } else if (detent && detent.height) {
+      return detent.height + "px";
+    }
+    return "90%";
+  }
+
+  // Later themes replace tokens from earlier themes.
+  function mergeThemes(...themes) {
+    const merged = { colors: {}, radii: {}, spacing: {}, "text-styles": {} };
+    for (const theme of themes) {
+      for (const group of Object.keys(merged)) {
+        Object.assign(merged[group], (theme && theme[group]) || {});
+      }
+    }
+    return merged;
+  }
+
+  function applyTheme(phone, theme) {
+    const mode = darkMode.matches ? "dark" : "light";
+    for (const [name, color] of Object.entries(theme.colors)) {
+      phone.style.setProperty("--color-" + name, color[mode]);
+    }
+    for (const [name, radius] of Object.entries(theme.radii)) {
+      phone.style.setProperty("--radius-" + name, radius + "px");
+    }
+    for (const [name, size] of Object.entries(theme.spacing)) {
+      phone.style.setProperty("--spacing-" + name, size + "px");
+    }
+    const weights = { regular: 400, medium: 500, semibold: 600, bold: 700 };
+    for (const [name, textStyle] of Object.entries(theme["text-styles"])) {
+      phone.style.setProperty("--text-" + name + "-size", textStyle.size + "px");
+      phone.style.setProperty("--text-" + name + "-weight", weights[textStyle.weight] || 400);
+    }
+  }
+
+  function cssColor(color) {
+    return color.startsWith("#") ? color : "var(--color-" + color + ")";
+  }
+
+  async function responseError(response) {
+    const text = await response.text();
+    if (response.status >= 400 && response.status < 500) {
+      try {
+        return JSON.parse(text).message || text;
+      } catch (e) {
+        return text;
+      }
+    }
+    return "Server error " + response.status + ". " + text;
+  }
+
+  async function poll() {
+    const response = await fetch(pollPath, {
+      credentials: "same-origin",
+      headers: { "applin-protocol-version": String(PROTOCOL_VERSION) },
+    });
+    if (!response.ok) {
+      throw new Error(await responseError(response));
+    }
+    const update = await response.json();
+    applyUpdate(update);
+    return update;
+  }
+
+  // Calls the RPC and shows the progress of the job that it starts until the job finishes.
+  // Gives up when the job sends no progress for JOB_TIMEOUT_MS.
+  async function rpcWithProgress(path) {
+    const update = await rpc(path);
+    if (!update) {
+      return false;
+    }
+    const id = update["started-job"];
+    if (!id) {
+      throw new Error("RPC " + path + " started no job");
+    }
+    state.activeJob = id;
+    render();
+    try {
+      let status = state.jobs[id];
+      let lastProgressMs = Date.now();
+      while (!state.jobs[id].done) {
+        if (Date.now() - lastProgressMs > JOB_TIMEOUT_MS) {
+          throw new Error("The server stopped responding.");
+        }
+        await new Promise((resolve) => setTimeout(resolve, 500));
+        if (!state.eventSource) {
+          await poll();
+        }
+        if (state.jobs[id] !== status) {
+          status = state.jobs[id];
+          lastProgressMs = Date.now();
+        }
+      }
+    } finally {
+      state.activeJob = null;
+      render();
+    }
+    if (state.jobs[id].error) {
+      throw new Error(state.jobs[id].error);
+    }
+    return true;
+  }
+
+  function progressBar(percent, text) {
+    const div = el("div", "");
+    const indeterminate = percent === undefined || percent === null;
+    const bar = el("div", indeterminate ? "progress-bar indeterminate" : "progress-bar");
+    const progress = el("div", "progress");
+    if (!indeterminate) {
+      progress.style.width = percent + "%";
+    }
+    bar.appendChild(progress);
+    div.appendChild(bar);
+    if (text) {
+      div.appendChild(el("div", "progress-text", text));
+    }
+    return div;
+  }
+
+  // Integer widgets send whole numbers, so servers can deserialize them as integers.
+  function numberOrNull(widget, n) {
+    if (n === undefined || n === null || n === "" || Number.isNaN(Number(n))) {
+      return null;
+    }
+    return widget.integer ? Math.round(Number(n)) : Number(n);
+  }
+
+  // Widget type to a function that returns the widget's initial var value.
+  const initialValues = {
+    checkbox: (w) => !!w["initial-bool"],
+    "date-picker": (w) => w["initial-date"] || null,
+    "date-range-picker": (w) => w["initial-range"] || null,
+    "multi-select": (w) => w["initial-ids"] || [],
+    "number-field": (w) => numberOrNull(w, w["initial-number"]),
+    picker: (w) => w["initial-id"] || null,
+    "radio-group": (w) => w["initial-id"] || null,
+    slider: (w) => numberOrNull(w, w["initial-number"] ?? w.min),
+    stepper: (w) => numberOrNull(w, w["initial-number"] ?? w.min ?? 0),
+    textfield: (w) => w["initial-string"] || "",
+    "time-picker": (w) => w["initial-time"] || null,
+  };
+
+  function varValue(widget) {
+    return widget.var in state.vars ? state.vars[widget.var] : initialValues[widget.typ](widget);
+  }
+
+  function setVar(widget, value) {
+    state.vars[widget.var] = value;
+    if (widget.rpc) {
+      doActions(["rpc:" + widget.rpc]);
+    }
+  }
+
+  function pageVars(page) {
+    const vars = {};
+    visit(page, (widget) => {
+      if (widget.typ in initialValues) {
+        vars[widget.var] = varValue(widget);
+      }
+    });
+    return vars;
+  }
+
+  async function rpc(path) {
+    const page = state.pages[topKey()];
+    const response = await fetch(path, {
+      method: "POST",
+      credentials: "same-origin",
+      headers: {
+        "applin-protocol-version": String(PROTOCOL_VERSION),
+        "content-type": "application/json",
+      },
+      body: JSON.stringify(page ? pageVars(page) : {}),
+    });
+    if (response.status === 422) {
+      // The server rejected the input and sent pages that show the errors.
+      applyUpdate(await response.json());
+      return null;
+    }
+    if (!response.ok) {
+      throw new Error(await responseError(response));
+    }
+    const update = await response.json();
+    applyUpdate(update);
+    return update;
+  }
+
+  // Gets the page with `key` again, or reloads the images with URL `key`.
+  async function refresh(key) {
+    if (state.pages[key]) {
+      const response = await fetch(refreshPath, {
+        method: "POST",
+        credentials: "same-origin",
+        headers: {
+          "applin-protocol-version": String(PROTOCOL_VERSION),
+          "content-type": "application/json",
+        },
+        body: JSON.stringify({ key: key }),
+      });
+      if (!response.ok) {
+        throw new Error(await responseError(response));
+      }
+      applyUpdate(await response.json());
+    } else {
+      // Replace the cached image, then draw the page again so the browser shows it.
+      await fetch(key, { cache: "reload", credentials: "same-origin" });
+      render();
+    }
+  }
+
+  // Fetches the lazy list's next chunk and adds it to the widget,
+  // so the rows stay when the page renders again.
+  async function loadMore(widget) {
+    const response = await fetch(widget.url, {
+      method: "POST",
+      credentials: "same-origin",
+      headers: {
+        "applin-protocol-version": String(PROTOCOL_VERSION),
+        "content-type": "application/json",
+      },
+      body: JSON.stringify({ cursor: widget["next-cursor"] }),
+    });
+    if (!response.ok) {
+      throw new Error(await responseError(response));
+    }
+    const chunk = await response.json();
+    widget.widgets = (widget.widgets || []).concat(chunk.widgets || []);
+    widget["next-cursor"] = chunk["next-cursor"];
+    return chunk.widgets || [];
+  }
+
+  function upload(path, capture, accept, maxBytes) {
+    return new Promise((resolve, reject) => {
+      const input = el("input");
+      input.type = "file";
+      input.accept = accept;
+      if (capture) {
+        input.capture = "environment";
+      }
+      // Resolves to false when the user closes the picker without choosing a file.
+      let picking = true;
+      const onFocus = () => {
+        // Browsers without the `cancel` event focus the window before `change`, so wait a moment.
+        setTimeout(() => {
+          if (picking && input.files.length === 0) {
+            stopPicking();
+            resolve(false);
+          }
+        }, 1000);
+      };
+      const stopPicking = () => {
+        picking = false;
+        window.removeEventListener("focus", onFocus);
+      };
+      window.addEventListener("focus", onFocus);
+      input.oncancel = () => {
+        stopPicking();
+        resolve(false);
+      };
+      input.onchange = async () => {
+        stopPicking();
+        const file = input.files[0];
+        if (!file) {
+          resolve(false);
+          return;
+        }
+        if (maxBytes && file.size > maxBytes) {
+          reject(new Error("The file is too large.  The limit is " + maxBytes + " bytes."));
+          return;
+        }
+        try {
+          const response = await fetch(path, {
+            method: "POST",
+            credentials: "same-origin",
+            headers: { "content-type": file.type || "application/octet-stream" },
+            body: file,
+          });
+          if (!response.ok) {
+            throw new Error(await responseError(response));
+          }
+          resolve(true);
+        } catch (e) {
+          reject(e);
+        }
+      };
+      input.click();
+    });
+  }
+
+  function splitAction(action) {
+    const n = action.indexOf(":");
+    return n < 0 ? [action, ""] : [action.slice(0, n), action.slice(n + 1)];
+  }
+
+  // Shows an alert with Cancel and OK buttons.  Resolves to true when the user taps OK.
+  function confirmDialog(title, text) {
+    return new Promise((resolve) => {
+      const backdrop = el("div", "modal-backdrop");
+      const alert = el("div", "alert");
+      alert.appendChild(el("div", "title", title));
+      if (text) {
+        alert.appendChild(el("div", "modal-text", text));
+      }
+      const cancel = el("button", "button modal-button", "Cancel");
+      cancel.onclick = () => {
+        backdrop.remove();
+        resolve(false);
+      };
+      const ok = el("button", "button modal-button default", "OK");
+      ok.onclick = () => {
+        backdrop.remove();
+        resolve(true);
+      };
+      alert.appendChild(cancel);
+      alert.appendChild(ok);
+      backdrop.appendChild(alert);
+      root.querySelector(".phone").appendChild(backdrop);
+    });
+  }
+
+  // Performs actions that contain other actions.
+  async function doObjectAction(action) {
+    switch (action.typ) {
+      case "confirm":
+        return (await confirmDialog(action.title, action.text)) && runActions(action.then);
+      case "rpc": {
+        let update;
+        try {
+          update = await rpc(action.url);
+        } catch (e) {
+          if (!action["on-error"]) {
+            throw e;
+          }
+          update = null;
+        }
+        if (!update) {
+          if (action["on-error"]) {
+            await runActions(action["on-error"]);
+          }
+          return false;
+        }
+        return runActions(action["on-success"]);
+      }
+      default:
+        throw new Error("Unsupported action: " + JSON.stringify(action));
+    }
+  }
+
+  // Returns false when the user cancelled and the remaining actions should not run.
+  async function doAction(action) {
+    if (typeof action === "object") {
+      return doObjectAction(action);
+    }
+    const [name, arg] = splitAction(action);
+    switch (name) {
+      case "choose-file": {
+        const [maxBytes, rest] = splitAction(arg);
+        const [types, path] = splitAction(rest);
+        return upload(path, false, types, Number(maxBytes));
+      }
+      case "choose-photo":
+        return upload(arg, false, "image/*", 0);
+      case "copy-to-clipboard":
+        await navigator.clipboard.writeText(arg);
+        return true;
+      case "launch-url":
+        window.open(arg, "_blank");
+        return true;
+      case "logout":
+        document.cookie = "session=; max-age=0";
+        state.stack = ["/"];
+        state.selectedTabs = {};
+        state.tabStacks = {};
+        state.vars = {};
+        await poll();
+        return true;
+      case "nothing":
+        return true;
+      case "pop":
+        popPage();
+        render();
+        return true;
+      case "push":
+        pushPage(arg);
+        render();
+        return true;
+      case "refresh":
+        await refresh(arg);
+        return true;
+      case "rpc":
+        return (await rpc(arg)) !== null;
+      case "rpc-with-progress":
+        return rpcWithProgress(arg);
+      case "take-photo":
+        return upload(arg, true, "image/*", 0);
+      default:
+        throw new Error("Unsupported action: " + action);
+    }
+  }
+
+  // Returns false when an action stopped the remaining actions.
+  async function runActions(actions) {
+    for (const action of actions || []) {
+      if (!(await doAction(action))) {
+        return false;
+      }
+    }
+    return true;
+  }
+
+  async function doActions(actions) {
+    if (state.working) {
+      return;
+    }
+    state.working = true;
+    root.classList.add("working");
+    try {
+      await runActions(actions);
+    } catch (e) {
+      showError(e.message);
+    } finally {
+      state.working = false;
+      root.classList.remove("working");
+    }
+  }
+
+  function children(widget) {
+    switch (widget.typ) {
+      case "column":
+      case "form":
+      case "form-section":
+      case "lazy-list":
+      case "row":
+      case "stack":
+        return widget.widgets || [];
+      case "grouped-row-table":
+        return (widget["row-groups"] || []).flat(2).filter((w) => w);
+      case "frame":
+      case "padding":
+      case "scroll":
+      case "styled":
+        return [widget.widget];
+      default:
+        return [];
+    }
+  }
+
+  function visit(page, f) {
+    const stack = [...(page.widgets || []), page.start, page.widget, page.end].filter((w) => w);
+    while (stack.length > 0) {
+      const widget = stack.pop();
+      f(widget);
+      stack.push(...children(widget));
+    }
+  }
+
+  function icon(name, size) {
+    const span = el("span", "icon glyph", ICONS[name] || "□");
+    span.title = name;
+    if (size) {
+      span.style.fontSize = size + "px";
+    }
+    return span;
+  }
+
+  function withIcon(e, name) {
+    if (name) {
+      e.insertBefore(icon(name), e.firstChild);
+    }
+    return e;
+  }
+
+  function button(className, text, actions, beforeActions) {
+    const b = el("button", className, text);
+    b.onclick = () => {
+      if (beforeActions) {
+        beforeActions();
+      }
+      doActions(actions);
+    };
+    return b;
+  }
+
+  function appendSpans(parent, spans) {
+    for (const span of spans || []) {
+      const e = el(span.actions ? "a" : "span", span.actions ? "link" : null, span.text || "");
+      if (span.bold) {
+        e.style.fontWeight = "bold";
+      }
+      if (span.italic) {
+        e.style.fontStyle = "italic";
+      }
+      if (span.monospace) {
+        e.style.fontFamily = "monospace";
+      }
+      if (span.strikethrough) {
+        e.style.textDecoration = "line-through";
+      }
+      if (span.color) {
+        e.style.color = cssColor(span.color);
+      }
+      if (span.actions) {
+        e.onclick = (event) => {
+          event.stopPropagation();
+          doActions(span.actions);
+        };
+      }
+      appendSpans(e, span.spans);
+      parent.appendChild(e);
+    }
+  }
+
+  function picker(widget) {
+    const div = el("div", "picker");
+    if (widget.label) {
+      div.appendChild(el("label", null, widget.label));
+    }
+    return div;
+  }
+
+  function dateInput(type, value, min, max) {
+    const input = el("input");
+    input.type = type;
+    input.value = value || "";
+    if (min) {
+      input.min = min;
+    }
+    if (max) {
+      input.max = max;
+    }
+    return input;
+  }
+
+  function choice(type, name, option, checked, onchange) {
+    const label = el("label", "choice");
+    const input = el("input");
+    input.type = type;
+    input.name = name;
+    input.checked = checked;
+    input.onchange = () => onchange(input.checked);
+    label.appendChild(input);
+    label.appendChild(document.createTextNode(" " + option.text));
+    return label;
+  }
+
+  function numberInput(widget, type) {
+    const input = dateInput(type, varValue(widget), widget.min, widget.max);
+    const step = widget.step ?? (widget.integer ? 1 : type === "range" ? "any" : null);
+    if (step !== null) {
+      input.step = step;
+    }
+    return input;
+  }
+
+  function units(parent, widget) {
+    if (widget.units) {
+      parent.appendChild(el("span", "units", " " + widget.units));
+    }
+  }
+
+  const FLEX_ALIGN = { start: "flex-start", top: "flex-start", center: "center", end: "flex-end", bottom: "flex-end" };
+
+  const renderers = {
+    "back-button": (w) => button("button", "‹ Back", w.actions),
+    button: (w) => withIcon(button("button", w.text, w.actions), w.icon),
+    checkbox: (w) => {
+      const label = el("label", "checkbox");
+      const input = el("input");
+      input.type = "checkbox";
+      input.checked = !!varValue(w);
+      input.onchange = () => setVar(w, input.checked);
+      label.appendChild(input);
+      label.appendChild(document.createTextNode(" " + w.text));
+      return label;
+    },
+    column: (w) => {
+      const div = el("div", "column");
+      div.style.alignItems = FLEX_ALIGN[w["h-alignment"]] || "flex-start";
+      div.style.gap = (w.spacing || 0) + "px";
+      appendAll(div, w.widgets);
+      return div;
+    },
+    "date-picker": (w) => {
+      const div = picker(w);
+      const input = dateInput("date", varValue(w), w["min-date"], w["max-date"]);
+      input.onchange = () => setVar(w, input.value || null);
+      div.appendChild(input);
+      return div;
+    },
+    "date-range-picker": (w) => {
+      const div = picker(w);
+      const [start, end] = (varValue(w) || "/").split("/");
+      const startInput = dateInput("date", start, w["min-date"], w["max-date"]);
+      const endInput = dateInput("date", end, w["min-date"], w["max-date"]);
+      const onchange = () => {
+        if (startInput.value && endInput.value && startInput.value <= endInput.value) {
+          setVar(w, startInput.value + "/" + endInput.value);
+        }
+      };
+      startInput.onchange = onchange;
+      endInput.onchange = onchange;
+      div.appendChild(startInput);
+      div.appendChild(document.createTextNode(" – "));
+      div.appendChild(endInput);
+      return div;
+    },
+    empty: () => null,
+    "error-details": () => el("div", "error-details", "(error details)"),
+    "error-text": (w) => el("div", "text error-text", "⚠️ " + w.text),
+    form: (w) => {
+      const div = el("div", "form");
+      appendAll(div, w.widgets);
+      return div;
+    },
+    "form-button": (w) =>
+      withIcon(button(w["is-destructive"] ? "button destructive" : "button", w.text, w.actions), w.icon),
+    "form-section": (w) => {
+      const div = el("div", "form-section");
+      if (w.title) {
+        div.appendChild(el("div", "section-title", w.title));
+      }
+      appendAll(div, w.widgets);
+      return div;
+    },
+    frame: (w) => {
+      const div = el("div", "frame");
+      for (const name of ["max-height", "max-width", "min-height", "min-width"]) {
+        if (w[name] !== undefined) {
+          div.style.setProperty(name, w[name] + "px");
+        }
+      }
+      if (w.weight) {
+        div.style.flex = w.weight + " 1 0";
+      }
+      appendWidget(div, w.widget);
+      return div;
+    },
+    "grouped-row-table": (w) => {
+      const table = el("table", "grouped-row-table");
+      table.style.borderSpacing = (w.spacing || 0) + "px";
+      for (const group of w["row-groups"] || []) {
+        const tbody = el("tbody");
+        for (const row of group) {
+          const tr = el("tr");
+          for (const cell of row) {
+            const td = el("td");
+            if (cell) {
+              appendWidget(td, cell);
+            }
+            tr.appendChild(td);
+          }
+          tbody.appendChild(tr);
+        }
+        table.appendChild(tbody);
+      }
+      return table;
+    },
+    icon: (w) => icon(w.name, w.size),
+    image: (w) => {
+      const div = el("div", "image");
+      div.style.aspectRatio = String(w["aspect-ratio"]);
+      div.style.backgroundImage = "url(" + JSON.stringify(w.url) + ")";
+      div.style.backgroundSize = { cover: "cover", fit: "contain", stretch: "100% 100%" }[w.disposition];
+      return div;
+    },
+    "lazy-list": (w) => {
+      const div = el("div", "column lazy-list");
+      div.style.gap = (w.spacing || 0) + "px";
+      appendAll(div, w.widgets);
+      if (w["next-cursor"]) {
+        // Append rows in place, so the user keeps their scroll position.
+        const loading = el("div", "lazy-list-loading", "Loading...");
+        const observer = new IntersectionObserver(async (entries) => {
+          if (!entries.some((entry) => entry.isIntersecting)) {
+            return;
+          }
+          observer.disconnect();
+          try {
+            for (const widget of await loadMore(w)) {
+              appendWidget(div, widget);
+            }
+          } catch (e) {
+            showError(e.message);
+            return;
+          }
+          div.appendChild(loading);
+          if (w["next-cursor"]) {
+            observer.observe(loading);
+          } else {
+            loading.remove();
+          }
+        });
+        div.appendChild(loading);
+        observer.observe(loading);
+      }
+      return div;
+    },
+    "modal-button": (w) => {
+      let className = "button modal-button";
+      if (w["is-default"] || w["is-cancel"]) {
+        className += " default";
+      }
+      if (w["is-destructive"]) {
+        className += " destructive";
+      }
+      // Tapping a modal button closes the modal.
+      return button(className, w.text, w.actions, () => {
+        state.stack.pop();
+        render();
+      });
+    },
+    "multi-select": (w) => {
+      const div = picker(w);
+      const chosen = new Set(varValue(w));
+      for (const option of w.options) {
+        div.appendChild(
+          choice("checkbox", w.var, option, chosen.has(option.id), (checked) => {
+            if (checked) {
+              chosen.add(option.id);
+            } else {
+              chosen.delete(option.id);
+            }
+            // Keep the ids in the order of the options.
+            setVar(
+              w,
+              w.options.map((o) => o.id).filter((id) => chosen.has(id)),
+            );
+          }),
+        );
+      }
+      return div;
+    },
+    "nav-button": (w) => {
+      const div = el("div", "nav-button");
+      if (w["photo-url"]) {
+        const img = el("img");
+        img.src = w["photo-url"];
+        img.alt = "";
+        div.appendChild(img);
+      }
+      if (w.icon) {
+        div.appendChild(icon(w.icon));
+      }
+      const labels = el("div", "labels", w.text);
+      if (w["sub-text"]) {
+        labels.appendChild(el("div", "sub-text", w["sub-text"]));
+      }
+      div.appendChild(labels);
+      if (w["badge-text"]) {
+        div.appendChild(el("span", "badge", w["badge-text"]));
+      }
+      div.appendChild(el("span", "chevron", "›"));
+      div.onclick = () => doActions(w.actions);
+      return div;
+    },
+    "number-field": (w) => {
+      const div = picker(w);
+      const input = numberInput(w, "number");
+      input.onchange = () => {
+        if (input.value !== "" && !input.checkValidity()) {
+          showError(input.validationMessage);
+          return;
+        }
+        setVar(w, numberOrNull(w, input.value));
+      };
+      div.appendChild(input);
+      units(div, w);
+      return div;
+    },
+    padding: (w) => {
+      const div = el("div", "padding");
+      div.style.padding = [w.top, w.end, w.bottom, w.start].map((n) => (n || 0) + "px").join(" ");
+      appendWidget(div, w.widget);
+      return div;
+    },
+    picker: (w) => {
+      const div = picker(w);
+      const select = el("select");
+      const value = varValue(w);
+      if (value === null) {
+        select.appendChild(el("option"));
+      }
+      for (const option of w.options) {
+        const o = el("option", null, option.text);
+        o.value = option.id;
+        o.selected = option.id === value;
+        select.appendChild(o);
+      }
+      select.onchange = () => setVar(w, select.value || null);
+      div.appendChild(select);
+      return div;
+    },
+    "progress-bar": (w) => progressBar(w.percent, w.text),
+    "radio-group": (w) => {
+      const div = picker(w);
+      const value = varValue(w);
+      for (const option of w.options) {
+        div.appendChild(choice("radio", w.var, option, option.id === value, () => setVar(w, option.id)));
+      }
+      return div;
+    },
+    "rich-text": (w) => {
+      const div = el("div", "text rich-text");
+      appendSpans(div, w.spans);
+      return div;
+    },
+    row: (w) => {
+      const div = el("div", "row");
+      div.style.alignItems = FLEX_ALIGN[w["v-alignment"]] || "flex-start";
+      div.style.gap = (w.spacing || 0) + "px";
+      appendAll(div, w.widgets);
+      return div;
+    },
+    scroll: (w) => {
+      const div = el("div", "scroll");
+      appendWidget(div, w.widget);
+      return div;
+    },
+    slider: (w) => {
+      const div = picker(w);
+      const input = numberInput(w, "range");
+      const value = el("span", null, " " + varValue(w));
+      input.oninput = () => {
+        value.textContent = " " + input.value;
+      };
+      input.onchange = () => setVar(w, numberOrNull(w, input.value));
+      div.appendChild(input);
+      div.appendChild(value);
+      units(div, w);
+      return div;
+    },
+    spacer: (w) => {
+      const div = el("div", "spacer");
+      div.style.flex = "1 0 " + (w["min-size"] || 0) + "px";
+      return div;
+    },
+    stack: (w) => {
+      const div = el("div", "stack");
+      div.style.justifyItems = FLEX_ALIGN[w["h-alignment"]] || "flex-start";
+      div.style.alignItems = FLEX_ALIGN[w["v-alignment"]] || "flex-start";
+      appendAll(div, w.widgets);
+      return div;
+    },
+    styled: (w) => {
+      const div = el("div", "styled");
+      const style = w.style || {};
+      if (style.color) {
+        div.style.color = cssColor(style.color);
+      }
+      if (style.background) {
+        div.style.background = cssColor(style.background);
+      }
+      if (style["corner-radius"]) {
+        div.style.borderRadius = "var(--radius-" + style["corner-radius"] + ")";
+      }
+      if (style.padding) {
+        div.style.padding = "var(--spacing-" + style.padding + ")";
+      }
+      if (style["text-style"]) {
+        div.style.fontSize = "var(--text-" + style["text-style"] + "-size)";
+        div.style.fontWeight = "var(--text-" + style["text-style"] + "-weight)";
+      }
+      appendWidget(div, w.widget);
+      return div;
+    },
+    stepper: (w) => {
+      const div = picker(w);
+      const span = el("span", "stepper");
+      const step = Number(w.step ?? 1);
+      const change = (sign) => {
+        let n = Number(varValue(w)) + sign * step;
+        if (w.min !== undefined) {
+          n = Math.max(n, w.min);
+        }
+        if (w.max !== undefined) {
+          n = Math.min(n, w.max);
+        }
+        setVar(w, numberOrNull(w, n));
+        render();
+      };
+      span.appendChild(button("button", "−", []));
+      span.lastChild.onclick = () => change(-1);
+      span.appendChild(document.createTextNode(" " + varValue(w)));
+      units(span, w);
+      span.appendChild(button("button", "+", []));
+      span.lastChild.onclick = () => change(1);
+      div.appendChild(span);
+      return div;
+    },
+    text: (w) => el("div", "text", w.text),
+    "time-picker": (w) => {
+      const div = picker(w);
+      const input = dateInput("time", varValue(w), w["min-time"], w["max-time"]);
+      input.onchange = () => setVar(w, input.value || null);
+      div.appendChild(input);
+      return div;
+    },
+    textfield: (w) => {
+      const div = el("div", "textfield");
+      if (w.label) {
+        div.appendChild(el("label", null, w.label));
+      }
+      const input = w["max-lines"] === 1 ? el("input") : el("textarea");
+      input.value = varValue(w);
+      if (w["max-chars"] !== undefined) {
+        input.maxLength = w["max-chars"];
+      }
+      input.oninput = () => {
+        state.vars[w.var] = input.value;
+      };
+      if (w.rpc) {
+        input.onchange = () => doActions(["rpc:" + w.rpc]);
+      }
+      div.appendChild(input);
+      if (w.error) {
+        div.appendChild(el("div", "error", "⚠️ " + w.error));
+      }
+      return div;
+    },
+  };
+
+  function appendWidget(parent, widget) {
+    const renderer = renderers[widget.typ];
+    const child = renderer ? renderer(widget) : el("div", "error-text", "Unsupported widget: " + widget.typ);
+    if (child) {
+      parent.appendChild(child);
+    }
+  }
+
+  function appendAll(parent, widgets) {
+    for (const widget of widgets || []) {
+      appendWidget(parent, widget);
+    }
+  }
+
+  // Returns the page key of the selected tab, when the page at `key` is a tab page.
+  // `key` defaults to the top of the stack.
+  function selectedTab(key = state.stack[state.stack.length - 1]) {
+    const page = state.pages[key];
+    if (!page || page.typ !== "tab-page") {
+      return null;
+    }
+    const keys = page.tabs.map((tab) => tab["page-key"]);
+    const selected = state.selectedTabs[key];
+    return keys.includes(selected) ? selected : page.selected || keys[0] || null;
+  }
+
+  // Returns the key of the visible page.
+  // When a tab page is on top of the stack, this is the top page of the selected tab.
+  function topKey(key = state.stack[state.stack.length - 1]) {
+    const tab = selectedTab(key);
+    if (tab) {
+      const tabStack = state.tabStacks[tab] || [];
+      return tabStack.length > 0 ? tabStack[tabStack.length - 1] : tab;
+    }
+    return key;
+  }
+
+  function canPop() {
+    const tab = selectedTab();
+    return (tab && (state.tabStacks[tab] || []).length > 0) || state.stack.length > 1;
+  }
+
+  function popPage() {
+    const tab = selectedTab();
+    if (tab && (state.tabStacks[tab] || []).length > 0) {
+      state.tabStacks[tab].pop();
+    } else if (state.stack.length > 1) {
+      state.stack.pop();
+    }
+  }
+
+  // Pushes pages on the selected tab.  Modals cover the tab bar.
+  function pushPage(key) {
+    const tab = selectedTab();
+    const page = state.pages[key];
+    const isModal = page && ["alert-modal", "drawer-modal", "sheet-modal"].includes(page.typ);
+    // Sheets open at their first detent.
+    delete state.sheetDetents[key];
+    if (tab && !isModal) {
+      state.tabStacks[tab] = (state.tabStacks[tab] || []).concat([key]);
+    } else {
+      state.stack.push(key);
+    }
+  }
+
+  function tabBar(key, page, selected) {
+    const bar = el("div", "tab-bar");
+    for (const tab of page.tabs) {
+      const tabKey = tab["page-key"];
+      const b = el("button", tabKey === selected ? "tab selected" : "tab");
+      if (tab.icon) {
+        const div = el("div", "tab-icon");
+        div.appendChild(icon(tab.icon));
+        b.appendChild(div);
+      }
+      b.appendChild(document.createTextNode(tab.title));
+      if (tab["badge-text"]) {
+        b.appendChild(el("span", "badge", tab["badge-text"]));
+      }
+      // Tapping the selected tab returns to the tab's first page.
+      b.onclick = () => {
+        if (tabKey === selected) {
+          delete state.tabStacks[tabKey];
+        }
+        state.selectedTabs[key] = tabKey;
+        render();
+      };
+      bar.appendChild(b);
+    }
+    return bar;
+  }
+
+  // Calls the page's pull-to-refresh RPC when the user drags the content down from the top.
+  function addPullToRefresh(content, page) {
+    const path = page["pull-to-refresh"];
+    if (!path) {
+      return;
+    }
+    const indicator = el("div", "pull-to-refresh", "↻ Refresh");
+    indicator.onclick = () => doActions(["rpc:" + path]);
+    content.appendChild(indicator);
+    let startY = null;
+    content.addEventListener("pointerdown", (event) => {
+      startY = content.scrollTop === 0 ? event.clientY : null;
+    });
+    content.addEventListener("pointerup", (event) => {
+      if (startY !== null && event.clientY - startY > 60) {
+        doActions(["rpc:" + path]);
+      }
+      startY = null;
+    });
+  }
+
+  function renderPage(phone, key) {
+    const page = state.pages[key];
+    switch (page.typ) {
+      case "alert-modal":
+      case "drawer-modal": {
+        // Show the page under the modal.
+        const under = state.stack[state.stack.length - 2];
+        if (under && state.pages[under]) {
+          renderPage(phone, under);
+        }
+        const backdrop = el("div", "modal-backdrop");
+        const modal = el("div", page.typ === "alert-modal" ? "alert" : "drawer");
+        modal.appendChild(el("div", "title", page.title));
+        if (page.text) {
+          modal.appendChild(el("div", "modal-text", page.text));
+        }
+        appendAll(modal, page.widgets);
+        backdrop.appendChild(modal);
+        phone.appendChild(backdrop);
+        break;
+      }
+      case "sheet-modal": {
+        const under = state.stack[state.stack.length - 2];
+        if (under && state.pages[under]) {
+          renderPage(phone, under);
+        }
+        const backdrop = el("div", "modal-backdrop");
+        // Tapping above the sheet closes it.
+        backdrop.onclick = (event) => {
+          if (event.target === backdrop) {
+            popPage();
+            render();
+          }
+        };
+        const detents = page.detents || [];
+        const n = (state.sheetDetents[key] || 0) % Math.max(detents.length, 1);
+        const sheet = el("div", "sheet");
+        sheet.style.height = detentHeight(detents[n]);
+        // Tapping the grabber moves the sheet to its next detent.
+        const grabber = el("div", "sheet-grabber");
+        grabber.onclick = () => {
+          state.sheetDetents[key] = n + 1;
+          render();
+        };
+        sheet.appendChild(grabber);
+        sheet.appendChild(el("div", "title", page.title));
+        const content = el("div", "content");
+        appendWidget(content, page.widget);
+        sheet.appendChild(content);
+        backdrop.appendChild(sheet);
+        phone.appendChild(backdrop);
+        break;
+      }
+      case "nav-page": {
+        const bar = el("div", "nav-bar");
+        const start = el("div", "start");
+        if (page.start) {
+          appendWidget(start, page.start);
+        } else if (canPop()) {
+          start.appendChild(button("button", "‹ Back", ["pop"]));
+        }
+        bar.appendChild(start);
+        bar.appendChild(el("div", "title", page.title));
+        const end = el("div", "end");
+        if (page.end) {
+          appendWidget(end, page.end);
+        }
+        bar.appendChild(end);
+        phone.appendChild(bar);
+        const content = el("div", "content");
+        addPullToRefresh(content, page);
+        appendWidget(content, page.widget);
+        phone.appendChild(content);
+        break;
+      }
+      case "plain-page": {
+        const content = el("div", "content");
+        addPullToRefresh(content, page);
+        appendWidget(content, page.widget);
+        phone.appendChild(content);
+        break;
+      }
+      case "tab-page": {
+        const selected = selectedTab(key);
+        const visible = topKey(key);
+        if (visible !== key && state.pages[visible]) {
+          renderPage(phone, visible);
+        } else {
+          phone.appendChild(el("div", "content", "Loading..."));
+        }
+        phone.appendChild(tabBar(key, page, selected));
+        break;
+      }
+      default:
+        phone.appendChild(el("div", "error-text", "Unsupported page: " + page.typ));
+    }
+  }
+
+  function updateConnection(page) {
+    const wantStream = !!(page && page.stream && streamPath);
+    if (wantStream && !state.eventSource) {
+      // EventSource cannot send headers, so the protocol version goes in the query.
+      const streamUrl =
+        streamPath +
+        (streamPath.includes("?") ? "&" : "?") +
+        "applin-protocol-version=" +
+        PROTOCOL_VERSION;
+      state.eventSource = new EventSource(streamUrl, { withCredentials: true });
+      state.eventSource.onmessage = (event) => applyUpdate(JSON.parse(event.data));
+    } else if (!wantStream && state.eventSource) {
+      state.eventSource.close();
+      state.eventSource = null;
+    }
+    const pollSeconds = page ? page["poll-seconds"] || 0 : 0;
+    if (state.pollTimer) {
+      clearInterval(state.pollTimer);
+      state.pollTimer = null;
+    }
+    if (pollSeconds > 0) {
+      state.pollTimer = setInterval(() => poll().catch((e) => console.log(e)), pollSeconds * 1000);
+    }
+  }
+
+  function render() {
+    const page = state.pages[topKey()];
+    const stackKey = state.stack[state.stack.length - 1];
+    const phone = el("div", "phone");
+    applyTheme(phone, mergeThemes(defaultTheme, state.theme, page && page.theme));
+    if (page && state.pages[stackKey]) {
+      document.title = page.title;
+      renderPage(phone, stackKey);
+    } else {
+      phone.appendChild(el("div", "text", "Loading..."));
+    }
+    if (state.toast) {
+      phone.appendChild(toastElement(state.toast));
+    }
+    if (state.activeJob) {
+      const job = state.jobs[state.activeJob];
+      const backdrop = el("div", "modal-backdrop");
+      const alert = el("div", "alert");
+      alert.appendChild(el("div", "title", "Working..."));
+      alert.appendChild(progressBar(job.percent, job.text));
+      backdrop.appendChild(alert);
+      phone.appendChild(backdrop);
+    }
+    root.replaceChildren(phone);
+    updateConnection(page);
+  }
+
+  darkMode.addEventListener("change", render);
+  render();
+  poll().catch((e) => showError(e.message));
+})();
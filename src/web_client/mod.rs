@@ -0,0 +1,124 @@
+//! A browser client that speaks the Applin protocol.
+//!
+//! It renders pages with the same styles as [`crate::preview`],
+//! performs actions, and updates the page from polls and the stream.
+//! Use it to try apps and run demos without building a mobile client.
+use crate::error::client_error;
+use crate::preview::escape_html;
+use crate::style::Theme;
+use servlin::{ContentType, Request, Response};
+
+const JS: &str = include_str!("applin.js");
+const CSS: &str = include_str!("../preview.css");
+const EXTRA_CSS: &str = "
+.working { cursor: progress; }
+.working .phone { opacity: 0.7; }
+.checkbox { display: block; margin: 0.25em 0.5em; }
+.nav-button { cursor: pointer; }
+";
+
+/// Serves the browser client.
+///
+/// Route requests for `prefix` and paths starting with `prefix` to [`WebClient::response`].
+///
+/// ```
+/// use applin::web_client::WebClient;
+/// let web_client = WebClient::new("/web").with_stream_path("/stream");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebClient {
+    prefix: String,
+    poll_path: String,
+    refresh_path: String,
+    stream_path: Option<String>,
+}
+impl WebClient {
+    /// Makes a client served at `prefix` that polls `/`, streams from `/stream`,
+    /// and refreshes pages with `/refresh`.
+    #[must_use]
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into().trim_end_matches('/').to_string(),
+            poll_path: "/".to_string(),
+            refresh_path: "/refresh".to_string(),
+            stream_path: Some("/stream".to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_poll_path(mut self, path: impl Into<String>) -> Self {
+        self.poll_path = path.into();
+        self
+    }
+
+    /// Sets the path for `refresh` actions with page keys.
+    /// See [`crate::session::ApplinSession::refresh_response`].
+    #[must_use]
+    pub fn with_refresh_path(mut self, path: impl Into<String>) -> Self {
+        self.refresh_path = path.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_stream_path(mut self, path: impl Into<String>) -> Self {
+        self.stream_path = Some(path.into());
+        self
+    }
+
+    /// Makes the client only poll, for servers that don't serve a stream.
+    #[must_use]
+    pub fn without_stream(mut self) -> Self {
+        self.stream_path = None;
+        self
+    }
+
+    #[must_use]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the page that loads the client.
+    /// It embeds the default theme, which session and page themes override.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn index_html(&self) -> String {
+        let default_theme = serde_json::to_string(&Theme::new()).unwrap();
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+<title>Applin</title><link rel=\"stylesheet\" href=\"{}/applin.css\"></head>\n\
+<body><div id=\"applin\"></div>\
+<script src=\"{}/applin.js\" data-poll-path=\"{}\" data-stream-path=\"{}\" \
+data-refresh-path=\"{}\" data-default-theme=\"{}\"></script>\
+</body></html>\n",
+            escape_html(&self.prefix),
+            escape_html(&self.prefix),
+            escape_html(&self.poll_path),
+            escape_html(self.stream_path.as_deref().unwrap_or_default()),
+            escape_html(&self.refresh_path),
+            escape_html(&default_theme),
+        )
+    }
+
+    /// Returns the page that loads the client, the client's script, or its stylesheet.
+    ///
+    /// # Errors
+    /// Returns an error when the request path does not start with the prefix,
+    /// and a 404 response for other paths under the prefix.
+    pub fn response(&self, req: &Request) -> Result<Response, Response> {
+        let path =
+            req.url.path().strip_prefix(&self.prefix).ok_or_else(|| {
+                client_error(format!("path does not start with {:?}", self.prefix))
+            })?;
+        match path {
+            "" | "/" => Ok(Response::html(200, self.index_html())),
+            "/applin.js" => Ok(Response::new(200)
+                .with_type(ContentType::JavaScript)
+                .with_body(JS)),
+            "/applin.css" => Ok(Response::new(200)
+                .with_type(ContentType::Css)
+                .with_body(format!("{CSS}{EXTRA_CSS}"))),
+            _ => Err(Response::not_found_404()),
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use crate::internal::{Action, Widget};
+use crate::style::Style;
+use crate::widget::Styled;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Button {
     actions: Vec<Action>,
+    icon: Option<String>,
+    style: Option<Style>,
     text: String,
 }
 impl Button {
@@ -10,6 +14,8 @@ impl Button {
     pub fn new(text: impl Into<String>) -> Self {
         Self {
             actions: Vec::new(),
+            icon: None,
+            style: None,
             text: text.into(),
         }
     }
@@ -28,12 +34,30 @@ impl Button {
         self
     }
 
+    /// Shows the icon with `name` before the text.  See [`crate::style::ICON_NAMES`].
+    #[must_use]
+    pub fn with_icon(mut self, name: impl Into<String>) -> Self {
+        self.icon = Some(name.into());
+        self
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
     #[must_use]
     pub fn to_widget(self) -> Widget {
-        Widget::ButtonVariant {
-            actions: self.actions,
-            text: self.text,
-        }
+        Styled::wrap(
+            self.style,
+            Widget::ButtonVariant {
+                actions: self.actions,
+                icon: self.icon,
+                text: self.text,
+            },
+        )
     }
 }
 impl From<Button> for Widget {
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// An option in a [`crate::widget::RadioGroup`], [`crate::widget::Picker`],
+/// or [`crate::widget::MultiSelect`].
+///
+/// The client shows `text` and puts `id` in the widget's var.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Choice {
+    pub id: String,
+    pub text: String,
+}
+impl Choice {
+    #[must_use]
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+        }
+    }
+}
+impl<I: Into<String>, T: Into<String>> From<(I, T)> for Choice {
+    fn from((id, text): (I, T)) -> Self {
+        Self::new(id, text)
+    }
+}
@@ -1,10 +1,12 @@
 use crate::internal::{Widget, WidgetList};
-use crate::widget::HAlignment;
+use crate::style::Style;
+use crate::widget::{HAlignment, Styled};
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Column {
     h_alignment: HAlignment,
     spacing: u16,
+    style: Option<Style>,
     widgets: Vec<Widget>,
 }
 impl Column {
@@ -15,6 +17,7 @@ impl Column {
         Self {
             h_alignment: HAlignment::Start,
             spacing: 0,
+            style: None,
             widgets: widgets.into().0,
         }
     }
@@ -45,13 +48,23 @@ impl Column {
         self
     }
 
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
     #[must_use]
     pub fn to_widget(self) -> Widget {
-        Widget::ColumnVariant {
-            h_alignment: self.h_alignment,
-            spacing: self.spacing,
-            widgets: self.widgets,
-        }
+        Styled::wrap(
+            self.style,
+            Widget::ColumnVariant {
+                h_alignment: self.h_alignment,
+                spacing: self.spacing,
+                widgets: self.widgets,
+            },
+        )
     }
 }
 impl From<Column> for Widget {
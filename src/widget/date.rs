@@ -0,0 +1,244 @@
+use core::fmt::{Display, Formatter};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+fn parse_digits(s: &str, len: usize) -> Option<u16> {
+    if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// A calendar date, without a time zone.
+///
+/// On the wire, it is an ISO-8601 string like `2024-02-29`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+impl Date {
+    /// # Panics
+    /// Panics when the date does not exist or the year is not in `1..=9999`.
+    #[must_use]
+    pub fn new(year: u16, month: u8, day: u8) -> Self {
+        Self::try_new(year, month, day).unwrap()
+    }
+
+    /// # Errors
+    /// Returns an error when the date does not exist or the year is not in `1..=9999`.
+    pub fn try_new(year: u16, month: u8, day: u8) -> Result<Self, String> {
+        if (1..=9999).contains(&year) && (1..=days_in_month(year, month)).contains(&day) {
+            Ok(Self { year, month, day })
+        } else {
+            Err(format!("invalid date: {year:04}-{month:02}-{day:02}"))
+        }
+    }
+
+    #[must_use]
+    pub fn year(self) -> u16 {
+        self.year
+    }
+
+    #[must_use]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    #[must_use]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+}
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        match (
+            parts.next().and_then(|p| parse_digits(p, 4)),
+            parts.next().and_then(|p| parse_digits(p, 2)),
+            parts.next().and_then(|p| parse_digits(p, 2)),
+            parts.next(),
+        ) {
+            #[allow(clippy::cast_possible_truncation)]
+            (Some(year), Some(month), Some(day), None) => {
+                Self::try_new(year, month as u8, day as u8)
+            }
+            _ => Err(format!("expected a date like 2024-12-31, got {s:?}")),
+        }
+    }
+}
+
+/// A time of day, in hours and minutes, without a time zone.
+///
+/// On the wire, it is an ISO-8601 string like `09:30` or `23:59`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+impl Time {
+    /// # Panics
+    /// Panics when `hour` is greater than 23 or `minute` is greater than 59.
+    #[must_use]
+    pub fn new(hour: u8, minute: u8) -> Self {
+        Self::try_new(hour, minute).unwrap()
+    }
+
+    /// # Errors
+    /// Returns an error when `hour` is greater than 23 or `minute` is greater than 59.
+    pub fn try_new(hour: u8, minute: u8) -> Result<Self, String> {
+        if hour < 24 && minute < 60 {
+            Ok(Self { hour, minute })
+        } else {
+            Err(format!("invalid time: {hour:02}:{minute:02}"))
+        }
+    }
+
+    #[must_use]
+    pub fn hour(self) -> u8 {
+        self.hour
+    }
+
+    #[must_use]
+    pub fn minute(self) -> u8 {
+        self.minute
+    }
+}
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+impl FromStr for Time {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            #[allow(clippy::cast_possible_truncation)]
+            Some((h, m)) => match (parse_digits(h, 2), parse_digits(m, 2)) {
+                (Some(hour), Some(minute)) => Self::try_new(hour as u8, minute as u8),
+                _ => Err(format!("expected a time like 23:59, got {s:?}")),
+            },
+            None => Err(format!("expected a time like 23:59, got {s:?}")),
+        }
+    }
+}
+
+/// A range of dates, including both `start` and `end`.
+///
+/// On the wire, it is an ISO-8601 interval like `2024-12-24/2024-12-31`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DateRange {
+    start: Date,
+    end: Date,
+}
+impl DateRange {
+    /// # Panics
+    /// Panics when `end` is before `start`.
+    #[must_use]
+    pub fn new(start: Date, end: Date) -> Self {
+        Self::try_new(start, end).unwrap()
+    }
+
+    /// # Errors
+    /// Returns an error when `end` is before `start`.
+    pub fn try_new(start: Date, end: Date) -> Result<Self, String> {
+        if start <= end {
+            Ok(Self { start, end })
+        } else {
+            Err(format!("date range ends before it starts: {start}/{end}"))
+        }
+    }
+
+    #[must_use]
+    pub fn start(self) -> Date {
+        self.start
+    }
+
+    #[must_use]
+    pub fn end(self) -> Date {
+        self.end
+    }
+
+    #[must_use]
+    pub fn contains(self, date: Date) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+impl Display for DateRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{}/{}", self.start, self.end)
+    }
+}
+impl FromStr for DateRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('/').ok_or_else(|| {
+            format!("expected a date range like 2024-12-24/2024-12-31, got {s:?}")
+        })?;
+        Self::try_new(start.parse()?, end.parse()?)
+    }
+}
+
+macro_rules! string_serde {
+    ($type:ident, $pattern:literal) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+        #[cfg(feature = "schema")]
+        impl schemars::JsonSchema for $type {
+            fn schema_name() -> String {
+                stringify!($type).to_string()
+            }
+
+            fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                use schemars::schema::{InstanceType, SchemaObject, StringValidation};
+                SchemaObject {
+                    instance_type: Some(InstanceType::String.into()),
+                    string: Some(Box::new(StringValidation {
+                        pattern: Some($pattern.to_string()),
+                        ..StringValidation::default()
+                    })),
+                    ..SchemaObject::default()
+                }
+                .into()
+            }
+        }
+    };
+}
+string_serde!(Date, "^[0-9]{4}-[0-9]{2}-[0-9]{2}$");
+string_serde!(Time, "^[0-9]{2}:[0-9]{2}$");
+string_serde!(
+    DateRange,
+    "^[0-9]{4}-[0-9]{2}-[0-9]{2}/[0-9]{4}-[0-9]{2}-[0-9]{2}$"
+);
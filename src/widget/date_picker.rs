@@ -0,0 +1,89 @@
+use crate::internal::Widget;
+use crate::widget::Date;
+
+/// Lets the user choose a date.
+///
+/// The client sends the var as a string like `2024-12-31`,
+/// or `null` when the user has not chosen a date.
+/// Deserialize it with [`Date`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DatePicker {
+    initial: Option<Date>,
+    label: String,
+    max: Option<Date>,
+    min: Option<Date>,
+    rpc: Option<String>,
+    var: String,
+}
+impl DatePicker {
+    /// # Panics
+    /// Panics when `var` is empty.
+    #[must_use]
+    pub fn new(var: impl Into<String>) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        Self {
+            initial: None,
+            label: String::new(),
+            max: None,
+            min: None,
+            rpc: None,
+            var,
+        }
+    }
+
+    #[must_use]
+    pub fn with_initial(mut self, date: Date) -> Self {
+        self.initial = Some(date);
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the latest date that the user can choose.
+    #[must_use]
+    pub fn with_max(mut self, date: Date) -> Self {
+        self.max = Some(date);
+        self
+    }
+
+    /// Sets the earliest date that the user can choose.
+    #[must_use]
+    pub fn with_min(mut self, date: Date) -> Self {
+        self.min = Some(date);
+        self
+    }
+
+    /// Makes the client call `rpc` when the user chooses a date.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::DatePickerVariant {
+            initial_date: self.initial,
+            label: self.label,
+            max_date: self.max,
+            min_date: self.min,
+            rpc: self.rpc,
+            var: self.var,
+        }
+    }
+}
+impl From<DatePicker> for Widget {
+    fn from(src: DatePicker) -> Self {
+        src.to_widget()
+    }
+}
+impl From<DatePicker> for Option<Widget> {
+    fn from(src: DatePicker) -> Self {
+        Some(src.to_widget())
+    }
+}
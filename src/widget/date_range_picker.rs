@@ -0,0 +1,89 @@
+use crate::internal::Widget;
+use crate::widget::{Date, DateRange};
+
+/// Lets the user choose a start date and an end date.
+///
+/// The client sends the var as a string like `2024-12-24/2024-12-31`,
+/// or `null` when the user has not chosen a range.
+/// Deserialize it with [`DateRange`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DateRangePicker {
+    initial: Option<DateRange>,
+    label: String,
+    max: Option<Date>,
+    min: Option<Date>,
+    rpc: Option<String>,
+    var: String,
+}
+impl DateRangePicker {
+    /// # Panics
+    /// Panics when `var` is empty.
+    #[must_use]
+    pub fn new(var: impl Into<String>) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        Self {
+            initial: None,
+            label: String::new(),
+            max: None,
+            min: None,
+            rpc: None,
+            var,
+        }
+    }
+
+    #[must_use]
+    pub fn with_initial(mut self, range: DateRange) -> Self {
+        self.initial = Some(range);
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the latest date that the range can include.
+    #[must_use]
+    pub fn with_max(mut self, date: Date) -> Self {
+        self.max = Some(date);
+        self
+    }
+
+    /// Sets the earliest date that the range can include.
+    #[must_use]
+    pub fn with_min(mut self, date: Date) -> Self {
+        self.min = Some(date);
+        self
+    }
+
+    /// Makes the client call `rpc` when the user chooses a range.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::DateRangePickerVariant {
+            initial_range: self.initial,
+            label: self.label,
+            max_date: self.max,
+            min_date: self.min,
+            rpc: self.rpc,
+            var: self.var,
+        }
+    }
+}
+impl From<DateRangePicker> for Widget {
+    fn from(src: DateRangePicker) -> Self {
+        src.to_widget()
+    }
+}
+impl From<DateRangePicker> for Option<Widget> {
+    fn from(src: DateRangePicker) -> Self {
+        Some(src.to_widget())
+    }
+}
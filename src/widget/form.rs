@@ -6,6 +6,9 @@ pub struct Form {
 }
 impl Form {
     /// Makes a `form` widget.
+    ///
+    /// Forms may contain form sections, textfields, checkboxes, pickers, number widgets,
+    /// form buttons, text, and error text.
     #[must_use]
     #[allow(clippy::new_without_default)]
     pub fn new(widgets: impl Into<WidgetList>) -> Self {
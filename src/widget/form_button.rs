@@ -1,9 +1,13 @@
 use crate::internal::{Action, Widget};
+use crate::style::Style;
+use crate::widget::Styled;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FormButton {
     actions: Vec<Action>,
+    icon: Option<String>,
     is_destructive: bool,
+    style: Option<Style>,
     text: String,
 }
 impl FormButton {
@@ -11,7 +15,9 @@ impl FormButton {
     pub fn new(text: impl Into<String>) -> Self {
         Self {
             actions: Vec::new(),
+            icon: None,
             is_destructive: false,
+            style: None,
             text: text.into(),
         }
     }
@@ -30,19 +36,37 @@ impl FormButton {
         self
     }
 
+    /// Shows the icon with `name` before the text.  See [`crate::style::ICON_NAMES`].
+    #[must_use]
+    pub fn with_icon(mut self, name: impl Into<String>) -> Self {
+        self.icon = Some(name.into());
+        self
+    }
+
     #[must_use]
     pub fn with_is_destructive(mut self) -> Self {
         self.is_destructive = true;
         self
     }
 
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
     #[must_use]
     pub fn to_widget(self) -> Widget {
-        Widget::FormButtonVariant {
-            actions: self.actions,
-            is_destructive: self.is_destructive,
-            text: self.text,
-        }
+        Styled::wrap(
+            self.style,
+            Widget::FormButtonVariant {
+                actions: self.actions,
+                icon: self.icon,
+                is_destructive: self.is_destructive,
+                text: self.text,
+            },
+        )
     }
 }
 impl From<FormButton> for Widget {
@@ -0,0 +1,107 @@
+use crate::internal::Widget;
+use crate::style::Style;
+use crate::widget::Styled;
+
+/// Constrains the size of a widget.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Frame {
+    max_height: Option<u16>,
+    max_width: Option<u16>,
+    min_height: Option<u16>,
+    min_width: Option<u16>,
+    style: Option<Style>,
+    weight: u16,
+    widget: Widget,
+}
+impl Frame {
+    /// Makes a `frame` widget with no constraints.
+    #[must_use]
+    pub fn new(widget: impl Into<Widget>) -> Self {
+        Self {
+            max_height: None,
+            max_width: None,
+            min_height: None,
+            min_width: None,
+            style: None,
+            weight: 0,
+            widget: widget.into(),
+        }
+    }
+
+    /// Sets the min and max height.
+    #[must_use]
+    pub fn with_height(self, height: u16) -> Self {
+        self.with_min_height(height).with_max_height(height)
+    }
+
+    #[must_use]
+    pub fn with_max_height(mut self, max_height: u16) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_width(mut self, max_width: u16) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    #[must_use]
+    pub fn with_min_height(mut self, min_height: u16) -> Self {
+        self.min_height = Some(min_height);
+        self
+    }
+
+    #[must_use]
+    pub fn with_min_width(mut self, min_width: u16) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    /// In a [`crate::widget::Row`] or [`crate::widget::Column`],
+    /// frames with positive weights share the free space in proportion to their weights.
+    /// The default weight is `0`, which does not grow.
+    #[must_use]
+    pub fn with_weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the min and max width.
+    #[must_use]
+    pub fn with_width(self, width: u16) -> Self {
+        self.with_min_width(width).with_max_width(width)
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(
+            self.style,
+            Widget::FrameVariant {
+                max_height: self.max_height,
+                max_width: self.max_width,
+                min_height: self.min_height,
+                min_width: self.min_width,
+                weight: self.weight,
+                widget: Box::new(self.widget),
+            },
+        )
+    }
+}
+impl From<Frame> for Widget {
+    fn from(src: Frame) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Frame> for Option<Widget> {
+    fn from(src: Frame) -> Self {
+        Some(src.to_widget())
+    }
+}
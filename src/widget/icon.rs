@@ -0,0 +1,57 @@
+use crate::internal::Widget;
+use crate::style::Style;
+use crate::widget::Styled;
+
+/// Shows an icon from the set that clients ship.
+/// See [`crate::style::ICON_NAMES`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Icon {
+    name: String,
+    size: u16,
+    style: Option<Style>,
+}
+impl Icon {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            size: 0,
+            style: None,
+        }
+    }
+
+    /// Sets the icon's width and height in points.  Zero uses the client's default size.
+    #[must_use]
+    pub fn with_size(mut self, size: u16) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(
+            self.style,
+            Widget::IconVariant {
+                name: self.name,
+                size: self.size,
+            },
+        )
+    }
+}
+impl From<Icon> for Widget {
+    fn from(src: Icon) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Icon> for Option<Widget> {
+    fn from(src: Icon) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::image_store::{ImageId, ImageStore};
 use crate::internal::{ImageDisposition, Widget};
 use crate::widget::Real32;
 
@@ -23,6 +24,19 @@ impl Image {
         }
     }
 
+    /// Creates an image that shows `id` from `store`.
+    ///
+    /// # Panics
+    /// Panics when `aspect_ratio` is infinite, zero, negative, or NaN.
+    #[must_use]
+    pub fn from_store(
+        aspect_ratio_width_over_height: f32,
+        store: &ImageStore,
+        id: &ImageId,
+    ) -> Self {
+        Self::new(aspect_ratio_width_over_height, store.url(id))
+    }
+
     #[must_use]
     pub fn with_disposition(mut self, disposition: ImageDisposition) -> Self {
         self.disposition = disposition;
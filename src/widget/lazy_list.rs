@@ -0,0 +1,69 @@
+use crate::internal::{Chunk, Widget};
+use crate::style::Style;
+use crate::widget::Styled;
+
+/// A column that loads more rows as the user scrolls.
+///
+/// The page shows the rows in `first_chunk`.
+/// When the user scrolls to the end, the client POSTs the chunk's next cursor to `url`
+/// and appends the rows in the response.
+/// Handle those requests with [`crate::lazy_list::LazyListLoader`].
+///
+/// When the server rebuilds the page, the list starts over with the new first chunk.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LazyList {
+    next_cursor: Option<String>,
+    spacing: u16,
+    style: Option<Style>,
+    url: String,
+    widgets: Vec<Widget>,
+}
+impl LazyList {
+    /// Makes a `lazy-list` widget with spacing `0`.
+    #[must_use]
+    pub fn new(url: impl Into<String>, first_chunk: Chunk) -> Self {
+        Self {
+            next_cursor: first_chunk.next_cursor,
+            spacing: 0,
+            style: None,
+            url: url.into(),
+            widgets: first_chunk.widgets,
+        }
+    }
+
+    #[must_use]
+    pub fn with_spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(
+            self.style,
+            Widget::LazyListVariant {
+                next_cursor: self.next_cursor,
+                spacing: self.spacing,
+                url: self.url,
+                widgets: self.widgets,
+            },
+        )
+    }
+}
+impl From<LazyList> for Widget {
+    fn from(src: LazyList) -> Self {
+        src.to_widget()
+    }
+}
+impl From<LazyList> for Option<Widget> {
+    fn from(src: LazyList) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -0,0 +1,232 @@
+use crate::internal::Action;
+use crate::widget::Span;
+
+/// Returns an action for a link URL, or `None` for unsafe or unsupported URLs.
+fn link_action(url: &str) -> Option<Action> {
+    if url.is_empty() || url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return None;
+    }
+    let lower = url.to_ascii_lowercase();
+    if ["http://", "https://", "mailto:", "tel:"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+    {
+        Some(Action::LaunchUrl(url.to_string()))
+    } else if url.starts_with('/') && !url.starts_with("//") {
+        Some(Action::Push(url.to_string()))
+    } else {
+        None
+    }
+}
+
+fn starts_with(chars: &[char], i: usize, pattern: &str) -> bool {
+    pattern
+        .chars()
+        .enumerate()
+        .all(|(n, c)| chars.get(i + n) == Some(&c))
+}
+
+/// Returns the index of the closing `delim` at or after `from`.
+fn find_closer(chars: &[char], from: usize, delim: &str) -> Option<usize> {
+    let mut j = from;
+    while j < chars.len() {
+        if chars[j] == '\\' {
+            j += 2;
+        } else if delim == "*" && starts_with(chars, j, "**") {
+            // Skip bold delimiters inside italic text.
+            j += 2;
+        } else if delim.len() == 2
+            && starts_with(chars, j, delim)
+            && chars.get(j + 2) == Some(&chars[j])
+        {
+            // Close on the last two characters of a run like `***`.
+            j += 1;
+        } else if starts_with(chars, j, delim) {
+            return Some(j);
+        } else {
+            j += 1;
+        }
+    }
+    None
+}
+
+/// Returns the index of the `]` that closes the `[` at `open`.
+fn find_bracket(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut j = open;
+    while j < chars.len() {
+        match chars[j] {
+            '\\' => j += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    None
+}
+
+/// Returns the index of the `)` that closes the `(` at `open`.
+fn find_paren(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (j, c) in chars.iter().enumerate().skip(open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns one span containing `spans`.
+fn wrap(mut spans: Vec<Span>) -> Span {
+    if spans.len() == 1 {
+        spans.remove(0)
+    } else {
+        Span::group(spans)
+    }
+}
+
+fn is_word_char(c: Option<&char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric())
+}
+
+/// Parses formatted text starting at `i`.
+/// Returns the span and the index after it.
+fn parse_formatted(chars: &[char], i: usize) -> Option<(Span, usize)> {
+    let inner = |start: usize, end: usize| -> Option<Vec<Span>> {
+        if end <= start || chars[start].is_whitespace() || chars[end - 1].is_whitespace() {
+            None
+        } else {
+            Some(parse_inline(&chars[start..end]))
+        }
+    };
+    match chars[i] {
+        '`' => {
+            let end = (i + 1..chars.len()).find(|j| chars[*j] == '`')?;
+            if end == i + 1 {
+                return None;
+            }
+            let code: String = chars[i + 1..end].iter().collect();
+            Some((Span::new(code).with_monospace(), end + 1))
+        }
+        '*' | '~' if starts_with(chars, i, "**") || starts_with(chars, i, "~~") => {
+            let delim: String = chars[i..i + 2].iter().collect();
+            let end = find_closer(chars, i + 2, &delim)?;
+            let span = wrap(inner(i + 2, end)?);
+            let span = if delim == "**" {
+                span.with_bold()
+            } else {
+                span.with_strikethrough()
+            };
+            Some((span, end + 2))
+        }
+        '*' | '_' => {
+            if chars[i] == '_' && is_word_char(i.checked_sub(1).and_then(|j| chars.get(j))) {
+                return None;
+            }
+            let end = find_closer(chars, i + 1, &chars[i].to_string())?;
+            if chars[i] == '_' && is_word_char(chars.get(end + 1)) {
+                return None;
+            }
+            Some((wrap(inner(i + 1, end)?).with_italic(), end + 1))
+        }
+        '[' => {
+            let close = find_bracket(chars, i)?;
+            if chars.get(close + 1) != Some(&'(') {
+                return None;
+            }
+            let url_end = find_paren(chars, close + 1)?;
+            let url: String = chars[close + 2..url_end].iter().collect();
+            let label = wrap(parse_inline(&chars[i + 1..close]));
+            let span = match link_action(url.trim()) {
+                Some(action) => label.with_action(action),
+                None => label,
+            };
+            Some((span, url_end + 1))
+        }
+        _ => None,
+    }
+}
+
+fn parse_inline(chars: &[char]) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '\\' && chars.get(i + 1).is_some_and(char::is_ascii_punctuation) {
+            text.push(chars[i + 1]);
+            i += 2;
+        } else if let Some((span, end)) = parse_formatted(chars, i) {
+            let plain_text = span.plain_text();
+            if span == Span::new(plain_text.clone()) {
+                // Unsupported links show as plain text.
+                text.push_str(&plain_text);
+            } else {
+                if !text.is_empty() {
+                    spans.push(Span::new(std::mem::take(&mut text)));
+                }
+                spans.push(span);
+            }
+            i = end;
+        } else {
+            text.push(chars[i]);
+            i += 1;
+        }
+    }
+    if !text.is_empty() {
+        spans.push(Span::new(text));
+    }
+    spans
+}
+
+/// Parses a safe subset of Markdown into spans for [`crate::widget::RichText`]:
+/// - `**bold**`, `*italic*`, `_italic_`, `~~strikethrough~~`, and `` `monospace` ``
+/// - `[text](url)` links.
+///   Tapping an `http`, `https`, `mailto`, or `tel` link launches the URL.
+///   Tapping a link to a path like `/terms` pushes the page with that key.
+///   Other links show only their text.
+/// - Lines that start with `- ` or `* ` show as bulleted list items.
+/// - A backslash shows the next punctuation character as-is, like `\*`.
+///
+/// Lines in a paragraph join with spaces.  Blank lines separate paragraphs.
+/// Everything else, including HTML, shows as plain text.
+#[must_use]
+pub fn parse_markdown(markdown: &str) -> Vec<Span> {
+    let mut text = String::new();
+    let mut blank = false;
+    for line in markdown.lines().map(str::trim) {
+        if line.is_empty() {
+            blank = true;
+            continue;
+        }
+        let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        if !text.is_empty() {
+            text.push_str(match (blank, item) {
+                (true, _) => "\n\n",
+                (false, Some(_)) => "\n",
+                (false, None) => " ",
+            });
+        }
+        if let Some(item) = item {
+            text.push_str("• ");
+            text.push_str(item.trim_start());
+        } else {
+            text.push_str(line);
+        }
+        blank = false;
+    }
+    let chars: Vec<char> = text.chars().collect();
+    parse_inline(&chars)
+}
@@ -2,7 +2,11 @@ mod alert_modal;
 mod back_button;
 mod button;
 mod checkbox;
+mod choice;
 mod column;
+mod date;
+mod date_picker;
+mod date_range_picker;
 mod drawer_modal;
 mod empty;
 mod error_details;
@@ -10,24 +14,51 @@ mod form;
 mod form_button;
 mod form_error;
 mod form_section;
+mod frame;
 mod grouped_row_table;
 mod h_alignment;
+mod icon;
 mod image;
+mod lazy_list;
+mod markdown;
 mod modal_button;
+mod multi_select;
 mod nav_button;
 mod nav_page;
+mod number_field;
+mod number_range;
+mod padding;
+mod picker;
 mod plain_page;
+mod progress_bar;
+mod radio_group;
 mod real32;
+mod rich_text;
+mod row;
 mod scroll;
+mod sheet_detent;
+mod sheet_modal;
+mod slider;
+mod spacer;
+mod span;
+mod stack;
+mod stepper;
+mod styled;
+mod tab_page;
 mod text;
 mod textfield;
+mod time_picker;
 mod v_alignment;
 
 pub use alert_modal::*;
 pub use back_button::*;
 pub use button::*;
 pub use checkbox::*;
+pub use choice::*;
 pub use column::*;
+pub use date::*;
+pub use date_picker::*;
+pub use date_range_picker::*;
 pub use drawer_modal::*;
 pub use empty::*;
 pub use error_details::*;
@@ -35,15 +66,38 @@ pub use form::*;
 pub use form_button::*;
 pub use form_error::*;
 pub use form_section::*;
+pub use frame::*;
 pub use grouped_row_table::*;
 pub use h_alignment::*;
+pub use icon::*;
 pub use image::*;
+pub use lazy_list::*;
+pub use markdown::*;
 pub use modal_button::*;
+pub use multi_select::*;
 pub use nav_button::*;
 pub use nav_page::*;
+pub use number_field::*;
+pub use number_range::*;
+pub use padding::*;
+pub use picker::*;
 pub use plain_page::*;
+pub use progress_bar::*;
+pub use radio_group::*;
 pub use real32::*;
+pub use rich_text::*;
+pub use row::*;
 pub use scroll::*;
+pub use sheet_detent::*;
+pub use sheet_modal::*;
+pub use slider::*;
+pub use spacer::*;
+pub use span::*;
+pub use stack::*;
+pub use stepper::*;
+pub use styled::*;
+pub use tab_page::*;
 pub use text::*;
 pub use textfield::*;
+pub use time_picker::*;
 pub use v_alignment::*;
@@ -0,0 +1,84 @@
+use crate::internal::Widget;
+use crate::widget::Choice;
+
+/// Shows a list of options and lets the user choose any number of them.
+///
+/// The client sends the var as a list of the chosen options' ids,
+/// in the order of the options.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MultiSelect {
+    initial: Vec<String>,
+    label: String,
+    options: Vec<Choice>,
+    rpc: Option<String>,
+    var: String,
+}
+impl MultiSelect {
+    /// # Panics
+    /// Panics when `var` is empty.
+    #[must_use]
+    pub fn new(var: impl Into<String>) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        Self {
+            initial: Vec::new(),
+            label: String::new(),
+            options: Vec::new(),
+            rpc: None,
+            var,
+        }
+    }
+
+    /// Chooses the options with `ids`.
+    #[must_use]
+    pub fn with_initial(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.initial = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_option(mut self, id: impl Into<String>, text: impl Into<String>) -> Self {
+        self.options.push(Choice::new(id, text));
+        self
+    }
+
+    #[must_use]
+    pub fn with_options(mut self, options: impl IntoIterator<Item = impl Into<Choice>>) -> Self {
+        self.options.extend(options.into_iter().map(Into::into));
+        self
+    }
+
+    /// Makes the client call `rpc` when the user chooses or un-chooses an option.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::MultiSelectVariant {
+            initial_ids: self.initial,
+            label: self.label,
+            options: self.options,
+            rpc: self.rpc,
+            var: self.var,
+        }
+    }
+}
+impl From<MultiSelect> for Widget {
+    fn from(src: MultiSelect) -> Self {
+        src.to_widget()
+    }
+}
+impl From<MultiSelect> for Option<Widget> {
+    fn from(src: MultiSelect) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -1,10 +1,18 @@
+use crate::image_store::{ImageId, ImageStore};
 use crate::internal::{Action, Widget};
+use crate::style::Style;
+use crate::widget::Styled;
+
+/// The size of the thumbnail for [`NavButton::with_stored_photo`], in pixels.
+pub const NAV_BUTTON_PHOTO_SIZE: u32 = 200;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct NavButton {
     actions: Vec<Action>,
     badge_text: Option<String>,
+    icon: Option<String>,
     photo_url: Option<String>,
+    style: Option<Style>,
     sub_text: Option<String>,
     text: String,
 }
@@ -14,7 +22,9 @@ impl NavButton {
         Self {
             actions: vec![],
             badge_text: None,
+            icon: None,
             photo_url: None,
+            style: None,
             sub_text: None,
             text: text.into(),
         }
@@ -40,27 +50,51 @@ impl NavButton {
         self
     }
 
+    /// Shows the icon with `name` before the text.  See [`crate::style::ICON_NAMES`].
+    #[must_use]
+    pub fn with_icon(mut self, name: impl Into<String>) -> Self {
+        self.icon = Some(name.into());
+        self
+    }
+
     #[must_use]
     pub fn with_photo_url(mut self, url: impl Into<String>) -> Self {
         self.photo_url = Some(url.into());
         self
     }
 
+    /// Shows a thumbnail of `id` from `store`.
+    #[must_use]
+    pub fn with_stored_photo(self, store: &ImageStore, id: &ImageId) -> Self {
+        self.with_photo_url(store.thumbnail_url(id, NAV_BUTTON_PHOTO_SIZE, NAV_BUTTON_PHOTO_SIZE))
+    }
+
     #[must_use]
     pub fn with_sub_text(mut self, sub_text: impl Into<String>) -> Self {
         self.sub_text = Some(sub_text.into());
         self
     }
 
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
     #[must_use]
     pub fn to_widget(self) -> Widget {
-        Widget::NavButtonVariant {
-            actions: self.actions,
-            badge_text: self.badge_text,
-            photo_url: self.photo_url,
-            sub_text: self.sub_text,
-            text: self.text,
-        }
+        Styled::wrap(
+            self.style,
+            Widget::NavButtonVariant {
+                actions: self.actions,
+                badge_text: self.badge_text,
+                icon: self.icon,
+                photo_url: self.photo_url,
+                sub_text: self.sub_text,
+                text: self.text,
+            },
+        )
     }
 }
 impl From<NavButton> for Widget {
@@ -1,12 +1,15 @@
 use crate::internal::{Page, Widget};
+use crate::style::Theme;
 use crate::widget::BackButton;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct NavPage {
     end: Option<Widget>,
     poll_seconds: u32,
+    pull_to_refresh: Option<String>,
     start: Option<Widget>,
     stream: bool,
+    theme: Option<Theme>,
     title: String,
     widget: Widget,
 }
@@ -16,8 +19,10 @@ impl NavPage {
         Self {
             end: None,
             poll_seconds: 0,
+            pull_to_refresh: None,
             start: None,
             stream: false,
+            theme: None,
             title: title.into(),
             widget: widget.into(),
         }
@@ -47,19 +52,37 @@ impl NavPage {
         self
     }
 
+    /// When the user pulls down on the page, the client calls the RPC at `rpc_url`.
+    /// Its handler can rebuild pages with [`crate::session::ApplinSession::refresh`].
+    #[must_use]
+    pub fn with_pull_to_refresh(mut self, rpc_url: impl Into<String>) -> Self {
+        self.pull_to_refresh = Some(rpc_url.into());
+        self
+    }
+
     #[must_use]
     pub fn with_stream(mut self) -> Self {
         self.stream = true;
         self
     }
 
+    /// Sets theme tokens for this page.
+    /// They replace the session theme's tokens with the same names.
+    #[must_use]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
     #[must_use]
     pub fn to_page(self) -> Page {
         Page::Nav {
             end: self.end,
             poll_seconds: if self.stream { 0 } else { self.poll_seconds },
+            pull_to_refresh: self.pull_to_refresh,
             start: self.start,
             stream: self.stream,
+            theme: self.theme,
             title: self.title,
             widget: self.widget,
         }
@@ -0,0 +1,86 @@
+use crate::internal::Widget;
+use crate::widget::{NumberRange, Real32};
+
+/// A text field for typing a number.
+/// The client shows a number keyboard and accepts only numbers in the range.
+///
+/// The client sends the var as a number, or `null` when the field is empty.
+/// Check it with the same [`NumberRange`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NumberField {
+    initial: Option<Real32>,
+    label: String,
+    range: NumberRange,
+    rpc: Option<String>,
+    units: String,
+    var: String,
+}
+impl NumberField {
+    /// # Panics
+    /// Panics when `var` is empty.
+    #[must_use]
+    pub fn new(var: impl Into<String>, range: NumberRange) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        Self {
+            initial: None,
+            label: String::new(),
+            range,
+            rpc: None,
+            units: String::new(),
+            var,
+        }
+    }
+
+    /// # Panics
+    /// Panics when `value` is NaN or infinity.
+    #[must_use]
+    pub fn with_initial(mut self, value: f32) -> Self {
+        self.initial = Some(Real32::new(value));
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Makes the client call `rpc` when the user finishes editing the number.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    /// Sets the units that the client shows after the number, like `kg` or `%`.
+    #[must_use]
+    pub fn with_units(mut self, units: impl Into<String>) -> Self {
+        self.units = units.into();
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::NumberFieldVariant {
+            initial_number: self.initial,
+            integer: self.range.is_integer(),
+            label: self.label,
+            max: self.range.max().map(Real32::new),
+            min: self.range.min().map(Real32::new),
+            rpc: self.rpc,
+            units: self.units,
+            var: self.var,
+        }
+    }
+}
+impl From<NumberField> for Widget {
+    fn from(src: NumberField) -> Self {
+        src.to_widget()
+    }
+}
+impl From<NumberField> for Option<Widget> {
+    fn from(src: NumberField) -> Self {
+        Some(src.to_widget())
+    }
+}
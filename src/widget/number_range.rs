@@ -0,0 +1,137 @@
+use crate::widget::Real32;
+
+/// The numbers that a [`crate::widget::Slider`], [`crate::widget::Stepper`],
+/// or [`crate::widget::NumberField`] accepts.
+///
+/// Clients send any number, so use the same range in your RPC handler
+/// to check the number that the client sends:
+/// ```
+/// use applin::error::user_error;
+/// use applin::widget::{NumberRange, Real32};
+///
+/// fn guests_range() -> NumberRange {
+///     NumberRange::new().with_min(1.0).with_max(12.0).with_integer()
+/// }
+///
+/// let guests = guests_range().check(4.0).map_err(user_error).ok().unwrap();
+/// assert_eq!(Real32::new(4.0), guests);
+/// assert_eq!(
+///     "guests must be at most 12",
+///     guests_range().check_var("guests", 13.0).unwrap_err(),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct NumberRange {
+    integer: bool,
+    max: Option<Real32>,
+    min: Option<Real32>,
+    step: Option<Real32>,
+}
+impl NumberRange {
+    /// Accepts all numbers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts only whole numbers.
+    #[must_use]
+    pub fn with_integer(mut self) -> Self {
+        self.integer = true;
+        self
+    }
+
+    /// # Panics
+    /// Panics when `max` is NaN or infinity.
+    #[must_use]
+    pub fn with_max(mut self, max: f32) -> Self {
+        self.max = Some(Real32::new(max));
+        self
+    }
+
+    /// # Panics
+    /// Panics when `min` is NaN or infinity.
+    #[must_use]
+    pub fn with_min(mut self, min: f32) -> Self {
+        self.min = Some(Real32::new(min));
+        self
+    }
+
+    /// Sets the amount that the slider or stepper changes the number.
+    /// Clients use 1 by default.
+    ///
+    /// # Panics
+    /// Panics when `step` is not positive, NaN, or infinity.
+    #[must_use]
+    pub fn with_step(mut self, step: f32) -> Self {
+        assert!(step > 0.0, "step must be positive");
+        self.step = Some(Real32::new(step));
+        self
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.integer
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<f32> {
+        self.max.clone().map(Real32::get)
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<f32> {
+        self.min.clone().map(Real32::get)
+    }
+
+    #[must_use]
+    pub fn step(&self) -> Option<f32> {
+        self.step.clone().map(Real32::get)
+    }
+
+    /// Checks that the range accepts `value`.
+    ///
+    /// # Errors
+    /// Returns an error when `value` is NaN, infinity, out of range,
+    /// not a whole number in an integer range,
+    /// or not `min` (or zero) plus a multiple of `step`.
+    pub fn check(&self, value: f32) -> Result<Real32, String> {
+        self.check_var("number", value)
+    }
+
+    /// Like [`NumberRange::check`], but the error message names `var`.
+    ///
+    /// # Errors
+    /// Returns an error when `value` is NaN, infinity, out of range,
+    /// not a whole number in an integer range,
+    /// or not `min` (or zero) plus a multiple of `step`.
+    pub fn check_var(&self, var: &str, value: f32) -> Result<Real32, String> {
+        let real = Real32::try_from(value).map_err(|_| format!("{var} must be a number"))?;
+        if self.integer && value.fract() != 0.0 {
+            return Err(format!("{var} must be a whole number"));
+        }
+        if let Some(min) = self.min() {
+            if value < min {
+                return Err(format!("{var} must be at least {min}"));
+            }
+        }
+        if let Some(max) = self.max() {
+            if max < value {
+                return Err(format!("{var} must be at most {max}"));
+            }
+        }
+        if let Some(step) = self.step() {
+            let base = self.min().unwrap_or(0.0);
+            let steps = (value - base) / step;
+            // Allow for rounding in the client's arithmetic.
+            if 0.001 < (steps - steps.round()).abs() {
+                return Err(if base == 0.0 {
+                    format!("{var} must be a multiple of {step}")
+                } else {
+                    format!("{var} must be {base} plus a multiple of {step}")
+                });
+            }
+        }
+        Ok(real)
+    }
+}
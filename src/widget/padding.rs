@@ -0,0 +1,95 @@
+use crate::internal::Widget;
+use crate::style::Style;
+use crate::widget::Styled;
+
+/// Adds space around a widget.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Padding {
+    bottom: u16,
+    end: u16,
+    start: u16,
+    style: Option<Style>,
+    top: u16,
+    widget: Widget,
+}
+impl Padding {
+    /// Makes a `padding` widget with `size` on all sides.
+    #[must_use]
+    pub fn new(size: u16, widget: impl Into<Widget>) -> Self {
+        Self {
+            bottom: size,
+            end: size,
+            start: size,
+            style: None,
+            top: size,
+            widget: widget.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_bottom(mut self, bottom: u16) -> Self {
+        self.bottom = bottom;
+        self
+    }
+
+    #[must_use]
+    pub fn with_end(mut self, end: u16) -> Self {
+        self.end = end;
+        self
+    }
+
+    /// Sets the `start` and `end` padding.
+    #[must_use]
+    pub fn with_horizontal(self, size: u16) -> Self {
+        self.with_start(size).with_end(size)
+    }
+
+    #[must_use]
+    pub fn with_start(mut self, start: u16) -> Self {
+        self.start = start;
+        self
+    }
+
+    #[must_use]
+    pub fn with_top(mut self, top: u16) -> Self {
+        self.top = top;
+        self
+    }
+
+    /// Sets the `top` and `bottom` padding.
+    #[must_use]
+    pub fn with_vertical(self, size: u16) -> Self {
+        self.with_top(size).with_bottom(size)
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(
+            self.style,
+            Widget::PaddingVariant {
+                bottom: self.bottom,
+                end: self.end,
+                start: self.start,
+                top: self.top,
+                widget: Box::new(self.widget),
+            },
+        )
+    }
+}
+impl From<Padding> for Widget {
+    fn from(src: Padding) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Padding> for Option<Widget> {
+    fn from(src: Padding) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -1,9 +1,12 @@
 use crate::internal::{Page, Widget};
+use crate::style::Theme;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PlainPage {
     poll_seconds: u32,
+    pull_to_refresh: Option<String>,
     stream: bool,
+    theme: Option<Theme>,
     title: String,
     widget: Widget,
 }
@@ -12,7 +15,9 @@ impl PlainPage {
     pub fn new(title: impl Into<String>, widget: impl Into<Widget>) -> Self {
         Self {
             poll_seconds: 0,
+            pull_to_refresh: None,
             stream: false,
+            theme: None,
             title: title.into(),
             widget: widget.into(),
         }
@@ -24,17 +29,35 @@ impl PlainPage {
         self
     }
 
+    /// When the user pulls down on the page, the client calls the RPC at `rpc_url`.
+    /// Its handler can rebuild pages with [`crate::session::ApplinSession::refresh`].
+    #[must_use]
+    pub fn with_pull_to_refresh(mut self, rpc_url: impl Into<String>) -> Self {
+        self.pull_to_refresh = Some(rpc_url.into());
+        self
+    }
+
     #[must_use]
     pub fn with_stream(mut self) -> Self {
         self.stream = true;
         self
     }
 
+    /// Sets theme tokens for this page.
+    /// They replace the session theme's tokens with the same names.
+    #[must_use]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
     #[must_use]
     pub fn to_page(self) -> Page {
         Page::Plain {
             poll_seconds: if self.stream { 0 } else { self.poll_seconds },
+            pull_to_refresh: self.pull_to_refresh,
             stream: self.stream,
+            theme: self.theme,
             title: self.title,
             widget: self.widget,
         }
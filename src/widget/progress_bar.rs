@@ -0,0 +1,52 @@
+use crate::internal::Widget;
+
+/// Shows how much of a task is done, like a payment or an upload.
+/// See [`crate::session::ApplinSession::start_job`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ProgressBar {
+    percent: Option<u8>,
+    text: String,
+}
+impl ProgressBar {
+    /// Makes an indeterminate progress bar.
+    #[must_use]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            percent: None,
+            text: String::new(),
+        }
+    }
+
+    /// Sets the percent done, from 0 to 100.
+    #[must_use]
+    pub fn with_percent(mut self, percent: u8) -> Self {
+        self.percent = Some(percent);
+        self
+    }
+
+    /// Sets the text that the client shows with the bar, like "Charging card".
+    #[must_use]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::ProgressBarVariant {
+            percent: self.percent,
+            text: self.text,
+        }
+    }
+}
+impl From<ProgressBar> for Widget {
+    fn from(src: ProgressBar) -> Self {
+        src.to_widget()
+    }
+}
+impl From<ProgressBar> for Option<Widget> {
+    fn from(src: ProgressBar) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::internal::Widget;
+use crate::widget::Choice;
+
+/// Shows a list of options and lets the user choose one of them.
+///
+/// The client sends the var as the chosen option's id,
+/// or `null` when the user has not chosen one.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct RadioGroup {
+    initial: Option<String>,
+    label: String,
+    options: Vec<Choice>,
+    rpc: Option<String>,
+    var: String,
+}
+impl RadioGroup {
+    /// # Panics
+    /// Panics when `var` is empty.
+    #[must_use]
+    pub fn new(var: impl Into<String>) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        Self {
+            initial: None,
+            label: String::new(),
+            options: Vec::new(),
+            rpc: None,
+            var,
+        }
+    }
+
+    /// Chooses the option with `id`.
+    #[must_use]
+    pub fn with_initial(mut self, id: impl Into<String>) -> Self {
+        self.initial = Some(id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_option(mut self, id: impl Into<String>, text: impl Into<String>) -> Self {
+        self.options.push(Choice::new(id, text));
+        self
+    }
+
+    #[must_use]
+    pub fn with_options(mut self, options: impl IntoIterator<Item = impl Into<Choice>>) -> Self {
+        self.options.extend(options.into_iter().map(Into::into));
+        self
+    }
+
+    /// Makes the client call `rpc` when the user chooses an option.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::RadioGroupVariant {
+            initial_id: self.initial,
+            label: self.label,
+            options: self.options,
+            rpc: self.rpc,
+            var: self.var,
+        }
+    }
+}
+impl From<RadioGroup> for Widget {
+    fn from(src: RadioGroup) -> Self {
+        src.to_widget()
+    }
+}
+impl From<RadioGroup> for Option<Widget> {
+    fn from(src: RadioGroup) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -72,3 +72,17 @@ impl<'de> Deserialize<'de> for Real32 {
         Real32::try_from(raw).map_err(serde::de::Error::custom)
     }
 }
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Real32 {
+    fn schema_name() -> String {
+        "Real32".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        f32::json_schema(gen)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::internal::Widget;
+use crate::style::Style;
+use crate::widget::{parse_markdown, Span, Styled};
+
+/// Text with inline formatting and links.
+///
+/// ```
+/// use applin::action::launch_url;
+/// use applin::widget::{RichText, Span};
+/// let terms = RichText::new((
+///     "By continuing, you agree to the ",
+///     Span::new("Terms").with_action(launch_url("https://example.com/terms")),
+///     ".",
+/// ));
+/// let same = RichText::from_markdown(
+///     "By continuing, you agree to the [Terms](https://example.com/terms).",
+/// );
+/// assert_eq!(terms.to_widget(), same.to_widget());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct RichText {
+    spans: Vec<Span>,
+    style: Option<Style>,
+}
+impl RichText {
+    /// Makes a `rich-text` widget.
+    /// Pass a span, a string, or a tuple or `Vec` of them.
+    #[must_use]
+    pub fn new(spans: impl Into<SpanList>) -> Self {
+        Self {
+            spans: spans.into().0,
+            style: None,
+        }
+    }
+
+    /// Makes a `rich-text` widget from a Markdown subset.
+    /// See [`parse_markdown`].
+    #[must_use]
+    pub fn from_markdown(markdown: &str) -> Self {
+        Self::new(parse_markdown(markdown))
+    }
+
+    /// Appends `span`.
+    #[must_use]
+    pub fn with_span(mut self, span: impl Into<Span>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(self.style, Widget::RichTextVariant { spans: self.spans })
+    }
+}
+impl From<RichText> for Widget {
+    fn from(src: RichText) -> Self {
+        src.to_widget()
+    }
+}
+impl From<RichText> for Option<Widget> {
+    fn from(src: RichText) -> Self {
+        Some(src.to_widget())
+    }
+}
+
+/// Converts a span, a `Vec` of spans, or a tuple of up to 12 spans to a vector of spans.
+pub struct SpanList(pub Vec<Span>);
+impl<S: Into<Span>> From<S> for SpanList {
+    fn from(src: S) -> Self {
+        SpanList(vec![src.into()])
+    }
+}
+impl<S: Into<Span>> From<Vec<S>> for SpanList {
+    fn from(src: Vec<S>) -> Self {
+        SpanList(src.into_iter().map(Into::into).collect())
+    }
+}
+
+macro_rules! span_list_from_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Into<Span>),+> From<($($name,)+)> for SpanList {
+            #[allow(non_snake_case)]
+            fn from(($($name,)+): ($($name,)+)) -> Self {
+                SpanList(vec![$($name.into()),+])
+            }
+        }
+    };
+}
+span_list_from_tuple!(A);
+span_list_from_tuple!(A, B);
+span_list_from_tuple!(A, B, C);
+span_list_from_tuple!(A, B, C, D);
+span_list_from_tuple!(A, B, C, D, E);
+span_list_from_tuple!(A, B, C, D, E, F);
+span_list_from_tuple!(A, B, C, D, E, F, G);
+span_list_from_tuple!(A, B, C, D, E, F, G, H);
+span_list_from_tuple!(A, B, C, D, E, F, G, H, I);
+span_list_from_tuple!(A, B, C, D, E, F, G, H, I, J);
+span_list_from_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+span_list_from_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
@@ -0,0 +1,79 @@
+use crate::internal::{Widget, WidgetList};
+use crate::style::Style;
+use crate::widget::{Styled, VAlignment};
+
+/// Shows widgets side by side.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Row {
+    spacing: u16,
+    style: Option<Style>,
+    v_alignment: VAlignment,
+    widgets: Vec<Widget>,
+}
+impl Row {
+    /// Makes a `row` widget with vertical alignment `top` and spacing `0`.
+    #[must_use]
+    pub fn new(widgets: impl Into<WidgetList>) -> Self {
+        Self {
+            spacing: 0,
+            style: None,
+            v_alignment: VAlignment::Top,
+            widgets: widgets.into().0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_alignment(mut self, v_alignment: VAlignment) -> Self {
+        self.v_alignment = v_alignment;
+        self
+    }
+
+    #[must_use]
+    pub fn with_spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Appends `widget`.
+    #[must_use]
+    pub fn with_widget(mut self, widget: impl Into<Widget>) -> Self {
+        self.widgets.push(widget.into());
+        self
+    }
+
+    /// Appends `widgets`.
+    #[must_use]
+    pub fn with_widgets(mut self, widgets: impl Into<WidgetList>) -> Self {
+        self.widgets.extend(widgets.into().0);
+        self
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(
+            self.style,
+            Widget::RowVariant {
+                spacing: self.spacing,
+                v_alignment: self.v_alignment,
+                widgets: self.widgets,
+            },
+        )
+    }
+}
+impl From<Row> for Widget {
+    fn from(src: Row) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Row> for Option<Widget> {
+    fn from(src: Row) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A height where a sheet rests.  The user drags the sheet between its detents.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Serialize, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SheetDetent {
+    /// About half of the screen.
+    #[serde(rename = "medium")]
+    Medium,
+    /// Almost all of the screen.
+    #[serde(rename = "large")]
+    Large,
+    /// A height in points.
+    #[serde(rename = "height")]
+    Height(u16),
+}
+impl SheetDetent {
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+impl From<SheetDetent> for Value {
+    fn from(src: SheetDetent) -> Self {
+        src.to_value()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::internal::{Page, Widget};
+use crate::widget::SheetDetent;
+
+/// A page that slides up from the bottom and covers part of the page under it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SheetModal {
+    detents: Vec<SheetDetent>,
+    title: String,
+    widget: Widget,
+}
+impl SheetModal {
+    /// Makes a sheet with the client's default detent, usually [`SheetDetent::Large`].
+    #[must_use]
+    pub fn new(title: impl Into<String>, widget: impl Into<Widget>) -> Self {
+        Self {
+            detents: Vec::new(),
+            title: title.into(),
+            widget: widget.into(),
+        }
+    }
+
+    /// Appends `detent`.  The client first shows the sheet at the first detent.
+    #[must_use]
+    pub fn with_detent(mut self, detent: SheetDetent) -> Self {
+        self.detents.push(detent);
+        self
+    }
+
+    #[must_use]
+    pub fn to_page(self) -> Page {
+        Page::Sheet {
+            detents: self.detents,
+            title: self.title,
+            widget: self.widget,
+        }
+    }
+}
+impl From<SheetModal> for Page {
+    fn from(src: SheetModal) -> Self {
+        src.to_page()
+    }
+}
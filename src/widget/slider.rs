@@ -0,0 +1,90 @@
+use crate::internal::Widget;
+use crate::widget::{NumberRange, Real32};
+
+/// Lets the user choose a number by dragging a handle along a track.
+///
+/// The client sends the var as a number.
+/// Check it with the same [`NumberRange`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Slider {
+    initial: Option<Real32>,
+    label: String,
+    range: NumberRange,
+    rpc: Option<String>,
+    units: String,
+    var: String,
+}
+impl Slider {
+    /// # Panics
+    /// Panics when `var` is empty or `range` has no min or no max.
+    #[must_use]
+    pub fn new(var: impl Into<String>, range: NumberRange) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        assert!(range.min().is_some() && range.max().is_some());
+        Self {
+            initial: None,
+            label: String::new(),
+            range,
+            rpc: None,
+            units: String::new(),
+            var,
+        }
+    }
+
+    /// The client uses the range's min by default.
+    ///
+    /// # Panics
+    /// Panics when `value` is NaN or infinity.
+    #[must_use]
+    pub fn with_initial(mut self, value: f32) -> Self {
+        self.initial = Some(Real32::new(value));
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Makes the client call `rpc` when the user lets go of the handle.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    /// Sets the units that the client shows after the number, like `kg` or `%`.
+    #[must_use]
+    pub fn with_units(mut self, units: impl Into<String>) -> Self {
+        self.units = units.into();
+        self
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_widget(self) -> Widget {
+        Widget::SliderVariant {
+            initial_number: self.initial,
+            integer: self.range.is_integer(),
+            label: self.label,
+            max: Real32::new(self.range.max().unwrap()),
+            min: Real32::new(self.range.min().unwrap()),
+            rpc: self.rpc,
+            step: self.range.step().map(Real32::new),
+            units: self.units,
+            var: self.var,
+        }
+    }
+}
+impl From<Slider> for Widget {
+    fn from(src: Slider) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Slider> for Option<Widget> {
+    fn from(src: Slider) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::internal::Widget;
+
+/// Fills the free space in a [`crate::widget::Row`] or [`crate::widget::Column`].
+/// Spacers in the same row or column share the free space equally.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Spacer {
+    min_size: u16,
+}
+impl Spacer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { min_size: 0 }
+    }
+
+    /// Makes the spacer at least `min_size` wide in a row, or tall in a column.
+    #[must_use]
+    pub fn with_min_size(mut self, min_size: u16) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::SpacerVariant {
+            min_size: self.min_size,
+        }
+    }
+}
+impl From<Spacer> for Widget {
+    fn from(src: Spacer) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Spacer> for Option<Widget> {
+    fn from(src: Spacer) -> Self {
+        Some(src.to_widget())
+    }
+}
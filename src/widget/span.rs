@@ -0,0 +1,135 @@
+use crate::internal::Action;
+use crate::style::check_color_ref;
+use crate::widget::SpanList;
+use serde::{Deserialize, Serialize};
+
+/// A run of text in a [`crate::widget::RichText`], with optional child spans.
+///
+/// Clients show `text` and then the child `spans`.
+/// Children inherit formatting and links from their parents.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Span {
+    /// Tapping the span performs these actions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<Action>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    /// A theme color name, like `accent`, or a color like `#0a84ff`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub monospace: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<Span>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strikethrough: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+}
+impl Span {
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Makes a span with no text of its own, to format `spans` together.
+    #[must_use]
+    pub fn group(spans: impl Into<SpanList>) -> Self {
+        Self {
+            spans: spans.into().0,
+            ..Self::default()
+        }
+    }
+
+    /// Appends `action`.
+    #[must_use]
+    pub fn with_action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Appends `actions`.
+    #[must_use]
+    pub fn with_actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        self.actions.extend(actions);
+        self
+    }
+
+    #[must_use]
+    pub fn with_bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// # Panics
+    /// Panics when `color` is neither a theme color name nor a color like `#0a84ff`.
+    #[must_use]
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        let color = color.into();
+        check_color_ref(&color).unwrap();
+        self.color = Some(color);
+        self
+    }
+
+    #[must_use]
+    pub fn with_italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    #[must_use]
+    pub fn with_monospace(mut self) -> Self {
+        self.monospace = true;
+        self
+    }
+
+    /// Appends a child span.
+    #[must_use]
+    pub fn with_span(mut self, span: impl Into<Span>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Returns the span's text followed by its children's text.
+    #[must_use]
+    pub fn plain_text(&self) -> String {
+        let mut out = self.text.clone();
+        for span in &self.spans {
+            out.push_str(&span.plain_text());
+        }
+        out
+    }
+
+    /// Returns the plain text and actions of each span that has actions.
+    #[must_use]
+    pub fn links(&self) -> Vec<(String, &[Action])> {
+        if self.actions.is_empty() {
+            self.spans.iter().flat_map(Span::links).collect()
+        } else {
+            vec![(self.plain_text(), &self.actions)]
+        }
+    }
+}
+impl From<&str> for Span {
+    fn from(src: &str) -> Self {
+        Self::new(src)
+    }
+}
+impl From<String> for Span {
+    fn from(src: String) -> Self {
+        Self::new(src)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::internal::{Widget, WidgetList};
+use crate::style::Style;
+use crate::widget::{HAlignment, Styled, VAlignment};
+
+/// Shows widgets on top of each other.
+/// The first widget is at the back.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Stack {
+    h_alignment: HAlignment,
+    style: Option<Style>,
+    v_alignment: VAlignment,
+    widgets: Vec<Widget>,
+}
+impl Stack {
+    /// Makes a `stack` widget with alignment `start` and `top`.
+    #[must_use]
+    pub fn new(widgets: impl Into<WidgetList>) -> Self {
+        Self {
+            h_alignment: HAlignment::Start,
+            style: None,
+            v_alignment: VAlignment::Top,
+            widgets: widgets.into().0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_alignment(mut self, h_alignment: HAlignment, v_alignment: VAlignment) -> Self {
+        self.h_alignment = h_alignment;
+        self.v_alignment = v_alignment;
+        self
+    }
+
+    /// Appends `widget`, in front of the other widgets.
+    #[must_use]
+    pub fn with_widget(mut self, widget: impl Into<Widget>) -> Self {
+        self.widgets.push(widget.into());
+        self
+    }
+
+    /// Appends `widgets`.
+    #[must_use]
+    pub fn with_widgets(mut self, widgets: impl Into<WidgetList>) -> Self {
+        self.widgets.extend(widgets.into().0);
+        self
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Styled::wrap(
+            self.style,
+            Widget::StackVariant {
+                h_alignment: self.h_alignment,
+                v_alignment: self.v_alignment,
+                widgets: self.widgets,
+            },
+        )
+    }
+}
+impl From<Stack> for Widget {
+    fn from(src: Stack) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Stack> for Option<Widget> {
+    fn from(src: Stack) -> Self {
+        Some(src.to_widget())
+    }
+}
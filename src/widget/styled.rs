@@ -0,0 +1,47 @@
+use crate::internal::Widget;
+use crate::style::Style;
+
+/// Applies style overrides to a widget.
+///
+/// Clients that do not support styles show the widget without them.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Styled {
+    style: Style,
+    widget: Widget,
+}
+impl Styled {
+    #[must_use]
+    pub fn new(style: Style, widget: impl Into<Widget>) -> Self {
+        Self {
+            style,
+            widget: widget.into(),
+        }
+    }
+
+    /// Returns `widget` with `style`, or just `widget` when `style` is `None`.
+    #[must_use]
+    pub fn wrap(style: Option<Style>, widget: impl Into<Widget>) -> Widget {
+        match style {
+            Some(style) => Self::new(style, widget).to_widget(),
+            None => widget.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::StyledVariant {
+            style: self.style,
+            widget: Box::new(self.widget),
+        }
+    }
+}
+impl From<Styled> for Widget {
+    fn from(src: Styled) -> Self {
+        src.to_widget()
+    }
+}
+impl From<Styled> for Option<Widget> {
+    fn from(src: Styled) -> Self {
+        Some(src.to_widget())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::internal::{Page, Tab};
+use crate::session::PageKey;
+use crate::style::Theme;
+
+/// A page with a tab bar.
+/// Each tab shows a page from the page map and keeps its own stack of pages.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TabPage {
+    selected: Option<String>,
+    tabs: Vec<Tab>,
+    theme: Option<Theme>,
+    title: String,
+}
+impl TabPage {
+    #[must_use]
+    pub fn new(title: impl Into<String>, tabs: impl IntoIterator<Item = Tab>) -> Self {
+        Self {
+            selected: None,
+            tabs: tabs.into_iter().collect(),
+            theme: None,
+            title: title.into(),
+        }
+    }
+
+    /// Selects the tab that shows `page_key`.  The default is the first tab.
+    /// See [`crate::session::ApplinSession::select_tab`] to change it from outside the page function.
+    #[must_use]
+    pub fn with_selected(mut self, page_key: &PageKey) -> Self {
+        self.selected = Some(page_key.clone().into_inner());
+        self
+    }
+
+    #[must_use]
+    pub fn with_tab(mut self, tab: Tab) -> Self {
+        self.tabs.push(tab);
+        self
+    }
+
+    /// Sets theme tokens for this page.
+    /// They replace the session theme's tokens with the same names.
+    #[must_use]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    #[must_use]
+    pub fn to_page(self) -> Page {
+        Page::Tab {
+            selected: self.selected,
+            tabs: self.tabs,
+            theme: self.theme,
+            title: self.title,
+        }
+    }
+}
+impl From<TabPage> for Page {
+    fn from(src: TabPage) -> Self {
+        src.to_page()
+    }
+}
@@ -1,18 +1,31 @@
 use crate::internal::Widget;
+use crate::style::Style;
+use crate::widget::Styled;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Text {
+    style: Option<Style>,
     text: String,
 }
 impl Text {
     #[must_use]
     pub fn new(text: impl Into<String>) -> Self {
-        Self { text: text.into() }
+        Self {
+            style: None,
+            text: text.into(),
+        }
+    }
+
+    /// Overrides the theme's style for this widget.
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
     }
 
     #[must_use]
     pub fn to_widget(self) -> Widget {
-        Widget::TextVariant { text: self.text }
+        Styled::wrap(self.style, Widget::TextVariant { text: self.text })
     }
 }
 impl From<Text> for Widget {
@@ -0,0 +1,89 @@
+use crate::internal::Widget;
+use crate::widget::Time;
+
+/// Lets the user choose a time.
+///
+/// The client sends the var as a string like `23:59`,
+/// or `null` when the user has not chosen a time.
+/// Deserialize it with [`Time`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TimePicker {
+    initial: Option<Time>,
+    label: String,
+    max: Option<Time>,
+    min: Option<Time>,
+    rpc: Option<String>,
+    var: String,
+}
+impl TimePicker {
+    /// # Panics
+    /// Panics when `var` is empty.
+    #[must_use]
+    pub fn new(var: impl Into<String>) -> Self {
+        let var = var.into();
+        assert!(!var.is_empty());
+        Self {
+            initial: None,
+            label: String::new(),
+            max: None,
+            min: None,
+            rpc: None,
+            var,
+        }
+    }
+
+    #[must_use]
+    pub fn with_initial(mut self, time: Time) -> Self {
+        self.initial = Some(time);
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the latest time that the user can choose.
+    #[must_use]
+    pub fn with_max(mut self, time: Time) -> Self {
+        self.max = Some(time);
+        self
+    }
+
+    /// Sets the earliest time that the user can choose.
+    #[must_use]
+    pub fn with_min(mut self, time: Time) -> Self {
+        self.min = Some(time);
+        self
+    }
+
+    /// Makes the client call `rpc` when the user chooses a time.
+    #[must_use]
+    pub fn with_rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    #[must_use]
+    pub fn to_widget(self) -> Widget {
+        Widget::TimePickerVariant {
+            initial_time: self.initial,
+            label: self.label,
+            max_time: self.max,
+            min_time: self.min,
+            rpc: self.rpc,
+            var: self.var,
+        }
+    }
+}
+impl From<TimePicker> for Widget {
+    fn from(src: TimePicker) -> Self {
+        src.to_widget()
+    }
+}
+impl From<TimePicker> for Option<Widget> {
+    fn from(src: TimePicker) -> Self {
+        Some(src.to_widget())
+    }
+}
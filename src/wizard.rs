@@ -0,0 +1,352 @@
+//! Multi-step forms.
+//!
+//! A [`Wizard`] shows one step at a time on a single page,
+//! keeps the answers in the session, and checks each step's [`FormRules`]
+//! before moving to the next step.
+use crate::action::{pop, push, rpc};
+use crate::data::Rebuilder;
+use crate::error::user_error;
+use crate::internal::{Action, Page, Widget};
+use crate::rules::FormRules;
+use crate::session::{ApplinSession, PageKey, PageMap};
+use crate::widget::{BackButton, Button, Column, Form, FormButton, NavPage, Text};
+use core::fmt::{Debug, Formatter};
+use serde_json::{Map, Value};
+use servlin::{Request, Response};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// A session's progress through a wizard.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WizardProgress {
+    /// Values of the vars from the steps that passed their rules.
+    pub answers: Map<String, Value>,
+    /// True after the completion handler succeeds.
+    pub done: bool,
+    /// Index of the current step.
+    pub step: usize,
+}
+
+/// Wizard page key to the session's progress through the wizard.
+/// Sessions keep this in their [`crate::session::Extensions`].
+#[derive(Default)]
+struct Wizards(HashMap<String, WizardProgress>);
+
+#[allow(clippy::module_name_repetitions)]
+pub type WizardWidgetFn = dyn 'static + Send + Sync + Fn(&Map<String, Value>) -> Widget;
+
+/// One page of a [`Wizard`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
+pub struct WizardStep {
+    rules: FormRules,
+    title: String,
+    widget_fn: Arc<WizardWidgetFn>,
+}
+impl WizardStep {
+    /// Makes a step that shows the widget returned by `widget_fn`.
+    /// The wizard calls `widget_fn` with the answers from all steps,
+    /// so the widgets can show earlier answers.
+    #[must_use]
+    pub fn new(
+        title: impl Into<String>,
+        widget_fn: impl 'static + Send + Sync + Fn(&Map<String, Value>) -> Widget,
+    ) -> Self {
+        Self {
+            rules: FormRules::new(),
+            title: title.into(),
+            widget_fn: Arc::new(widget_fn),
+        }
+    }
+
+    /// The wizard checks these rules before moving to the next step.
+    #[must_use]
+    pub fn with_rules(mut self, rules: FormRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    #[must_use]
+    pub fn rules(&self) -> &FormRules {
+        &self.rules
+    }
+
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+}
+impl Debug for WizardStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "WizardStep{{title={:?}, rules={:?}}}",
+            self.title, self.rules
+        )
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub type WizardCompleteFn<T> = dyn 'static
+    + Send
+    + Sync
+    + Fn(&Arc<ApplinSession<T>>, Map<String, Value>) -> Result<(), String>;
+
+/// A multi-step form on one page.
+///
+/// Add the page with [`Wizard::add_to`], route POST requests for paths that start
+/// with the wizard's key to [`Wizard::handle`],
+/// and open the wizard with the actions from [`Wizard::start_actions`].
+///
+/// ```
+/// use applin::rules::{FormRules, Rule};
+/// use applin::session::PageMap;
+/// use applin::widget::{Button, Textfield};
+/// use applin::wizard::{Wizard, WizardStep};
+/// use std::sync::Arc;
+///
+/// let wizard: Arc<Wizard<()>> = Arc::new(
+///     Wizard::new("/signup", |_session, answers| {
+///         println!("signup {answers:?}");
+///         Ok(())
+///     })
+///     .with_step(
+///         WizardStep::new("Name", |_| Textfield::new("name").into())
+///             .with_rules(FormRules::new().with_rule("name", Rule::required())),
+///     )
+///     .with_step(WizardStep::new("Email", |_| Textfield::new("email").into())),
+/// );
+/// let mut page_map = PageMap::new();
+/// wizard.add_to(&mut page_map);
+/// let button = Button::new("Sign Up").with_actions(wizard.start_actions());
+/// ```
+pub struct Wizard<T> {
+    done_widget: Widget,
+    key: String,
+    on_complete: Box<WizardCompleteFn<T>>,
+    steps: Vec<WizardStep>,
+}
+impl<T: 'static + Send + Sync> Wizard<T> {
+    /// Makes a wizard with page key `key`.
+    /// After the user finishes the last step, the wizard calls `on_complete` with the answers.
+    /// When `on_complete` returns an error, the client shows it and stays on the last step.
+    ///
+    /// # Panics
+    /// Panics when `key` does not start with `/`.
+    #[must_use]
+    pub fn new(
+        key: impl Into<String>,
+        on_complete: impl 'static
+            + Send
+            + Sync
+            + Fn(&Arc<ApplinSession<T>>, Map<String, Value>) -> Result<(), String>,
+    ) -> Self {
+        let key = key.into();
+        assert!(key.starts_with('/'), "wizard key must start with '/'");
+        Self {
+            done_widget: Text::new("Done").into(),
+            key,
+            on_complete: Box::new(on_complete),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Sets the widget shown after the wizard completes.
+    /// The default is the text "Done".
+    #[must_use]
+    pub fn with_done_widget(mut self, widget: impl Into<Widget>) -> Self {
+        self.done_widget = widget.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_step(mut self, step: WizardStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    #[must_use]
+    pub fn with_steps(mut self, steps: impl IntoIterator<Item = WizardStep>) -> Self {
+        self.steps.extend(steps);
+        self
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> &[WizardStep] {
+        &self.steps
+    }
+
+    #[must_use]
+    pub fn back_path(&self) -> String {
+        format!("{}/back", self.key)
+    }
+
+    #[must_use]
+    pub fn next_path(&self) -> String {
+        format!("{}/next", self.key)
+    }
+
+    #[must_use]
+    pub fn start_path(&self) -> String {
+        format!("{}/start", self.key)
+    }
+
+    /// Actions that clear the session's answers and open the first step.
+    #[must_use]
+    pub fn start_actions(&self) -> Vec<Action> {
+        vec![
+            rpc(self.start_path()),
+            push(&PageKey::new(self.key.clone())),
+        ]
+    }
+
+    /// Adds the wizard's page to `page_map`.
+    pub fn add_to(self: &Arc<Self>, page_map: &mut PageMap<T>) -> PageKey {
+        let self_clone = Arc::clone(self);
+        page_map.add_page_fn(self.key.clone(), move |rebuilder| {
+            self_clone.page(&rebuilder)
+        })
+    }
+
+    /// Returns the session's progress through the wizard.
+    #[must_use]
+    pub fn progress(&self, session: &ApplinSession<T>) -> WizardProgress {
+        session
+            .lock_extensions()
+            .get::<Wizards>()
+            .and_then(|wizards| wizards.0.get(&self.key))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_progress(&self, session: &ApplinSession<T>, progress: WizardProgress) {
+        session
+            .lock_extensions()
+            .get_or_default::<Wizards>()
+            .0
+            .insert(self.key.clone(), progress);
+    }
+
+    /// Builds the page for the session's current step.
+    ///
+    /// # Errors
+    /// Returns an error when the session is gone.
+    pub fn page(&self, rebuilder: &Rebuilder<T>) -> Result<Page, Box<dyn std::error::Error>> {
+        let session = rebuilder.session()?;
+        let progress = self.progress(&session);
+        let Some(step) = self.steps.get(progress.step).filter(|_| !progress.done) else {
+            return Ok(NavPage::new(
+                self.steps.last().map(WizardStep::title).unwrap_or_default(),
+                Column::new((
+                    self.done_widget.clone(),
+                    Button::new("OK").with_action(pop()),
+                )),
+            )
+            .without_back()
+            .into());
+        };
+        let is_last = progress.step + 1 == self.steps.len();
+        let widget = step.rules.apply(
+            &session.field_errors(&self.key),
+            Form::new((
+                (step.widget_fn)(&progress.answers),
+                FormButton::new(if is_last { "Done" } else { "Next" })
+                    .with_action(rpc(self.next_path())),
+            )),
+        );
+        let page = NavPage::new(
+            format!(
+                "{} ({}/{})",
+                step.title,
+                progress.step + 1,
+                self.steps.len()
+            ),
+            widget,
+        );
+        if progress.step == 0 {
+            Ok(page.into())
+        } else {
+            Ok(page
+                .with_start(BackButton::new().with_action(rpc(self.back_path())))
+                .into())
+        }
+    }
+
+    fn rebuild(&self, session: &Arc<ApplinSession<T>>) {
+        if session.lock_inner().page_map.contains_key(&self.key) {
+            session.rebuild_value(&self.key, session.rpc_context());
+        }
+    }
+
+    /// Handles the wizard's RPCs:
+    /// - `start` clears the answers and shows the first step
+    /// - `next` checks the step's rules, saves its vars, and shows the next step.
+    ///   After the last step, it calls the completion handler.
+    /// - `back` shows the previous step
+    ///
+    /// # Errors
+    /// Returns an error response when the path is not one of the wizard's RPCs,
+    /// the wizard is done and gets `back`,
+    /// the step fails its rules, or the completion handler fails.
+    pub fn handle(
+        &self,
+        session: &Arc<ApplinSession<T>>,
+        req: &Request,
+    ) -> Result<Response, Response> {
+        let path = req.url.path();
+        if path == self.start_path() {
+            self.set_progress(session, WizardProgress::default());
+        } else if path == self.back_path() {
+            let mut progress = self.progress(session);
+            if progress.done {
+                return Err(Response::not_found_404());
+            }
+            progress.step = progress.step.saturating_sub(1);
+            self.set_progress(session, progress);
+        } else if path == self.next_path() {
+            self.next(session, req)?;
+        } else {
+            return Err(Response::not_found_404());
+        }
+        self.rebuild(session);
+        session.rpc_response()
+    }
+
+    fn next(&self, session: &Arc<ApplinSession<T>>, req: &Request) -> Result<(), Response> {
+        let vars: Map<String, Value> = req.json()?;
+        let progress = self.progress(session);
+        let Some(step) = self.steps.get(progress.step).filter(|_| !progress.done) else {
+            return Ok(());
+        };
+        step.rules.enforce(session, &self.key, &vars)?;
+        // The client sends the vars of every page, so keep only the step's vars.
+        let widget = (step.widget_fn)(&progress.answers);
+        let step_vars: HashSet<&str> = widget.vars().into_iter().chain(step.rules.vars()).collect();
+        let mut answers = progress.answers.clone();
+        answers.extend(
+            vars.into_iter()
+                .filter(|(var, _)| step_vars.contains(var.as_str())),
+        );
+        let mut progress = WizardProgress {
+            answers,
+            done: false,
+            step: progress.step + 1,
+        };
+        if progress.step == self.steps.len() {
+            (self.on_complete)(session, progress.answers.clone()).map_err(user_error)?;
+            progress.done = true;
+        }
+        self.set_progress(session, progress);
+        Ok(())
+    }
+}
+impl<T> Debug for Wizard<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "Wizard{{key={:?}, steps={:?}}}", self.key, self.steps)
+    }
+}
@@ -95,3 +95,105 @@ fn action_deserialize() {
         Action::Rpc("/method?arg=val".to_string())
     );
 }
+
+#[test]
+fn choose_file() {
+    let action = applin::action::choose_file("/upload?a=b:c", ["image/*", "application/pdf"], 1024);
+    assert_eq!(
+        Value::String("choose-file:1024:image/*,application/pdf:/upload?a=b:c".to_string()),
+        action.to_value()
+    );
+    assert_eq!(action, serde_json::from_value(action.to_value()).unwrap());
+    assert_eq!(
+        Action::ChooseFile {
+            accepted_types: Vec::new(),
+            max_bytes: 5,
+            upload_url: "/u".to_string()
+        },
+        serde_json::from_str::<Action>("\"choose-file:5::/u\"").unwrap()
+    );
+    for s in [
+        "choose-file:",
+        "choose-file:5:",
+        "choose-file:5::",
+        "choose-file:x::/u",
+        "choose-file:-1::/u",
+    ] {
+        serde_json::from_value::<Action>(Value::String(s.to_string())).unwrap_err();
+    }
+}
+
+#[test]
+fn confirm() {
+    let action = applin::action::confirm(
+        "Delete?",
+        "This cannot be undone.",
+        [applin::action::rpc("/delete"), Action::Pop],
+    );
+    assert_eq!(
+        serde_json::json!({
+            "typ": "confirm",
+            "title": "Delete?",
+            "text": "This cannot be undone.",
+            "then": ["rpc:/delete", "pop"],
+        }),
+        action.to_value()
+    );
+    assert_eq!(action, serde_json::from_value(action.to_value()).unwrap());
+    assert_eq!(
+        serde_json::json!({"typ": "confirm", "title": "Delete?", "then": []}),
+        applin::action::confirm("Delete?", "", []).to_value()
+    );
+    serde_json::from_value::<Action>(serde_json::json!({"typ": "confirm", "then": []}))
+        .unwrap_err();
+    serde_json::from_value::<Action>(serde_json::json!({"typ": "other"})).unwrap_err();
+}
+
+#[test]
+fn rpc_branch() {
+    let action = applin::action::rpc_branch(
+        "/pay",
+        [Action::Push("/paid".to_string())],
+        [applin::action::confirm(
+            "Try again?",
+            "",
+            [applin::action::rpc("/pay")],
+        )],
+    );
+    assert_eq!(
+        serde_json::json!({
+            "typ": "rpc",
+            "url": "/pay",
+            "on-success": ["push:/paid"],
+            "on-error": [{"typ": "confirm", "title": "Try again?", "then": ["rpc:/pay"]}],
+        }),
+        action.to_value()
+    );
+    assert_eq!(action, serde_json::from_value(action.to_value()).unwrap());
+    assert_eq!(
+        applin::action::rpc_branch("/pay", [], []),
+        serde_json::from_value(serde_json::json!({"typ": "rpc", "url": "/pay"})).unwrap()
+    );
+}
+
+#[test]
+fn rpc_with_progress() {
+    let action = applin::action::rpc_with_progress("/pay");
+    assert_eq!(
+        Value::String("rpc-with-progress:/pay".to_string()),
+        action.to_value()
+    );
+    assert_eq!(action, serde_json::from_value(action.to_value()).unwrap());
+    serde_json::from_str::<Action>("\"rpc-with-progress:\"").unwrap_err();
+}
+
+#[test]
+fn refresh() {
+    let action = applin::action::refresh("/logo.png");
+    assert_eq!(
+        Value::String("refresh:/logo.png".to_string()),
+        action.to_value()
+    );
+    assert_eq!(action, serde_json::from_value(action.to_value()).unwrap());
+    serde_json::from_str::<Action>("\"refresh:\"").unwrap_err();
+}
@@ -0,0 +1,290 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{confirm, copy_to_clipboard, pop, push, rpc, rpc_branch};
+use applin::internal::{Page, Update};
+use applin::session::PageKey;
+use applin::testing::{AppClient, Connection, RpcError};
+use applin::widget::{
+    AlertModal, BackButton, Button, Checkbox, Column, Date, DatePicker, DateRange, DateRangePicker,
+    Form, ModalButton, NavPage, Text, Textfield, Time, TimePicker,
+};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Returns canned updates and records RPCs.
+#[derive(Default)]
+struct FakeConnection {
+    updates: Mutex<Vec<Update>>,
+    rpcs: Mutex<Vec<(String, Value)>>,
+}
+impl FakeConnection {
+    fn with_update(self, pages: impl IntoIterator<Item = (&'static str, Option<Page>)>) -> Self {
+        self.updates.lock().unwrap().push(Update {
+            pages: pages
+                .into_iter()
+                .map(|(key, page)| (key.to_string(), page))
+                .collect::<BTreeMap<_, _>>(),
+            ..Update::default()
+        });
+        self
+    }
+
+    fn take_rpcs(&self) -> Vec<(String, Value)> {
+        std::mem::take(&mut self.rpcs.lock().unwrap())
+    }
+}
+impl Connection for FakeConnection {
+    fn poll(&self) -> Result<Update, String> {
+        let mut updates = self.updates.lock().unwrap();
+        if updates.is_empty() {
+            Ok(Update::default())
+        } else {
+            Ok(updates.remove(0))
+        }
+    }
+
+    fn rpc(&self, path: &str, vars: &Map<String, Value>) -> Result<Update, RpcError> {
+        if path == "/error" {
+            return Err(RpcError::Failed("500 error1".to_string()));
+        }
+        self.rpcs
+            .lock()
+            .unwrap()
+            .push((path.to_string(), Value::Object(vars.clone())));
+        Ok(self.poll()?)
+    }
+}
+
+fn home() -> Page {
+    NavPage::new(
+        "Home",
+        Column::new((
+            Text::new("Welcome"),
+            Button::new("Next").with_action(push(&PageKey::new("/next"))),
+            Button::new("Copy").with_action(copy_to_clipboard("abc")),
+            Button::new("Broken").with_actions([rpc("/error"), push(&PageKey::new("/next"))]),
+        )),
+    )
+    .into()
+}
+
+fn next() -> Page {
+    NavPage::new(
+        "Next",
+        Form::new((
+            Textfield::new("name").with_initial("Al"),
+            Checkbox::new("agree", "Agree").with_rpc("/agree"),
+            Button::new("Save").with_actions([rpc("/save"), pop()]),
+            Button::new("Alert").with_action(push(&PageKey::new("/alert"))),
+        )),
+    )
+    .with_start(BackButton::new().with_action(pop()))
+    .into()
+}
+
+fn alert() -> Page {
+    AlertModal::new("Sure?")
+        .with_widget(ModalButton::new("Yes").with_action(rpc("/yes")))
+        .into()
+}
+
+#[test]
+fn navigate() {
+    let connection = FakeConnection::default().with_update([
+        ("/", Some(home())),
+        ("/next", Some(next())),
+        ("/alert", Some(alert())),
+    ]);
+    let mut client = AppClient::new(connection).unwrap();
+    client.assert_page("/");
+    client.assert_text("Home");
+    client.assert_text("Welcome");
+    client.assert_no_text("Agree");
+    assert_eq!(
+        "page Some(\"/\") has no button \"Missing\", it shows [\"Home\", \"Welcome\", \"Next\", \"Copy\", \"Broken\"]",
+        client.tap("Missing").unwrap_err()
+    );
+    client.tap("Copy").unwrap();
+    assert_eq!(Some("abc"), client.clipboard());
+    client.tap("Next").unwrap();
+    assert_eq!(["/", "/next"], client.stack());
+    client.assert_text("Agree");
+    client.tap("Alert").unwrap();
+    client.assert_text("Sure?");
+    client.tap("Yes").unwrap();
+    client.assert_page("/next");
+    assert_eq!(
+        vec![("/yes".to_string(), json!({"name": "Al", "agree": false}))],
+        client.connection().take_rpcs()
+    );
+    client.tap_back().unwrap();
+    client.assert_page("/");
+    assert_eq!("cannot pop the last page", client.tap_back().unwrap_err());
+}
+
+#[test]
+fn vars_and_rpcs() {
+    let connection =
+        FakeConnection::default().with_update([("/", Some(home())), ("/next", Some(next()))]);
+    let mut client = AppClient::new(connection).unwrap();
+    client.tap("Next").unwrap();
+    assert_eq!(Some(json!("Al")), client.var("name"));
+    client.set_text("name", "Alice").unwrap();
+    assert_eq!(
+        Vec::<(String, Value)>::new(),
+        client.connection().take_rpcs()
+    );
+    client.set_checkbox("agree", true).unwrap();
+    assert_eq!(
+        vec![(
+            "/agree".to_string(),
+            json!({"name": "Alice", "agree": true})
+        )],
+        client.connection().take_rpcs()
+    );
+    client.set_checkbox("name", true).unwrap_err();
+    client.tap("Save").unwrap();
+    client.assert_page("/");
+    assert_eq!("/save", client.connection().take_rpcs()[0].0);
+}
+
+#[test]
+fn pickers() {
+    let page: Page = NavPage::new(
+        "Trip",
+        Form::new((
+            DateRangePicker::new("dates").with_label("Dates"),
+            TimePicker::new("departs").with_initial(Time::new(9, 0)),
+            DatePicker::new("booked").with_rpc("/booked"),
+        )),
+    )
+    .into();
+    let connection = FakeConnection::default().with_update([("/", Some(page))]);
+    let mut client = AppClient::new(connection).unwrap();
+    client.assert_text("Dates");
+    assert_eq!(Some(Value::Null), client.var("dates"));
+    assert_eq!(Some(json!("09:00")), client.var("departs"));
+    let range = DateRange::new(Date::new(2024, 7, 1), Date::new(2024, 7, 4));
+    client.set_date_range("dates", range).unwrap();
+    client.set_time("departs", Time::new(10, 30)).unwrap();
+    client.set_date("booked", Date::new(2024, 6, 1)).unwrap();
+    assert_eq!(
+        vec![(
+            "/booked".to_string(),
+            json!({"dates": "2024-07-01/2024-07-04", "departs": "10:30", "booked": "2024-06-01"})
+        )],
+        client.connection().take_rpcs()
+    );
+    client.set_date("dates", Date::new(2024, 6, 1)).unwrap_err();
+}
+
+#[test]
+fn rpc_error_stops_actions() {
+    let connection =
+        FakeConnection::default().with_update([("/", Some(home())), ("/next", Some(next()))]);
+    let mut client = AppClient::new(connection).unwrap();
+    assert_eq!(
+        "rpc \"/error\" failed: 500 error1",
+        client.tap("Broken").unwrap_err()
+    );
+    client.assert_page("/");
+}
+
+#[test]
+fn removed_page_leaves_stack() {
+    let connection = FakeConnection::default()
+        .with_update([("/", Some(home())), ("/next", Some(next()))])
+        .with_update([("/next", None)]);
+    let mut client = AppClient::new(connection).unwrap();
+    client.tap("Next").unwrap();
+    client.poll().unwrap();
+    client.assert_page("/");
+    assert_eq!(
+        "cannot push unknown page \"/next\"",
+        client.tap("Next").unwrap_err()
+    );
+}
+
+fn branches() -> Page {
+    NavPage::new(
+        "Branches",
+        Column::new((
+            Button::new("Delete").with_actions([
+                confirm("Delete?", "This cannot be undone.", [rpc("/delete")]),
+                push(&PageKey::new("/next")),
+            ]),
+            Button::new("Pay").with_actions([
+                rpc_branch(
+                    "/pay",
+                    [copy_to_clipboard("paid")],
+                    [copy_to_clipboard("failed")],
+                ),
+                push(&PageKey::new("/next")),
+            ]),
+            Button::new("Pay Broken").with_actions([
+                rpc_branch(
+                    "/error",
+                    [copy_to_clipboard("paid")],
+                    [copy_to_clipboard("failed")],
+                ),
+                push(&PageKey::new("/next")),
+            ]),
+            Button::new("Pay Broken Plain").with_action(rpc_branch(
+                "/error",
+                [copy_to_clipboard("paid")],
+                [],
+            )),
+        )),
+    )
+    .into()
+}
+
+#[test]
+fn confirm_action() {
+    let connection =
+        FakeConnection::default().with_update([("/", Some(branches())), ("/next", Some(next()))]);
+    let mut client = AppClient::new(connection).unwrap();
+    assert_eq!(None, client.confirmation());
+    client.tap("Delete").unwrap();
+    assert_eq!(
+        Some(("Delete?", "This cannot be undone.")),
+        client.confirmation()
+    );
+    client.assert_text("This cannot be undone.");
+    assert_eq!(
+        "confirmation \"Delete?\" is showing",
+        client.tap("Delete").unwrap_err()
+    );
+    client.cancel_confirmation().unwrap();
+    assert_eq!(None, client.confirmation());
+    assert!(client.connection().take_rpcs().is_empty());
+    client.assert_page("/");
+    client.tap("Delete").unwrap();
+    client.accept_confirmation().unwrap();
+    assert_eq!(
+        vec![("/delete".to_string(), json!({}))],
+        client.connection().take_rpcs()
+    );
+    client.assert_page("/next");
+    assert_eq!(
+        "no confirmation is showing",
+        client.accept_confirmation().unwrap_err()
+    );
+}
+
+#[test]
+fn rpc_branch_action() {
+    let connection =
+        FakeConnection::default().with_update([("/", Some(branches())), ("/next", Some(next()))]);
+    let mut client = AppClient::new(connection).unwrap();
+    client.tap("Pay Broken").unwrap();
+    assert_eq!(Some("failed"), client.clipboard());
+    client.assert_page("/");
+    assert_eq!(
+        "rpc \"/error\" failed: 500 error1",
+        client.tap("Pay Broken Plain").unwrap_err()
+    );
+    client.tap("Pay").unwrap();
+    assert_eq!(Some("paid"), client.clipboard());
+    client.assert_page("/next");
+}
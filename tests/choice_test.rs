@@ -0,0 +1,77 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::internal::{Page, Update};
+use applin::testing::{AppClient, Connection, RpcError};
+use applin::widget::{Form, MultiSelect, NavPage, Picker, RadioGroup};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Sends one page and records RPCs.
+struct OnePage {
+    page: Mutex<Option<Page>>,
+    rpcs: Mutex<Vec<(String, Value)>>,
+}
+impl Connection for OnePage {
+    fn poll(&self) -> Result<Update, String> {
+        Ok(Update {
+            pages: self
+                .page
+                .lock()
+                .unwrap()
+                .take()
+                .map(|page| BTreeMap::from([("/".to_string(), Some(page))]))
+                .unwrap_or_default(),
+            ..Update::default()
+        })
+    }
+
+    fn rpc(&self, path: &str, vars: &Map<String, Value>) -> Result<Update, RpcError> {
+        self.rpcs
+            .lock()
+            .unwrap()
+            .push((path.to_string(), Value::Object(vars.clone())));
+        Ok(Update::default())
+    }
+}
+
+#[test]
+fn app_client() {
+    let page = NavPage::new(
+        "Order",
+        Form::new((
+            RadioGroup::new("size")
+                .with_label("Size")
+                .with_options([("s", "Small"), ("l", "Large")]),
+            Picker::new("color")
+                .with_options([("r", "Red"), ("g", "Green")])
+                .with_initial("g"),
+            MultiSelect::new("toppings")
+                .with_options([("a", "Anchovies"), ("b", "Basil")])
+                .with_rpc("/toppings"),
+        )),
+    );
+    let mut client = AppClient::new(OnePage {
+        page: Mutex::new(Some(page.into())),
+        rpcs: Mutex::new(Vec::new()),
+    })
+    .unwrap();
+    client.assert_text("Size");
+    client.assert_text("Anchovies");
+    assert_eq!(Some(Value::Null), client.var("size"));
+    assert_eq!(Some(json!("g")), client.var("color"));
+    assert_eq!(Some(json!([])), client.var("toppings"));
+    client.set_choice("size", "l").unwrap();
+    assert_eq!(
+        "\"size\" has no option \"m\"",
+        client.set_choice("size", "m").unwrap_err()
+    );
+    client.set_choice("toppings", "a").unwrap_err();
+    client.set_choices("toppings", &["a", "b"]).unwrap();
+    assert_eq!(
+        vec![(
+            "/toppings".to_string(),
+            json!({"size": "l", "color": "g", "toppings": ["a", "b"]})
+        )],
+        *client.connection().rpcs.lock().unwrap()
+    );
+}
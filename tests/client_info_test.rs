@@ -0,0 +1,408 @@
+use applin::action::{
+    choose_file, confirm, nothing, pop, push, refresh, rpc, rpc_branch, rpc_with_progress,
+};
+use applin::internal::{
+    Action, Chunk, JobStatus, Page, Tab, Toast, Update, Widget, UPDATE_APP_TEXT,
+};
+use applin::session::{ClientInfo, PageKey, PageMap, SessionSet, PROTOCOL_VERSION};
+use applin::style::{Style, Theme};
+use applin::testing::Harness;
+use applin::widget::{
+    BackButton, Button, Column, DatePicker, Form, FormButton, Frame, Icon, LazyList, NavButton,
+    NavPage, Padding, PlainPage, ProgressBar, RadioGroup, RichText, Row, SheetDetent, SheetModal,
+    Spacer, Span, Stack, Styled, TabPage, Text, Textfield, TimePicker,
+};
+use serde_json::Value;
+use servlin::{Event, HeaderList, ResponseBody};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[test]
+fn parse() {
+    assert_eq!(
+        ClientInfo {
+            protocol_version: 1,
+            app_version: None
+        },
+        ClientInfo::parse(None, None).unwrap()
+    );
+    assert_eq!(
+        ClientInfo {
+            protocol_version: 3,
+            app_version: Some("1.2.3".to_string())
+        },
+        ClientInfo::parse(Some(" 3 "), Some("1.2.3")).unwrap()
+    );
+    assert_eq!(
+        "invalid protocol version: 0",
+        ClientInfo::parse(Some("0"), None).unwrap_err()
+    );
+    ClientInfo::parse(Some(""), None).unwrap_err();
+    ClientInfo::parse(Some("-1"), None).unwrap_err();
+    ClientInfo::parse(Some("1.0"), None).unwrap_err();
+}
+
+#[test]
+fn supports() {
+    let client_info = ClientInfo::parse(Some("2"), None).unwrap();
+    assert!(client_info.supports(1));
+    assert!(client_info.supports(2));
+    assert!(!client_info.supports(3));
+    assert!(ClientInfo::default().supports(1));
+}
+
+#[test]
+fn downgrade_keeps_supported_widgets() {
+    let page: Page = NavPage::new(
+        "Page1",
+        Column::new((Text::new("t1"), Form::new(Textfield::new("name")))),
+    )
+    .into();
+    let mut downgraded = page.clone();
+    downgraded.downgrade(1);
+    assert_eq!(page, downgraded);
+    let mut downgraded = page.clone();
+    downgraded.downgrade(PROTOCOL_VERSION);
+    assert_eq!(page, downgraded);
+}
+
+#[test]
+fn min_protocol_version() {
+    let widget: Widget = Column::new(Text::new("t1")).into();
+    assert_eq!(1, widget.min_protocol_version());
+    assert_eq!(1, widget.children()[0].min_protocol_version());
+    let page: Page = NavPage::new("Page1", widget).into();
+    assert_eq!(1, page.min_protocol_version());
+}
+
+#[test]
+fn downgrade_actions() {
+    let key = PageKey::new("/a");
+    for actions in [
+        vec![choose_file("/upload", ["image/*"], 1024)],
+        vec![confirm("Delete?", "", [rpc("/delete")])],
+        vec![refresh("/a")],
+        vec![rpc_branch("/save", [pop()], [])],
+        vec![rpc("/save"), rpc_with_progress("/export")],
+    ] {
+        let mut widget: Widget = Button::new("b1").with_actions(actions.clone()).into();
+        widget.downgrade(1);
+        assert_eq!(
+            Widget::from(Button::new("b1").with_action(nothing())),
+            widget
+        );
+        let mut widget: Widget = Button::new("b1").with_actions(actions).into();
+        let expected = widget.clone();
+        widget.downgrade(2);
+        assert_eq!(expected, widget);
+    }
+    let mut widget: Widget = Column::new((
+        BackButton::new().with_actions([rpc_with_progress("/a"), pop()]),
+        NavButton::new("n1").with_action(refresh("/a")),
+        FormButton::new("f1").with_action(rpc_branch("/a", [], [])),
+    ))
+    .into();
+    widget.downgrade(1);
+    let expected: Widget = Column::new((
+        BackButton::new().with_action(nothing()),
+        NavButton::new("n1").with_action(nothing()),
+        FormButton::new("f1").with_action(nothing()),
+    ))
+    .into();
+    assert_eq!(expected, widget);
+    // Supported actions stay.
+    let mut widget: Widget = Button::new("b1")
+        .with_actions([rpc("/a"), push(&key), pop()])
+        .into();
+    let expected = widget.clone();
+    widget.downgrade(1);
+    assert_eq!(expected, widget);
+    assert_eq!(
+        2,
+        Action::RpcWithProgress("/a".to_string()).min_protocol_version()
+    );
+    assert_eq!(1, Action::Rpc("/a".to_string()).min_protocol_version());
+}
+
+#[test]
+fn downgrade_icons() {
+    let mut widget: Widget = Column::new((
+        Button::new("b1").with_icon("star"),
+        FormButton::new("f1").with_icon("star"),
+        NavButton::new("n1").with_icon("star"),
+    ))
+    .into();
+    let expected = widget.clone();
+    widget.downgrade(2);
+    assert_eq!(expected, widget);
+    widget.downgrade(1);
+    let expected: Widget = Column::new((
+        Button::new("b1"),
+        FormButton::new("f1"),
+        NavButton::new("n1"),
+    ))
+    .into();
+    assert_eq!(expected, widget);
+    // Old clients skip icons.
+    let mut widget: Widget = Column::new(Icon::new("star")).into();
+    widget.downgrade(1);
+    assert_eq!(Widget::from(Column::new(Widget::EmptyVariant)), widget);
+}
+
+#[test]
+fn downgrade_page_fields() {
+    let theme = Theme::empty().with_radius("card", 12);
+    for (mut page, expected) in [
+        (
+            Page::from(
+                NavPage::new("Page1", Text::new("t1"))
+                    .with_theme(theme.clone())
+                    .with_pull_to_refresh("/refresh"),
+            ),
+            Page::from(NavPage::new("Page1", Text::new("t1"))),
+        ),
+        (
+            Page::from(
+                PlainPage::new("Page1", Text::new("t1"))
+                    .with_theme(theme)
+                    .with_pull_to_refresh("/refresh"),
+            ),
+            Page::from(PlainPage::new("Page1", Text::new("t1"))),
+        ),
+    ] {
+        let original = page.clone();
+        page.downgrade(2);
+        assert_eq!(original, page);
+        page.downgrade(1);
+        assert_eq!(expected, page);
+    }
+}
+
+#[test]
+fn downgrade_update() {
+    let pages = BTreeMap::from([("/".to_string(), Some(Page::default()))]);
+    let mut update = Update {
+        pages: pages.clone(),
+        theme: Some(Theme::empty()),
+        select_tabs: BTreeMap::from([("/".to_string(), "/a".to_string())]),
+        push: Some("/a".to_string()),
+        toasts: vec![Toast::new("Saved")],
+        jobs: vec![JobStatus::new("1")],
+        ..Update::default()
+    };
+    let expected = update.clone();
+    update.downgrade(2);
+    assert_eq!(expected, update);
+    update.downgrade(1);
+    assert_eq!(
+        Update {
+            pages,
+            ..Update::default()
+        },
+        update
+    );
+}
+
+#[test]
+fn downgrade_choices() {
+    let mut widget: Widget = RadioGroup::new("r").with_option("a", "A").into();
+    assert_eq!(2, widget.min_protocol_version());
+    widget.downgrade(1);
+    assert!(matches!(widget, Widget::TextVariant { .. }));
+}
+
+#[test]
+fn downgrade_date_pickers() {
+    let mut page: Page = NavPage::new(
+        "Page1",
+        Form::new((Text::new("t1"), DatePicker::new("d"), TimePicker::new("t"))),
+    )
+    .into();
+    let original = page.clone();
+    page.downgrade(2);
+    assert_eq!(original, page);
+    page.downgrade(1);
+    let expected: Page = NavPage::new(
+        "Page1",
+        Form::new((
+            Text::new("t1"),
+            Text::new(UPDATE_APP_TEXT),
+            Text::new(UPDATE_APP_TEXT),
+        )),
+    )
+    .into();
+    assert_eq!(expected, page);
+}
+
+#[test]
+fn downgrade_layout() {
+    let mut widget: Widget = Column::new((
+        Text::new("a"),
+        Row::new((Text::new("b"), Spacer::new())).with_spacing(4),
+        Stack::new((Text::new("c"), Icon::new("star"))),
+    ))
+    .into();
+    widget.downgrade(1);
+    assert_eq!(
+        Widget::from(Column::new((
+            Text::new("a"),
+            Column::new((Text::new("b"), Widget::EmptyVariant)).with_spacing(4),
+            Column::new((Text::new("c"), Widget::EmptyVariant)),
+        ))),
+        widget
+    );
+}
+
+#[test]
+fn downgrade_form_in_padding() {
+    let mut widget: Widget = Padding::new(
+        8,
+        Frame::new(Form::new((
+            Textfield::new("name"),
+            FormButton::new("Save").with_action(rpc("/save")),
+        )))
+        .with_max_width(300),
+    )
+    .into();
+    widget.downgrade(1);
+    assert_eq!(
+        Widget::from(Form::new((
+            Textfield::new("name"),
+            FormButton::new("Save").with_action(rpc("/save")),
+        ))),
+        widget
+    );
+    assert_eq!(vec!["name"], widget.vars());
+}
+
+#[test]
+fn downgrade_styles() {
+    let mut widget: Widget = Column::new((
+        Text::new("a").with_style(Style::new().with_text_style("title")),
+        Styled::new(
+            Style::new().with_color("accent"),
+            Styled::new(Style::new(), Text::new("b")),
+        ),
+    ))
+    .into();
+    widget.downgrade(1);
+    assert_eq!(
+        Widget::from(Column::new((Text::new("a"), Text::new("b")))),
+        widget
+    );
+}
+
+#[test]
+fn downgrade_sheet_modal() {
+    let mut page: Page = SheetModal::new("Filters", Text::new("a"))
+        .with_detent(SheetDetent::Medium)
+        .into();
+    page.downgrade(1);
+    assert_eq!(Page::from(NavPage::new("Filters", Text::new("a"))), page);
+}
+
+#[test]
+fn downgrade_tab_page() {
+    let mut page: Page = TabPage::new(
+        "Shop",
+        [
+            Tab::new("Orders", &PageKey::new("/orders")).with_icon("cart"),
+            Tab::new("Settings", &PageKey::new("/settings")).with_badge_text("2"),
+        ],
+    )
+    .into();
+    page.downgrade(1);
+    let expected: Page = NavPage::new(
+        "Shop",
+        Column::new((
+            NavButton::new("Orders").with_action(push(&PageKey::new("/orders"))),
+            NavButton::new("Settings")
+                .with_badge_text("2")
+                .with_action(push(&PageKey::new("/settings"))),
+        )),
+    )
+    .into();
+    assert_eq!(expected, page);
+}
+
+#[test]
+fn downgrade_progress_bar() {
+    for (progress_bar, text) in [
+        (ProgressBar::new().with_percent(30), "30%"),
+        (
+            ProgressBar::new().with_percent(30).with_text("Saving"),
+            "Saving 30%",
+        ),
+        (ProgressBar::new().with_text("Saving"), "Saving"),
+    ] {
+        let mut widget: Widget = progress_bar.into();
+        widget.downgrade(1);
+        assert_eq!(Widget::from(Text::new(text)), widget);
+    }
+}
+
+#[test]
+fn downgrade_rich_text() {
+    let mut widget: Widget = RichText::new(("a", Span::new("b").with_bold())).into();
+    widget.downgrade(1);
+    assert_eq!(Widget::from(Text::new(UPDATE_APP_TEXT)), widget);
+}
+
+#[test]
+fn downgrade_lazy_list() {
+    // Old clients get the first chunk in a column.
+    let mut widget: Widget = LazyList::new(
+        "/more",
+        Chunk::new((Text::new("a"), Text::new("b"))).with_next_cursor("2"),
+    )
+    .with_spacing(4)
+    .into();
+    widget.downgrade(1);
+    assert_eq!(
+        Widget::from(Column::new((Text::new("a"), Text::new("b"))).with_spacing(4)),
+        widget
+    );
+}
+
+#[test]
+fn stream_protocol_version_from_query() {
+    let sessions: SessionSet<()> = SessionSet::without_executor();
+    let page: Page = SheetModal::new("Filters", Text::new("a")).into();
+    let page2 = page.clone();
+    let session = sessions.new_session(
+        move |_| Ok(PageMap::new().with_static_page("/", page2.clone())),
+        (),
+    );
+    let harness = Harness::new(Arc::clone(&session)).with_client_info(ClientInfo::default());
+    // Browsers cannot send headers with `EventSource` requests.
+    let mut req = harness.request("GET", "/stream?applin-protocol-version=2", &Value::Null);
+    req.headers = HeaderList::new();
+    let response = sessions.get(&req).ok().unwrap().stream().ok().unwrap();
+    assert_eq!(2, session.client_info().protocol_version);
+    session.send_pending_updates().unwrap();
+    let ResponseBody::EventStream(receiver) = response.body else {
+        panic!("expected an event stream");
+    };
+    let Ok(Event::Message(data)) = receiver.try_recv() else {
+        panic!("expected a message");
+    };
+    let update: Update = serde_json::from_str(&data).unwrap();
+    assert_eq!(Some(&Some(page)), update.pages.get("/"));
+}
+
+#[test]
+fn request_without_versions_keeps_client_info() {
+    let sessions: SessionSet<()> = SessionSet::without_executor();
+    let session = sessions.new_session(|_| Ok(PageMap::new()), ());
+    let harness = Harness::new(Arc::clone(&session));
+    let mut req = harness.request("GET", "/photo.jpg", &Value::Null);
+    req.headers = HeaderList::new();
+    sessions.get(&req).ok().unwrap();
+    assert_eq!(PROTOCOL_VERSION, session.client_info().protocol_version);
+    let req = harness
+        .with_client_info(ClientInfo::default())
+        .request("GET", "/", &Value::Null);
+    session.set_client_info(ClientInfo::parse(Some("2"), None).unwrap());
+    sessions.get(&req).ok().unwrap();
+    assert_eq!(1, session.client_info().protocol_version);
+}
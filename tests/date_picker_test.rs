@@ -0,0 +1,90 @@
+use applin::widget::{Date, DateRange, Time};
+use serde::Deserialize;
+use serde_json::json;
+
+#[test]
+fn date() {
+    assert_eq!(Date::new(2024, 2, 29), "2024-02-29".parse().unwrap());
+    assert_eq!("2024-02-29", Date::new(2024, 2, 29).to_string());
+    assert_eq!("0001-01-01", Date::new(1, 1, 1).to_string());
+    assert_eq!(
+        "invalid date: 2023-02-29",
+        "2023-02-29".parse::<Date>().unwrap_err()
+    );
+    Date::try_new(2000, 2, 29).unwrap();
+    Date::try_new(1900, 2, 29).unwrap_err();
+    Date::try_new(2024, 4, 31).unwrap_err();
+    Date::try_new(2024, 13, 1).unwrap_err();
+    Date::try_new(2024, 1, 0).unwrap_err();
+    Date::try_new(0, 1, 1).unwrap_err();
+    for s in [
+        "",
+        "2024-1-01",
+        "2024-01-01T00:00",
+        "24-01-01",
+        "2024/01/01",
+        "+024-01-01",
+    ] {
+        assert_eq!(
+            format!("expected a date like 2024-12-31, got {s:?}"),
+            s.parse::<Date>().unwrap_err()
+        );
+    }
+    assert!(Date::new(2023, 12, 31) < Date::new(2024, 1, 1));
+}
+
+#[test]
+fn time() {
+    assert_eq!(Time::new(9, 5), "09:05".parse().unwrap());
+    assert_eq!("23:59", Time::new(23, 59).to_string());
+    assert_eq!("invalid time: 24:00", "24:00".parse::<Time>().unwrap_err());
+    "12:60".parse::<Time>().unwrap_err();
+    "9:05".parse::<Time>().unwrap_err();
+    "09:05:00".parse::<Time>().unwrap_err();
+    assert!(Time::new(9, 59) < Time::new(10, 0));
+}
+
+#[test]
+fn date_range() {
+    let range = DateRange::new(Date::new(2024, 12, 24), Date::new(2024, 12, 31));
+    assert_eq!(range, "2024-12-24/2024-12-31".parse().unwrap());
+    assert_eq!("2024-12-24/2024-12-31", range.to_string());
+    assert!(range.contains(Date::new(2024, 12, 24)));
+    assert!(range.contains(Date::new(2024, 12, 31)));
+    assert!(!range.contains(Date::new(2025, 1, 1)));
+    assert_eq!(
+        "date range ends before it starts: 2024-12-31/2024-12-24",
+        "2024-12-31/2024-12-24".parse::<DateRange>().unwrap_err()
+    );
+    "2024-12-24".parse::<DateRange>().unwrap_err();
+}
+
+#[test]
+fn rpc_vars() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Vars {
+        birthday: Option<Date>,
+        alarm: Time,
+        trip: DateRange,
+    }
+    assert_eq!(
+        Vars {
+            birthday: None,
+            alarm: Time::new(7, 30),
+            trip: DateRange::new(Date::new(2024, 7, 1), Date::new(2024, 7, 4)),
+        },
+        serde_json::from_value(json!({
+            "birthday": null,
+            "alarm": "07:30",
+            "trip": "2024-07-01/2024-07-04",
+        }))
+        .unwrap()
+    );
+    let err = serde_json::from_value::<Vars>(json!({
+        "birthday": "2024-02-30",
+        "alarm": "07:30",
+        "trip": "2024-07-01/2024-07-04",
+    }))
+    .unwrap_err();
+    assert_eq!("invalid date: 2024-02-30", err.to_string());
+}
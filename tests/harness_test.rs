@@ -0,0 +1,121 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::rpc;
+use applin::data::{Context, Roster};
+use applin::internal::{Page, Update};
+use applin::session::{ClientInfo, PageMap, SessionCookie, SessionSet};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{Button, Column, NavPage, Text};
+use serde_json::json;
+use servlin::{Request, Response};
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+use std::sync::Arc;
+
+struct ServerState {
+    counter: Roster<u32, ()>,
+    sessions: SessionSet<()>,
+}
+
+fn counter_page(count: u32) -> Page {
+    NavPage::new(
+        "t1",
+        Column::new((
+            Text::new(format!("count: {count}")),
+            Button::new("Increment").with_action(rpc("/increment")),
+        )),
+    )
+    .into()
+}
+
+fn counter_update(count: u32) -> Update {
+    Update {
+        pages: BTreeMap::from([("/".to_string(), Some(counter_page(count)))]),
+        ..Update::default()
+    }
+}
+
+fn start() -> (Arc<ServerState>, Harness<()>) {
+    let state = Arc::new(ServerState {
+        counter: Roster::new(3),
+        sessions: SessionSet::without_executor(),
+    });
+    let state2 = Arc::clone(&state);
+    let page_map_fn = move |_| {
+        let state3 = Arc::clone(&state2);
+        Ok(PageMap::new().with_page_fn("/", move |rebuilder| {
+            Ok(counter_page(*state3.counter.read(rebuilder)))
+        }))
+    };
+    let session = state.sessions.new_session(page_map_fn, ());
+    let state4 = Arc::clone(&state);
+    let harness = Harness::new(session).with_handler(move |req: Request| {
+        match (req.method.as_str(), req.url.path()) {
+            ("POST", "/increment") => {
+                let session = state4.sessions.get(&req)?;
+                state4.counter.write(session.rpc_context()).add_assign(1);
+                session.rpc_response()
+            }
+            _ => Ok(Response::not_found_404()),
+        }
+    });
+    (state, harness)
+}
+
+#[test]
+fn poll_and_rpc() {
+    let (state, harness) = start();
+    assert_eq!(counter_update(3), harness.poll().unwrap());
+    assert_eq!(Update::default(), harness.poll().unwrap());
+    // Background thread updates state.
+    *state.counter.write(Context::Empty) = 5;
+    assert_eq!(counter_update(5), harness.poll().unwrap());
+    assert_eq!(Update::default(), harness.poll().unwrap());
+    // RPC updates state.
+    assert_eq!(
+        counter_update(6),
+        harness.rpc("/increment", &json!({})).unwrap()
+    );
+    assert_eq!(Update::default(), harness.poll().unwrap());
+    assert_eq!(
+        "404 not found",
+        harness
+            .rpc("/nonexistent", &json!({}))
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn stream() {
+    let (state, harness) = start();
+    harness.stream().unwrap();
+    assert_eq!(vec![counter_update(3)], harness.events().unwrap());
+    assert_eq!(Vec::<Update>::new(), harness.events().unwrap());
+    *state.counter.write(Context::Empty) = 5;
+    *state.counter.write(Context::Empty) = 6;
+    assert_eq!(vec![counter_update(6)], harness.events().unwrap());
+}
+
+#[test]
+fn app_client() {
+    let (_state, harness) = start();
+    let mut client = AppClient::new(harness).unwrap();
+    client.assert_text("count: 3");
+    client.tap("Increment").unwrap();
+    client.assert_text("count: 4");
+}
+
+#[test]
+fn client_info() {
+    let (_state, harness) = start();
+    let harness = harness.with_client_info(ClientInfo::parse(Some("1"), Some("1.2")).unwrap());
+    let req = harness.request("POST", "/increment", &json!({}));
+    assert_eq!(
+        ClientInfo::parse(Some("1"), Some("1.2")).unwrap(),
+        ClientInfo::from_req(&req).ok().unwrap()
+    );
+    assert_eq!(
+        harness.session().cookie,
+        SessionCookie::from_req(&req).ok().unwrap()
+    );
+}
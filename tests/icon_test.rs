@@ -0,0 +1,44 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::session::{PageMap, SessionSet};
+use applin::style::{check_icon_name, ICON_NAMES};
+use applin::testing::{Harness, Snapshots};
+use applin::widget::{Button, NavPage};
+use std::sync::Arc;
+use temp_dir::TempDir;
+
+#[test]
+fn icon_names() {
+    let mut sorted = ICON_NAMES.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted, ICON_NAMES);
+    for name in ICON_NAMES {
+        check_icon_name(name).unwrap();
+    }
+    assert_eq!(
+        Err("unknown icon \"stra\", expected a name from icon set version 1".to_string()),
+        check_icon_name("stra")
+    );
+}
+
+#[test]
+fn misspelled_icon_fails() {
+    let page = NavPage::new("Home", Button::new("Save").with_icon("chek"));
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(
+        move |_| Ok(PageMap::new().with_static_page("/", page.clone())),
+        (),
+    );
+    let expected =
+        "page \"/\" \"/widget\": unknown icon \"chek\", expected a name from icon set version 1";
+    assert_eq!(
+        expected,
+        Harness::new(Arc::clone(&session)).poll().unwrap_err()
+    );
+    let temp_dir = TempDir::new().unwrap();
+    let err = Snapshots::new(temp_dir.path())
+        .with_update(true)
+        .check_session("app", &session)
+        .unwrap_err();
+    assert!(err.contains(expected), "{err}");
+}
@@ -0,0 +1,247 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::image_store::{ImageId, ImageStore};
+use applin::internal::Widget;
+use applin::session::{PageMap, SessionSet};
+use applin::testing::Harness;
+use applin::widget::{Image, NavButton};
+use serde_json::Value;
+use servlin::{AsciiString, Request, Response, ResponseBody};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use temp_dir::TempDir;
+
+const SHA_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+fn harness() -> Harness<()> {
+    let sessions: SessionSet<()> = SessionSet::without_executor();
+    Harness::new(sessions.new_session(|_| Ok(PageMap::new()), ()))
+}
+
+fn get(store: &ImageStore, path: &str, if_none_match: Option<&str>) -> Result<Response, Response> {
+    let mut req: Request = harness().request("GET", path, &Value::Null);
+    if let Some(etag) = if_none_match {
+        req.headers
+            .add("if-none-match", AsciiString::try_from(etag).unwrap());
+    }
+    store.handle(&req)
+}
+
+fn body(response: Response) -> Vec<u8> {
+    assert_eq!(200, response.code);
+    match response.body {
+        ResponseBody::Vec(bytes) => bytes,
+        _ => panic!("unexpected body type"),
+    }
+}
+
+#[test]
+fn image_id() {
+    let id = ImageId::new(SHA_ABC, "image/PNG; charset=binary").unwrap();
+    assert_eq!(format!("{SHA_ABC}.png"), id.to_string());
+    assert_eq!(Ok(id.clone()), id.to_string().parse());
+    assert_eq!("image/png", id.mime_type());
+    assert_eq!("jpg", ImageId::new(SHA_ABC, "image/jpeg").unwrap().ext());
+    assert!(ImageId::new(SHA_ABC, "image/svg+xml").is_err());
+    assert!(ImageId::new("abc", "image/png").is_err());
+    for s in [
+        "",
+        "abc.png",
+        SHA_ABC,
+        &format!("{SHA_ABC}.svg"),
+        &format!("{SHA_ABC}.png.png"),
+        &format!("{}.png", SHA_ABC.to_uppercase()),
+        &format!("../{}.png", &SHA_ABC[3..]),
+    ] {
+        assert!(s.parse::<ImageId>().is_err(), "{s:?}");
+    }
+}
+
+#[test]
+fn save_and_serve() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ImageStore::new(temp_dir.child("images"), "/images")
+        .with_max_bytes(3)
+        .with_thumbnail_sizes([(10, 20)]);
+    let id = store.save_bytes("image/png", b"abc").ok().unwrap();
+    assert_eq!(SHA_ABC, id.sha256());
+    assert!(store.contains(&id));
+    assert_eq!(b"abc".to_vec(), std::fs::read(store.path(&id)).unwrap());
+    // Saving the same bytes again returns the same id.
+    assert_eq!(id, store.save_bytes("image/png", b"abc").ok().unwrap());
+    assert_eq!(format!("/images/{SHA_ABC}.png"), store.url(&id));
+    assert_eq!(
+        format!("/images/10x20/{SHA_ABC}.png"),
+        store.thumbnail_url(&id, 10, 20)
+    );
+    // Limits
+    assert_eq!(
+        415,
+        store.save_bytes("text/plain", b"abc").err().unwrap().code
+    );
+    assert_eq!(
+        413,
+        store.save_bytes("image/png", b"abcd").err().unwrap().code
+    );
+    // Serving
+    assert_eq!(
+        b"abc".to_vec(),
+        body(get(&store, &store.url(&id), None).ok().unwrap())
+    );
+    assert_eq!(
+        b"abc".to_vec(),
+        body(
+            get(&store, &store.thumbnail_url(&id, 10, 20), None)
+                .ok()
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        304,
+        get(&store, &store.url(&id), Some(&format!("\"{SHA_ABC}\"")))
+            .ok()
+            .unwrap()
+            .code
+    );
+    assert_eq!(
+        304,
+        get(
+            &store,
+            &store.thumbnail_url(&id, 10, 20),
+            Some(&format!("\"x\", W/\"{SHA_ABC}-10x20\""))
+        )
+        .ok()
+        .unwrap()
+        .code
+    );
+    assert_eq!(
+        200,
+        get(&store, &store.url(&id), Some("\"other\""))
+            .ok()
+            .unwrap()
+            .code
+    );
+    let other = format!("/images/{}.png", "0".repeat(64));
+    for path in [
+        "/images",
+        "/images/",
+        "/other/x.png",
+        &other,
+        &format!("/images/{SHA_ABC}.jpg"),
+        &format!("/images/0x20/{SHA_ABC}.png"),
+        &format!("/images/010x20/{SHA_ABC}.png"),
+        &format!("/images/4097x20/{SHA_ABC}.png"),
+        &format!("/images/20x10/{SHA_ABC}.png"),
+        &format!("/images/10x20/x/{SHA_ABC}.png"),
+    ] {
+        assert_eq!(404, get(&store, path, None).err().unwrap().code, "{path:?}");
+    }
+    // Delete
+    store.delete(&id).unwrap();
+    assert!(!store.contains(&id));
+    assert_eq!(404, get(&store, &store.url(&id), None).err().unwrap().code);
+    store.delete(&id).unwrap();
+}
+
+#[test]
+fn thumbnails() {
+    let temp_dir = TempDir::new().unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = Arc::clone(&calls);
+    let store = ImageStore::new(temp_dir.path(), "/images")
+        .with_resizer(move |bytes, mime_type, width, height| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(b"abc", bytes);
+            assert_eq!("image/jpeg", mime_type);
+            Ok(format!("{width}x{height}").into_bytes())
+        })
+        .with_thumbnail_sizes([(30, 40), (1, 1)]);
+    let id = store.save_bytes("image/jpeg", b"abc").ok().unwrap();
+    let url = store.thumbnail_url(&id, 30, 40);
+    assert_eq!(
+        b"30x40".to_vec(),
+        body(get(&store, &url, None).ok().unwrap())
+    );
+    assert_eq!(
+        b"30x40".to_vec(),
+        body(get(&store, &url, None).ok().unwrap())
+    );
+    assert_eq!(1, calls.load(Ordering::SeqCst));
+    assert_eq!(
+        b"200x200".to_vec(),
+        body(
+            get(&store, &format!("/images/200x200/{id}"), None)
+                .ok()
+                .unwrap()
+        )
+    );
+    assert_eq!(2, calls.load(Ordering::SeqCst));
+    // Other sizes
+    assert_eq!(
+        404,
+        get(&store, &format!("/images/31x40/{id}"), None)
+            .err()
+            .unwrap()
+            .code
+    );
+    assert_eq!(404, store.thumbnail(&id, 4096, 4096).err().unwrap().code);
+    assert_eq!(2, calls.load(Ordering::SeqCst));
+    assert!(!temp_dir
+        .path()
+        .join("thumbs")
+        .join(format!("31x40-{id}"))
+        .exists());
+    assert_eq!(
+        b"abc".to_vec(),
+        body(get(&store, &store.url(&id), None).ok().unwrap())
+    );
+    // Delete removes thumbnails.
+    store.delete(&id).unwrap();
+    store.save_bytes("image/jpeg", b"abc").ok().unwrap();
+    assert_eq!(
+        b"30x40".to_vec(),
+        body(get(&store, &url, None).ok().unwrap())
+    );
+    assert_eq!(3, calls.load(Ordering::SeqCst));
+    // Resizer errors
+    let store = store.with_resizer(|_, _, _, _| Err("bad image".to_string()));
+    assert_eq!(
+        500,
+        get(&store, &store.thumbnail_url(&id, 1, 1), None)
+            .err()
+            .unwrap()
+            .code
+    );
+}
+
+#[test]
+#[should_panic(expected = "thumbnail size 30x40 is not in the store's thumbnail sizes")]
+fn thumbnail_url_unknown_size() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ImageStore::new(temp_dir.path(), "/images");
+    let id = store.save_bytes("image/png", b"abc").ok().unwrap();
+    let _ = store.thumbnail_url(&id, 30, 40);
+}
+
+#[test]
+#[should_panic(expected = "thumbnail size must be from 1 to 4096")]
+fn thumbnail_sizes_too_large() {
+    let _ = ImageStore::new("/tmp/images", "/images").with_thumbnail_sizes([(4097, 1)]);
+}
+
+#[test]
+fn widgets() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ImageStore::new(temp_dir.path(), "/i");
+    let id = ImageId::new(SHA_ABC, "image/gif").unwrap();
+    assert_eq!(
+        Image::new(2.0, format!("/i/{SHA_ABC}.gif")).to_widget(),
+        Image::from_store(2.0, &store, &id).to_widget()
+    );
+    let Widget::NavButtonVariant { photo_url, .. } = NavButton::new("a")
+        .with_stored_photo(&store, &id)
+        .to_widget()
+    else {
+        panic!("expected nav-button");
+    };
+    assert_eq!(Some(format!("/i/200x200/{SHA_ABC}.gif")), photo_url);
+}
@@ -0,0 +1,183 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{push, rpc_with_progress};
+use applin::data::{Context, Rebuilder, Roster};
+use applin::internal::{JobStatus, Toast, Update};
+use applin::session::{ApplinSession, Job, PageMap, PendingNotifications};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{Button, Column, NavPage, Text};
+use serde_json::{json, Value};
+use servlin::{Request, Response, ResponseBody};
+use std::error::Error;
+use std::sync::Arc;
+
+struct Session {
+    paid: Roster<bool, Session>,
+}
+
+impl Session {
+    fn new() -> Self {
+        Self {
+            paid: Roster::new(false),
+        }
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn page_map(_rebuilder: Rebuilder<Session>) -> Result<PageMap<Session>, Box<dyn Error>> {
+    let mut keys = PageMap::new();
+    let receipt = keys.add_static_page("/receipt", NavPage::new("Receipt", Text::new("Thanks")));
+    keys.add_page_fn("/", move |rebuilder: Rebuilder<Session>| {
+        let session = rebuilder.session()?;
+        let paid = *session.value().paid.read(rebuilder);
+        Ok(NavPage::new(
+            "Cart",
+            Column::new((
+                Text::new(if paid { "paid" } else { "unpaid" }),
+                Button::new("Pay").with_actions([rpc_with_progress("/pay"), push(&receipt)]),
+                Button::new("Pay Broken")
+                    .with_actions([rpc_with_progress("/pay-broken"), push(&receipt)]),
+                Button::new("Pay No Job").with_action(rpc_with_progress("/no-job")),
+            )),
+        ))
+    });
+    Ok(keys)
+}
+
+fn handle(session: &Arc<ApplinSession<Session>>, req: &Request) -> Result<Response, Response> {
+    match req.url.path() {
+        "/pay" => session.start_job(|job| {
+            job.set_progress(50, "Charging card");
+            *job.session().value().paid.write(job.context()) = true;
+            job.set_var("receipt-id", "r1");
+            Ok(())
+        }),
+        "/pay-broken" => session.start_job(|job| {
+            job.set_progress(10, "Charging card");
+            Err("card declined".to_string())
+        }),
+        "/pay-panic" => session.start_job(|_job| panic!("card reader missing")),
+        "/no-job" => session.rpc_response(),
+        _ => Ok(Response::not_found_404()),
+    }
+}
+
+#[test]
+fn job() {
+    let harness = Harness::with_pages(page_map, Session::new()).with_session_handler(handle);
+    let mut client = AppClient::new(harness).unwrap();
+    client.assert_text("unpaid");
+    client.tap("Pay").unwrap();
+    assert_eq!(
+        [JobStatus::new("1")
+            .with_percent(100)
+            .with_text("")
+            .with_done()],
+        client.job_statuses()
+    );
+    assert_eq!(Some(Value::from("r1")), client.var("receipt-id"));
+    client.assert_page("/receipt");
+    client.tap_back().unwrap();
+    client.assert_text("paid");
+}
+
+#[test]
+fn job_error() {
+    let harness = Harness::with_pages(page_map, Session::new()).with_session_handler(handle);
+    let mut client = AppClient::new(harness).unwrap();
+    assert_eq!(
+        "job \"1\" failed: card declined",
+        client.tap("Pay Broken").unwrap_err()
+    );
+    client.assert_page("/");
+    assert_eq!(
+        "rpc \"/no-job\" started no job",
+        client.tap("Pay No Job").unwrap_err()
+    );
+}
+
+#[test]
+fn job_response() {
+    let harness = Harness::with_pages(page_map, Session::new()).with_session_handler(handle);
+    harness.stream().unwrap();
+    harness.events().unwrap();
+    let session = Arc::clone(harness.session());
+    let update = harness.rpc("/pay", &json!({})).unwrap();
+    assert_eq!(Some("1".to_string()), update.started_job);
+    assert_eq!(
+        vec![JobStatus::new("1").with_percent(100).with_done()],
+        update.jobs
+    );
+    assert_eq!(
+        Some(json!({"receipt-id": "r1"})),
+        update.vars.map(Value::Object)
+    );
+    assert!(update.pages.contains_key("/"));
+    assert_eq!(
+        vec![JobStatus::new("2")
+            .with_percent(100)
+            .with_error("card declined")],
+        harness.rpc("/pay-broken", &json!({})).unwrap().jobs
+    );
+    assert!(session
+        .lock_extensions()
+        .get::<PendingNotifications>()
+        .is_none_or(|notifications| notifications.jobs.is_empty()));
+}
+
+#[test]
+fn job_panic() {
+    let harness = Harness::with_pages(page_map, Session::new()).with_session_handler(handle);
+    let update = harness.rpc("/pay-panic", &json!({})).unwrap();
+    assert_eq!(Some("1".to_string()), update.started_job);
+    assert_eq!(
+        vec![JobStatus::new("1")
+            .with_percent(100)
+            .with_error("Server error")],
+        update.jobs
+    );
+}
+
+#[test]
+fn job_status_serialize() {
+    let status = JobStatus::new("1").with_percent(100).with_error("declined");
+    assert_eq!(
+        json!({"id": "1", "percent": 100, "done": true, "error": "declined"}),
+        status.to_value()
+    );
+    assert_eq!(status, serde_json::from_value(status.to_value()).unwrap());
+}
+
+#[test]
+fn background_job_sends_only_its_status() {
+    let harness = Harness::with_pages(page_map, Session::new());
+    harness.stream().unwrap();
+    harness.events().unwrap();
+    let session = Arc::clone(harness.session());
+    // An RPC is running and has queued a toast for its response.
+    session.show_toast(Toast::new("Saved"), session.rpc_context());
+    let job = Job::new(Arc::clone(&session), "7", true);
+    assert!(Context::Empty == job.context());
+    job.set_var("receipt-id", "r1");
+    job.set_progress(50, "Charging card");
+    let response = session.rpc_response().ok().unwrap();
+    let ResponseBody::Vec(bytes) = response.body else {
+        panic!("expected a body");
+    };
+    let update: Update = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(vec![Toast::new("Saved")], update.toasts);
+    assert!(update.jobs.is_empty());
+    assert!(update.vars.is_none());
+    let updates = harness.events().unwrap();
+    assert_eq!(1, updates.len());
+    assert_eq!(
+        vec![JobStatus::new("7")
+            .with_percent(50)
+            .with_text("Charging card")],
+        updates[0].jobs
+    );
+    assert_eq!(
+        Some(json!({"receipt-id": "r1"})),
+        updates[0].vars.clone().map(Value::Object)
+    );
+    assert!(updates[0].toasts.is_empty());
+}
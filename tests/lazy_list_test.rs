@@ -0,0 +1,78 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::data::Context;
+use applin::internal::{Chunk, Widget};
+use applin::lazy_list::LazyListLoader;
+use applin::session::PageMap;
+use applin::testing::{AppClient, Connection, Harness};
+use applin::widget::{NavPage, Scroll, Text};
+use serde_json::json;
+
+const NUM_ORDERS: usize = 25;
+
+fn orders(start: usize) -> Chunk {
+    let end = (start + 10).min(NUM_ORDERS);
+    let chunk = Chunk::new(
+        (start..end)
+            .map(|n| Text::new(format!("Order {n}")).into())
+            .collect::<Vec<Widget>>(),
+    );
+    if end < NUM_ORDERS {
+        chunk.with_next_cursor(end.to_string())
+    } else {
+        chunk
+    }
+}
+
+fn loader() -> LazyListLoader<()> {
+    LazyListLoader::new("/orders/more", |_session, cursor| {
+        Ok(orders(cursor.parse()?))
+    })
+}
+
+#[test]
+fn load_more() {
+    let harness = Harness::with_pages(
+        |_| {
+            Ok(PageMap::new().with_static_page(
+                "/",
+                NavPage::new("Orders", Scroll::new(loader().list(orders(0)))),
+            ))
+        },
+        (),
+    )
+    .with_session_handler(|session, req| loader().handle(session, req));
+    let mut client = AppClient::new(harness).unwrap();
+    client.assert_text("Order 9");
+    client.assert_no_text("Order 10");
+    assert!(client.load_more().unwrap());
+    client.assert_text("Order 0");
+    client.assert_text("Order 19");
+    client.assert_no_text("Order 20");
+    assert!(client.load_more().unwrap());
+    client.assert_text("Order 24");
+    assert!(!client.load_more().unwrap());
+    // Rebuilding the page starts the list over.
+    client.poll().unwrap();
+    client.assert_text("Order 24");
+    client
+        .connection()
+        .session()
+        .rebuild_value("/", Context::Empty);
+    client.poll().unwrap();
+    client.assert_no_text("Order 24");
+}
+
+#[test]
+fn handle() {
+    let harness = Harness::with_pages(|_| Ok(PageMap::new()), ())
+        .with_session_handler(|session, req| loader().handle(session, req));
+    assert_eq!(Ok(orders(20)), harness.load_more("/orders/more", "20"));
+    assert_eq!(
+        Err("500 error loading \"/orders/more\": invalid digit found in string".to_string()),
+        harness.load_more("/orders/more", "x")
+    );
+    let response = harness
+        .call("POST", "/orders/more", &json!({"other": 1}))
+        .unwrap_or_else(|r| r);
+    assert_eq!(400, response.code);
+}
@@ -0,0 +1,111 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::session::{PageMap, SessionSet};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{Form, NavPage, NumberField, NumberRange, Real32, Slider, Stepper};
+use serde_json::{json, Value};
+
+#[test]
+fn number_range() {
+    let range = NumberRange::new()
+        .with_min(1.0)
+        .with_max(12.0)
+        .with_integer();
+    assert_eq!(Real32::new(4.0), range.check(4.0).unwrap());
+    assert_eq!(Real32::new(1.0), range.check(1.0).unwrap());
+    assert_eq!(Real32::new(12.0), range.check(12.0).unwrap());
+    assert_eq!("number must be at least 1", range.check(0.0).unwrap_err());
+    assert_eq!(
+        "guests must be at most 12",
+        range.check_var("guests", 13.0).unwrap_err()
+    );
+    assert_eq!(
+        "number must be a whole number",
+        range.check(1.5).unwrap_err()
+    );
+    assert_eq!(
+        "number must be a number",
+        range.check(f32::NAN).unwrap_err()
+    );
+    let range = NumberRange::new();
+    assert_eq!(Real32::new(-1.5), range.check(-1.5).unwrap());
+    range.check(f32::INFINITY).unwrap_err();
+}
+
+#[test]
+fn number_range_step() {
+    let range = NumberRange::new()
+        .with_min(1.0)
+        .with_max(3.0)
+        .with_step(0.5);
+    assert_eq!(Real32::new(1.0), range.check(1.0).unwrap());
+    assert_eq!(Real32::new(2.5), range.check(2.5).unwrap());
+    assert_eq!(
+        "number must be 1 plus a multiple of 0.5",
+        range.check(1.25).unwrap_err()
+    );
+    let range = NumberRange::new().with_step(0.1);
+    assert_eq!(Real32::new(0.3), range.check(0.3).unwrap());
+    assert_eq!(Real32::new(-0.7), range.check(-0.7).unwrap());
+    assert_eq!(
+        "number must be a multiple of 0.1",
+        range.check(0.35).unwrap_err()
+    );
+}
+
+#[test]
+#[should_panic(expected = "step must be positive")]
+fn number_range_step_must_be_positive() {
+    let _ = NumberRange::new().with_step(0.0);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed")]
+fn slider_requires_bounds() {
+    let _ = Slider::new("s", NumberRange::new().with_min(0.0));
+}
+
+#[test]
+fn app_client() {
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| {
+            Ok(PageMap::new().with_static_page(
+                "/",
+                NavPage::new(
+                    "Order",
+                    Form::new((
+                        Stepper::new(
+                            "guests",
+                            NumberRange::new()
+                                .with_min(1.0)
+                                .with_max(12.0)
+                                .with_integer(),
+                        )
+                        .with_label("Guests"),
+                        Slider::new("tip", NumberRange::new().with_min(0.0).with_max(30.0))
+                            .with_initial(15.0),
+                        NumberField::new("budget", NumberRange::new().with_min(0.0)),
+                    )),
+                ),
+            ))
+        },
+        (),
+    );
+    let mut client = AppClient::new(Harness::new(session)).unwrap();
+    client.assert_text("Guests");
+    assert_eq!(Some(json!(1)), client.var("guests"));
+    assert_eq!(Some(json!(15.0)), client.var("tip"));
+    assert_eq!(Some(Value::Null), client.var("budget"));
+    client.set_number("guests", 4.0).unwrap();
+    assert_eq!(Some(json!(4)), client.var("guests"));
+    assert_eq!(
+        "guests must be a whole number",
+        client.set_number("guests", 4.5).unwrap_err()
+    );
+    assert_eq!(
+        "tip must be at most 30",
+        client.set_number("tip", 31.0).unwrap_err()
+    );
+    client.set_number("budget", 99.5).unwrap();
+    assert_eq!(Some(json!(99.5)), client.var("budget"));
+}
@@ -1,4 +1,6 @@
-use applin::internal::{Page, Widget};
+use applin::internal::{Page, Tab, Widget};
+use applin::session::PageKey;
+use applin::widget::{Empty, NavPage, PlainPage, SheetDetent, SheetModal, TabPage, Text};
 use serde_json::{json, Value};
 
 #[test]
@@ -6,7 +8,9 @@ fn page_to_value() {
     assert_eq!(
         Page::Plain {
             poll_seconds: 0,
+            pull_to_refresh: None,
             stream: false,
+            theme: None,
             title: "T1".to_string(),
             widget: Widget::EmptyVariant
         }
@@ -19,7 +23,9 @@ fn page_to_value() {
 fn value_from_page() {
     let value: Value = Page::Plain {
         poll_seconds: 0,
+        pull_to_refresh: None,
         stream: false,
+        theme: None,
         title: "T1".to_string(),
         widget: Widget::EmptyVariant,
     }
@@ -44,8 +50,10 @@ fn page_nav_serialize() {
         serde_json::to_string(&Page::Nav {
             end: None,
             poll_seconds: 0,
+            pull_to_refresh: None,
             start: None,
             stream: false,
+            theme: None,
             title: String::new(),
             widget: Widget::EmptyVariant,
         })
@@ -58,10 +66,12 @@ fn page_nav_serialize() {
                 text: "e1".to_string()
             }),
             poll_seconds: 0,
+            pull_to_refresh: None,
             start: Some(Widget::TextVariant {
                 text: "s1".to_string()
             }),
             stream: false,
+            theme: None,
             title: "T1".to_string(),
             widget: Widget::TextVariant {
                 text: "w1".to_string()
@@ -84,8 +94,10 @@ fn page_nav_deserialize() {
         Page::Nav {
             end: None,
             poll_seconds: 0,
+            pull_to_refresh: None,
             start: None,
             stream: false,
+            theme: None,
             title: String::new(),
             widget: Widget::EmptyVariant,
         }
@@ -99,10 +111,12 @@ fn page_nav_deserialize() {
                 text: "e1".to_string()
             }),
             poll_seconds: 0,
+            pull_to_refresh: None,
             start: Some(Widget::TextVariant {
                 text: "s1".to_string()
             }),
             stream: false,
+            theme: None,
             title: "T1".to_string(),
             widget: Widget::TextVariant {
                 text: "w1".to_string()
@@ -116,7 +130,9 @@ fn page_plain_serialize() {
     assert_eq!(
         serde_json::to_string(&Page::Plain {
             poll_seconds: 0,
+            pull_to_refresh: None,
             stream: false,
+            theme: None,
             title: String::new(),
             widget: Widget::EmptyVariant,
         })
@@ -126,7 +142,9 @@ fn page_plain_serialize() {
     assert_eq!(
         serde_json::to_string(&Page::Plain {
             poll_seconds: 0,
+            pull_to_refresh: None,
             stream: false,
+            theme: None,
             title: "T1".to_string(),
             widget: Widget::TextVariant {
                 text: "w1".to_string()
@@ -148,7 +166,9 @@ fn page_plain_deserialize() {
             .unwrap(),
         Page::Plain {
             poll_seconds: 0,
+            pull_to_refresh: None,
             stream: false,
+            theme: None,
             title: String::new(),
             widget: Widget::EmptyVariant,
         }
@@ -160,7 +180,9 @@ fn page_plain_deserialize() {
         .unwrap(),
         Page::Plain {
             poll_seconds: 0,
+            pull_to_refresh: None,
             stream: false,
+            theme: None,
             title: "T1".to_string(),
             widget: Widget::TextVariant {
                 text: "w1".to_string()
@@ -168,3 +190,79 @@ fn page_plain_deserialize() {
         }
     );
 }
+
+#[test]
+fn page_sheet_modal_serialize() {
+    let page: Page = SheetModal::new("Filters", Text::new("a"))
+        .with_detent(SheetDetent::Medium)
+        .with_detent(SheetDetent::Height(300))
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "sheet-modal",
+            "title": "Filters",
+            "detents": ["medium", {"height": 300}],
+            "widget": {"typ": "text", "text": "a"},
+        }),
+        page.to_value()
+    );
+    assert_eq!(page, serde_json::from_value(page.to_value()).unwrap());
+    assert_eq!(
+        json!({"typ": "sheet-modal", "title": "t", "widget": {"typ": "empty"}}),
+        Page::from(SheetModal::new("t", Empty::new())).to_value()
+    );
+}
+
+#[test]
+fn page_tab_serialize() {
+    let page: Page = TabPage::new("Shop", [Tab::new("Orders", &PageKey::new("/orders"))])
+        .with_tab(
+            Tab::new("Settings", &PageKey::new("/settings"))
+                .with_icon("gear")
+                .with_badge_text("2"),
+        )
+        .with_selected(&PageKey::new("/settings"))
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "tab-page",
+            "selected": "/settings",
+            "tabs": [
+                {"page-key": "/orders", "title": "Orders"},
+                {"badge-text": "2", "icon": "gear", "page-key": "/settings", "title": "Settings"},
+            ],
+            "title": "Shop",
+        }),
+        page.to_value()
+    );
+    assert_eq!(page, serde_json::from_value(page.to_value()).unwrap());
+    assert_eq!(Some("/settings"), page.selected_tab());
+    let page: Page = TabPage::new("Shop", [Tab::new("Orders", &PageKey::new("/orders"))]).into();
+    assert_eq!(Some("/orders"), page.selected_tab());
+}
+
+#[test]
+fn page_pull_to_refresh_serialize() {
+    let page: Page = NavPage::new("t", Text::new("a"))
+        .with_pull_to_refresh("/r")
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "nav-page",
+            "title": "t",
+            "pull-to-refresh": "/r",
+            "widget": {"typ": "text", "text": "a"},
+        }),
+        page.to_value()
+    );
+    assert_eq!(page, serde_json::from_value(page.to_value()).unwrap());
+    assert_eq!(
+        json!({
+            "typ": "plain-page",
+            "title": "t",
+            "pull-to-refresh": "/r",
+            "widget": {"typ": "text", "text": "a"},
+        }),
+        Page::from(PlainPage::new("t", Text::new("a")).with_pull_to_refresh("/r")).to_value()
+    );
+}
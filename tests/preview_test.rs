@@ -0,0 +1,281 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{push, rpc};
+use applin::internal::{Chunk, Page, Tab, Widget};
+use applin::preview::{escape_css_url, page_to_html, preview_response, widget_to_html};
+use applin::session::{PageKey, PageMap, SessionSet};
+use applin::testing::Harness;
+use applin::widget::{
+    AlertModal, BackButton, Button, Form, FormSection, Frame, HAlignment, Icon, Image, LazyList,
+    ModalButton, NavPage, Padding, PlainPage, ProgressBar, RichText, Row, SheetDetent, SheetModal,
+    Spacer, Stack, TabPage, Text, Textfield, VAlignment,
+};
+use servlin::ResponseBody;
+
+fn body_string(body: ResponseBody) -> String {
+    match body {
+        ResponseBody::Vec(bytes) => String::from_utf8(bytes).unwrap(),
+        _ => panic!("unexpected body"),
+    }
+}
+
+#[test]
+fn widget() {
+    let widget: Widget = Button::new("<b>")
+        .with_actions([rpc("/r1"), push(&PageKey::new("/p1"))])
+        .into();
+    assert_eq!(
+        "<button class=\"button\">&lt;b&gt;<span class=\"actions\">rpc:/r1, push:/p1</span></button>",
+        widget_to_html(&widget, None)
+    );
+    assert_eq!(
+        "<button class=\"button\">&lt;b&gt;<span class=\"actions\">rpc:/r1, <a href=\"/preview/p1\">push:/p1</a></span></button>",
+        widget_to_html(&widget, Some("/preview"))
+    );
+}
+
+#[test]
+fn page() {
+    let page: Page = NavPage::new(
+        "Title1",
+        Form::new(
+            FormSection::new()
+                .with_title("Section1")
+                .with_widget(Textfield::new("name").with_label("Name").with_error("Bad")),
+        ),
+    )
+    .with_start(BackButton::new())
+    .with_poll(10)
+    .into();
+    let html = page_to_html(&page, None);
+    assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+    for s in [
+        "<title>Title1</title>",
+        "<div class=\"title\">Title1</div>",
+        "‹ Back",
+        "<div class=\"section-title\">Section1</div>",
+        "<label>Name</label>",
+        "⚠️ Bad",
+        "polls every 10 seconds",
+    ] {
+        assert!(html.contains(s), "missing {s:?} in {html}");
+    }
+    let page: Page = AlertModal::new("Alert1")
+        .with_text("text1")
+        .with_widget(ModalButton::new("Delete").with_is_destructive())
+        .into();
+    let html = page_to_html(&page, None);
+    assert!(html.contains("<div class=\"alert\"><div class=\"title\">Alert1</div>"));
+    assert!(html.contains("<button class=\"button modal-button destructive\">Delete</button>"));
+}
+
+#[test]
+fn route() {
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| {
+            Ok(PageMap::new()
+                .with_static_page("/", NavPage::new("Home", Text::new("hello")))
+                .with_static_page("/p2", NavPage::new("Page 2", Text::new("two"))))
+        },
+        (),
+    );
+    let harness = Harness::new(session.clone());
+    let req = harness.request("GET", "/preview", &serde_json::Value::Null);
+    let response = preview_response(&session, "/preview", &req).ok().unwrap();
+    let html = body_string(response.body);
+    assert!(
+        html.contains("<a href=\"/preview/\">/</a><a href=\"/preview/p2\">/p2</a>"),
+        "{html}"
+    );
+    let req = harness.request("GET", "/preview/p2", &serde_json::Value::Null);
+    let response = preview_response(&session, "/preview", &req).ok().unwrap();
+    assert!(body_string(response.body).contains("<div class=\"text\">two</div>"));
+    let req = harness.request("GET", "/preview/nonexistent", &serde_json::Value::Null);
+    assert_eq!(
+        400,
+        preview_response(&session, "/preview", &req)
+            .err()
+            .unwrap()
+            .code
+    );
+}
+
+#[test]
+fn image_url() {
+    assert_eq!(
+        "/a%27%29;%20b%22%28%5C%0A%C2%A0.png",
+        escape_css_url("/a');\u{20}b\"(\\\n\u{a0}.png")
+    );
+    let html = widget_to_html(
+        &Image::new(1.0, "/x');background:url('/evil.png").into(),
+        None,
+    );
+    assert!(
+        html.contains("url('/x%27%29;background:url%28%27/evil.png')"),
+        "{html}"
+    );
+}
+
+#[test]
+fn route_page_fn_error() {
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| {
+            let mut page_map = PageMap::new();
+            page_map.add_page_fn("/", |_| -> Result<Page, _> { Err("broken".into()) });
+            Ok(page_map)
+        },
+        (),
+    );
+    let harness = Harness::new(session.clone());
+    let req = harness.request("GET", "/preview/", &serde_json::Value::Null);
+    assert_eq!(
+        500,
+        preview_response(&session, "/preview", &req)
+            .err()
+            .unwrap()
+            .code
+    );
+}
+
+#[test]
+fn layout() {
+    let html = widget_to_html(
+        &Stack::new((
+            Row::new((Text::new("a"), Spacer::new().with_min_size(5)))
+                .with_alignment(VAlignment::Bottom),
+            Padding::new(
+                2,
+                Frame::new(Text::new("b")).with_max_width(30).with_weight(1),
+            ),
+        ))
+        .with_alignment(HAlignment::Center, VAlignment::Center)
+        .into(),
+        None,
+    );
+    for part in [
+        "<div class=\"stack\" style=\"justify-items: center; align-items: center;\">",
+        "<div class=\"row\" style=\"align-items: flex-end; gap: 0px;\">",
+        "<div class=\"spacer\" style=\"flex: 1 0 5px;\"></div>",
+        "<div class=\"padding\" style=\"padding: 2px 2px 2px 2px;\">",
+        "<div class=\"frame\" style=\"max-width: 30px; flex: 1 1 0;\">",
+    ] {
+        assert!(html.contains(part), "missing {part:?} in {html}");
+    }
+}
+
+#[test]
+fn icon() {
+    assert_eq!(
+        "<span class=\"icon\" style=\"font-size: 32px;\">star</span>",
+        widget_to_html(&Icon::new("star").with_size(32).into(), None)
+    );
+    assert_eq!(
+        "<button class=\"button\"><span class=\"icon\">check</span>Save</button>",
+        widget_to_html(&Button::new("Save").with_icon("check").into(), None)
+    );
+}
+
+#[test]
+fn rich_text() {
+    let html = widget_to_html(
+        &RichText::from_markdown("a **b** [c](/c) <i>").into(),
+        Some("/preview"),
+    );
+    assert_eq!(
+        "<div class=\"text rich-text\"><span style=\"\">a </span>\
+         <span style=\"font-weight: bold; \">b</span><span style=\"\"> </span>\
+         <a class=\"link\" title=\"push:/c\" href=\"/preview/c\" style=\"\">c</a>\
+         <span style=\"\"> &lt;i&gt;</span></div>",
+        html
+    );
+}
+
+#[test]
+fn sheet_modal() {
+    let page: Page = SheetModal::new("Filters", Text::new("in stock"))
+        .with_detent(SheetDetent::Medium)
+        .into();
+    let html = page_to_html(&page, None);
+    assert!(html.contains("<div class=\"sheet\" style=\"height: 50%\">"));
+    assert!(html.contains("Filters"));
+    assert!(html.contains("in stock"));
+}
+
+#[test]
+fn tab_page() {
+    let page: Page = TabPage::new(
+        "Shop",
+        [
+            Tab::new("Orders", &PageKey::new("/orders")).with_icon("list"),
+            Tab::new("Settings", &PageKey::new("/settings")).with_badge_text("2"),
+        ],
+    )
+    .into();
+    let html = page_to_html(&page, Some("/preview"));
+    assert!(
+        html.contains(
+            "<div class=\"tab-bar\">\
+             <a class=\"tab selected\" href=\"/preview/orders\"><div class=\"tab-icon\"><span class=\"icon\">list</span></div>Orders</a>\
+             <a class=\"tab\" href=\"/preview/settings\">Settings<span class=\"badge\">2</span></a>\
+             </div>"
+        ),
+        "{html}"
+    );
+}
+
+#[test]
+fn lazy_list() {
+    let widget = LazyList::new("/more", Chunk::new(Text::new("a")).with_next_cursor("1")).into();
+    assert_eq!(
+        "<div class=\"column lazy-list\" style=\"gap: 0px;\" title=\"/more\">\
+         <div class=\"text\">a</div>\
+         <div class=\"lazy-list-loading\">Loading...</div></div>",
+        widget_to_html(&widget, None)
+    );
+}
+
+#[test]
+fn progress_bar() {
+    let html = widget_to_html(
+        &ProgressBar::new()
+            .with_percent(30)
+            .with_text("Saving")
+            .into(),
+        None,
+    );
+    assert!(html.contains("<div class=\"progress\" style=\"width: 30%;\"></div>"));
+    assert!(html.contains("<div class=\"progress-text\">Saving</div>"));
+    let html = widget_to_html(&ProgressBar::new().into(), None);
+    assert!(html.contains("progress-bar indeterminate"));
+}
+
+#[test]
+fn pull_to_refresh() {
+    let page: Page = PlainPage::new("t", Text::new("a"))
+        .with_pull_to_refresh("/r")
+        .into();
+    assert!(page_to_html(&page, None)
+        .contains("<div class=\"update-info\">pull to refresh calls /r</div>"));
+}
+
+#[test]
+fn route_keeps_session_pages() {
+    let harness = Harness::with_pages(
+        |_| {
+            Ok(PageMap::new()
+                .with_static_page("/", NavPage::new("Home", Text::new("hello")))
+                .with_static_page("/p2", NavPage::new("Page 2", Text::new("two"))))
+        },
+        (),
+    );
+    let req = harness.request("GET", "/preview", &serde_json::Value::Null);
+    preview_response(harness.session(), "/preview", &req)
+        .ok()
+        .unwrap();
+    let update = harness.poll().unwrap();
+    assert_eq!(
+        vec!["/", "/p2"],
+        update.pages.keys().map(String::as_str).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,93 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{refresh, rpc};
+use applin::internal::Page;
+use applin::session::{ApplinSession, PageMap};
+use applin::testing::{AppClient, Connection, Harness};
+use applin::widget::{Button, Column, NavPage, Text};
+use serde_json::json;
+use servlin::{Request, Response};
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering::SeqCst;
+use std::sync::Arc;
+
+/// The page reads the count without a roster, so changes need a refresh.
+fn page_map(count: &Arc<AtomicU32>) -> PageMap<()> {
+    let count = Arc::clone(count);
+    PageMap::new().with_page_fn("/", move |_| {
+        Ok(NavPage::new(
+            "Orders",
+            Column::new((
+                Text::new(format!("orders: {}", count.load(SeqCst))),
+                Button::new("Refresh").with_action(refresh("/")),
+                Button::new("Refresh Image").with_action(refresh("/logo.png")),
+            )),
+        )
+        .with_pull_to_refresh("/orders/refresh"))
+    })
+}
+
+fn handle(session: &Arc<ApplinSession<()>>, req: &Request) -> Result<Response, Response> {
+    match (req.method.as_str(), req.url.path()) {
+        ("POST", "/orders/refresh") => {
+            session.refresh(["/", "/missing"]);
+            session.rpc_response()
+        }
+        ("POST", "/refresh") => session.refresh_response(req),
+        _ => Ok(Response::not_found_404()),
+    }
+}
+
+#[test]
+fn pull_to_refresh() {
+    let count = Arc::new(AtomicU32::new(1));
+    let count2 = Arc::clone(&count);
+    let harness =
+        Harness::with_pages(move |_| Ok(page_map(&count2)), ()).with_session_handler(handle);
+    let mut client = AppClient::new(harness).unwrap();
+    client.assert_text("orders: 1");
+    count.store(2, SeqCst);
+    client.poll().unwrap();
+    client.assert_text("orders: 1");
+    let Page::Nav {
+        pull_to_refresh, ..
+    } = client.page().unwrap().clone()
+    else {
+        panic!("expected a nav page");
+    };
+    client.run_action(&rpc(pull_to_refresh.unwrap())).unwrap();
+    client.assert_text("orders: 2");
+}
+
+#[test]
+fn refresh_action() {
+    let count = Arc::new(AtomicU32::new(1));
+    let count2 = Arc::clone(&count);
+    let harness =
+        Harness::with_pages(move |_| Ok(page_map(&count2)), ()).with_session_handler(handle);
+    let mut client = AppClient::new(harness).unwrap();
+    count.store(3, SeqCst);
+    client.tap("Refresh").unwrap();
+    client.assert_text("orders: 3");
+    // This client shows no images, so refreshing an image does nothing.
+    count.store(4, SeqCst);
+    client.tap("Refresh Image").unwrap();
+    client.assert_text("orders: 3");
+}
+
+#[test]
+fn refresh_response() {
+    let count = Arc::new(AtomicU32::new(1));
+    let harness =
+        Harness::with_pages(move |_| Ok(page_map(&count)), ()).with_session_handler(handle);
+    let client = AppClient::new(harness).unwrap();
+    let harness = client.connection();
+    assert!(harness.refresh("/").is_ok());
+    assert_eq!(
+        Err("400 unknown page key \"/missing\"".to_string()),
+        harness.refresh("/missing")
+    );
+    let response = harness
+        .call("POST", "/refresh", &json!({"other": 1}))
+        .unwrap_or_else(|r| r);
+    assert_eq!(400, response.code);
+}
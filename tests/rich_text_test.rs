@@ -0,0 +1,97 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{launch_url, push};
+use applin::internal::Action;
+use applin::session::{PageKey, PageMap, SessionSet};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{parse_markdown, NavPage, RichText, Span, Text};
+
+#[test]
+fn markdown() {
+    assert_eq!(
+        vec![
+            Span::new("a "),
+            Span::new("b").with_bold(),
+            Span::new(" "),
+            Span::new("c").with_italic(),
+            Span::new(" "),
+            Span::new("d").with_italic(),
+            Span::new(" "),
+            Span::new("e").with_strikethrough(),
+            Span::new(" "),
+            Span::new("*f*").with_monospace(),
+        ],
+        parse_markdown("a **b** *c* _d_ ~~e~~ `*f*`")
+    );
+    assert_eq!(
+        vec![Span::group(("x ", Span::new("y").with_italic())).with_bold()],
+        parse_markdown("**x *y***")
+    );
+    assert_eq!(
+        vec![
+            Span::new("See "),
+            Span::new("docs").with_action(launch_url("https://example.com/docs")),
+            Span::new(", "),
+            Span::new("terms").with_action(push(&PageKey::new("/terms"))),
+            Span::new(", and "),
+            Span::new("mail").with_action(launch_url("mailto:a@example.com")),
+            Span::new(". bad"),
+        ],
+        parse_markdown(
+            "See [docs](https://example.com/docs), [terms](/terms), \
+             and [mail](mailto:a@example.com). [bad](javascript:alert(1))"
+        )
+    );
+    assert_eq!(
+        vec![Span::new("Line one line two\n\n• item 1\n• item 2\n\nEnd")],
+        parse_markdown("Line one\nline two\n\n- item 1\n* item 2\n\nEnd\n")
+    );
+    // Unclosed, escaped, and intraword delimiters show as text.
+    for (markdown, text) in [
+        ("2 * 3 * 4", "2 * 3 * 4"),
+        ("**open", "**open"),
+        ("\\*not italic\\*", "*not italic*"),
+        ("snake_case_name", "snake_case_name"),
+        ("<b>html</b>", "<b>html</b>"),
+        ("[link](", "[link]("),
+        ("``", "``"),
+    ] {
+        assert_eq!(
+            vec![Span::new(text)],
+            parse_markdown(markdown),
+            "{markdown:?}"
+        );
+    }
+    assert_eq!(Vec::<Span>::new(), parse_markdown(" \n\n"));
+    assert_eq!(
+        RichText::new(Span::new("hi").with_bold()).to_widget(),
+        RichText::from_markdown("**hi**").to_widget()
+    );
+}
+
+#[test]
+fn tap_link() {
+    let sessions: SessionSet<()> = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| {
+            Ok(PageMap::new()
+                .with_static_page(
+                    "/",
+                    NavPage::new(
+                        "Home",
+                        RichText::from_markdown("Read the [terms](/terms) or [help](https://x.y)."),
+                    ),
+                )
+                .with_static_page("/terms", NavPage::new("Terms", Text::new("..."))))
+        },
+        (),
+    );
+    let mut client = AppClient::new(Harness::new(session)).unwrap();
+    client.assert_text("Read the terms or help.");
+    client.tap("help").unwrap();
+    assert_eq!(&["https://x.y".to_string()], client.launched_urls());
+    client.tap("terms").unwrap();
+    client.assert_page("/terms");
+    assert!(client.tap("Read").is_err());
+    let span = Span::group(("a", Span::new("b"))).with_action(Action::Pop);
+    assert_eq!(vec![("ab".to_string(), &[Action::Pop][..])], span.links());
+}
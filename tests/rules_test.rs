@@ -0,0 +1,297 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{push, rpc};
+use applin::data::Roster;
+use applin::internal::{Update, Widget};
+use applin::rules::{FormRules, Rule};
+use applin::session::{PageKey, PageMap, SessionSet};
+use applin::testing::{AppClient, Harness, RpcError};
+use applin::widget::{Checkbox, Form, FormButton, NavPage, NumberRange, Text, Textfield};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use servlin::{Request, Response};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn errors<const N: usize>(pairs: [(&str, &str); N]) -> BTreeMap<String, String> {
+    pairs
+        .into_iter()
+        .map(|(var, e)| (var.to_string(), e.to_string()))
+        .collect()
+}
+
+#[test]
+fn rule() {
+    assert_eq!(Ok(()), Rule::required().check(&json!("a")));
+    assert_eq!(Ok(()), Rule::required().check(&json!(false)));
+    for value in [Value::Null, json!(""), json!("  "), json!([])] {
+        assert_eq!(Err("Required".to_string()), Rule::required().check(&value));
+        assert_eq!(Ok(()), Rule::email().check(&value));
+    }
+    assert_eq!(Ok(()), Rule::email().check(&json!("a@b.com")));
+    for s in ["a", "a@b", "@b.com", "a@b..com", "a b@c.com", "a@b@c.com"] {
+        assert_eq!(
+            Err("Enter a valid email address".to_string()),
+            Rule::email().check(&json!(s)),
+            "{s:?}"
+        );
+    }
+    assert_eq!(Ok(()), Rule::min_chars(2).check(&json!("ab")));
+    assert_eq!(
+        Err("Enter at least 2 characters".to_string()),
+        Rule::min_chars(2).check(&json!("é"))
+    );
+    assert_eq!(Ok(()), Rule::max_chars(2).check(&json!("éé")));
+    assert_eq!(
+        Err("Enter at most 2 characters".to_string()),
+        Rule::max_chars(2).check(&json!("abc"))
+    );
+    assert_eq!(
+        Err("Must be text".to_string()),
+        Rule::max_chars(2).check(&json!(1))
+    );
+    let zip = Rule::regex("^[0-9]{5}$");
+    assert_eq!(Ok(()), zip.check(&json!("12345")));
+    assert_eq!(Err("Invalid format".to_string()), zip.check(&json!("1234")));
+    assert_eq!(
+        Err("Enter a 5-digit ZIP code".to_string()),
+        zip.with_message("Enter a 5-digit ZIP code")
+            .check(&json!("x"))
+    );
+    let guests = Rule::range(
+        NumberRange::new()
+            .with_min(1.0)
+            .with_max(4.0)
+            .with_integer(),
+    );
+    assert_eq!(Ok(()), guests.check(&json!(4)));
+    assert_eq!(
+        Err("Number must be at most 4".to_string()),
+        guests.check(&json!(5))
+    );
+    assert_eq!(
+        Err("Number must be a whole number".to_string()),
+        guests.check(&json!(1.5))
+    );
+    assert_eq!(Err("Enter a number".to_string()), guests.check(&json!("2")));
+    let agree = Rule::custom(|value| match value {
+        Value::Bool(true) => Ok(()),
+        _ => Err("You must agree".to_string()),
+    });
+    assert_eq!(Ok(()), agree.check(&json!(true)));
+    assert_eq!(Err("You must agree".to_string()), agree.check(&Value::Null));
+    assert_eq!(
+        "Rule::Regex(\"^[0-9]{5}$\").with_message(\"m\")",
+        format!("{:?}", Rule::regex("^[0-9]{5}$").with_message("m"))
+    );
+}
+
+fn signup_rules() -> FormRules {
+    FormRules::new()
+        .with_rules("name", [Rule::required(), Rule::max_chars(20)])
+        .with_rule("email", Rule::required())
+        .with_rule("email", Rule::email())
+        .with_rule(
+            "agree",
+            Rule::custom(|value| match value {
+                Value::Bool(true) => Ok(()),
+                _ => Err("You must agree".to_string()),
+            }),
+        )
+}
+
+#[test]
+fn check() {
+    let rules = signup_rules();
+    assert_eq!(
+        vec!["name", "email", "agree"],
+        rules.vars().collect::<Vec<_>>()
+    );
+    assert_eq!(2, rules.rules("email").len());
+    assert!(rules.rules("other").is_empty());
+    assert_eq!(
+        errors([
+            ("agree", "You must agree"),
+            ("email", "Required"),
+            ("name", "Required")
+        ]),
+        rules.check(json!({}).as_object().unwrap())
+    );
+    assert_eq!(
+        errors([("email", "Enter a valid email address")]),
+        rules.check(
+            json!({"name": "Ann", "email": "ann", "agree": true, "other": 1})
+                .as_object()
+                .unwrap()
+        )
+    );
+}
+
+#[test]
+fn apply() {
+    let form = Form::new((
+        Textfield::new("name").with_max_chars(10),
+        Textfield::new("email"),
+        Checkbox::new("agree", "I agree"),
+        Text::new("t"),
+    ));
+    let widget = signup_rules().apply(
+        &errors([("email", "Required"), ("agree", "You must agree")]),
+        form,
+    );
+    let expected: Widget = Form::new((
+        Textfield::new("name").with_max_chars(10),
+        Textfield::new("email").with_error("Required"),
+        Checkbox::new("agree", "I agree"),
+        Widget::ErrorTextVariant {
+            text: "You must agree".to_string(),
+        },
+        Text::new("t"),
+    ))
+    .into();
+    assert_eq!(expected, widget);
+    assert_eq!(
+        Widget::from(Textfield::new("name").with_min_chars(2).with_max_chars(20)),
+        FormRules::new()
+            .with_rules("name", [Rule::min_chars(2), Rule::max_chars(20)])
+            .apply(&BTreeMap::new(), Textfield::new("name"))
+    );
+}
+
+#[derive(Deserialize)]
+struct SignupVars {
+    name: String,
+    email: String,
+}
+
+fn form_page(errors: &BTreeMap<String, String>, rpc_path: &str) -> NavPage {
+    let rules = FormRules::new()
+        .with_rule("name", Rule::required())
+        .with_rules("email", [Rule::required(), Rule::email()]);
+    NavPage::new(
+        "Sign Up",
+        rules.apply(
+            errors,
+            Form::new((
+                Textfield::new("name"),
+                Textfield::new("email"),
+                FormButton::new("Sign Up")
+                    .with_actions([rpc(rpc_path), push(&PageKey::new("/welcome"))]),
+            )),
+        ),
+    )
+}
+
+fn start() -> (Arc<Roster<Vec<String>, ()>>, Harness<()>) {
+    let sessions: Arc<SessionSet<()>> = Arc::new(SessionSet::without_executor());
+    let signups: Arc<Roster<Vec<String>, ()>> = Arc::new(Roster::new(Vec::new()));
+    let page_map_fn = move |_| {
+        Ok(PageMap::new()
+            .with_page_fn("/", move |rebuilder| {
+                Ok(form_page(
+                    &rebuilder.session()?.field_errors("/"),
+                    "/signup",
+                ))
+            })
+            .with_page_fn("/other", move |rebuilder| {
+                Ok(form_page(
+                    &rebuilder.session()?.field_errors("/other"),
+                    "/signup-other",
+                ))
+            })
+            .with_static_page("/welcome", NavPage::new("Welcome", Text::new("Welcome!"))))
+    };
+    let session = sessions.new_session(page_map_fn, ());
+    let signups2 = Arc::clone(&signups);
+    let harness = Harness::new(session).with_handler(move |req: Request| {
+        let page_key = match (req.method.as_str(), req.url.path()) {
+            ("POST", "/signup") => "/",
+            ("POST", "/signup-other") => "/other",
+            _ => return Ok(Response::not_found_404()),
+        };
+        let session = sessions.get(&req)?;
+        let rules = FormRules::new()
+            .with_rule("name", Rule::required())
+            .with_rules("email", [Rule::required(), Rule::email()]);
+        let vars: SignupVars = rules.parse(&session, page_key, &req)?;
+        signups2
+            .write(session.rpc_context())
+            .push(format!("{} {}", vars.name, vars.email));
+        session.rpc_response()
+    });
+    (signups, harness)
+}
+
+fn signup(harness: &Harness<()>, vars: &Value) -> Update {
+    match harness.rpc("/signup", vars) {
+        Ok(update) => update,
+        Err(RpcError::Rejected(update)) => *update,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+fn email_error(harness: &Harness<()>, vars: &Value) -> Value {
+    let update = signup(harness, vars);
+    update.pages["/"].as_ref().unwrap().to_value()["widget"]["widgets"][1]["error"].clone()
+}
+
+#[test]
+fn parse() {
+    let (signups, harness) = start();
+    harness.poll().unwrap();
+    assert_eq!(
+        json!("Required"),
+        email_error(&harness, &json!({"name": "Ann"}))
+    );
+    assert_eq!(
+        errors([("email", "Required")]),
+        harness.session().field_errors("/")
+    );
+    assert_eq!(
+        json!("Enter a valid email address"),
+        email_error(&harness, &json!({"name": "Ann", "email": "ann"}))
+    );
+    assert!(signups.peek().is_empty());
+    assert_eq!(
+        Value::Null,
+        email_error(
+            &harness,
+            &json!({"name": "Ann", "email": "ann@example.com"})
+        )
+    );
+    assert_eq!(None, harness.session().field_error("/", "email"));
+    assert_eq!(vec!["Ann ann@example.com".to_string()], *signups.peek());
+}
+
+#[test]
+fn rejected_rpc() {
+    let (_signups, harness) = start();
+    harness.poll().unwrap();
+    let Err(RpcError::Rejected(update)) = harness.rpc("/signup", &json!({"name": "Ann"})) else {
+        panic!("expected a rejected rpc");
+    };
+    // Only the page with the form changed.
+    assert_eq!(vec!["/"], update.pages.keys().collect::<Vec<_>>());
+    assert_eq!(
+        errors([("email", "Required")]),
+        harness.session().field_errors("/")
+    );
+    assert!(harness.session().field_errors("/other").is_empty());
+}
+
+#[test]
+fn rejected_rpc_skips_actions() {
+    let (signups, harness) = start();
+    let mut client = AppClient::new(harness).unwrap();
+    client.set_text("name", "Ann").unwrap();
+    assert_eq!(
+        "rpc \"/signup\" rejected",
+        client.tap("Sign Up").unwrap_err()
+    );
+    client.assert_page("/");
+    client.assert_text("Required");
+    assert!(signups.peek().is_empty());
+    client.set_text("email", "ann@example.com").unwrap();
+    client.tap("Sign Up").unwrap();
+    client.assert_page("/welcome");
+    assert_eq!(vec!["Ann ann@example.com".to_string()], *signups.peek());
+}
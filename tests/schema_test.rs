@@ -0,0 +1,280 @@
+#![cfg(feature = "schema")]
+#![allow(clippy::missing_panics_doc)]
+#[path = "../examples/clock.rs"]
+#[allow(dead_code)]
+mod clock;
+#[path = "../examples/demo/main.rs"]
+#[allow(dead_code)]
+mod demo;
+#[path = "../examples/dynamic_page.rs"]
+#[allow(dead_code)]
+mod dynamic_page;
+#[path = "../examples/minimal.rs"]
+#[allow(dead_code)]
+mod minimal;
+#[path = "../examples/server_state.rs"]
+#[allow(dead_code)]
+mod server_state;
+#[path = "../examples/session_state.rs"]
+#[allow(dead_code)]
+mod session_state;
+
+use applin::action::{
+    choose_file, choose_photo, confirm, copy_to_clipboard, launch_url, logout, nothing, pop, push,
+    refresh, rpc, rpc_branch, rpc_with_progress, take_photo,
+};
+use applin::data::Rebuilder;
+use applin::image_store::ImageStore;
+use applin::internal::{json_schema, Action, ImageDisposition, Page, Update};
+use applin::session::{PageKey, PageMap, SessionSet};
+use applin::style::{Style, Theme};
+use applin::testing::Harness;
+use applin::widget::{
+    AlertModal, BackButton, Button, Checkbox, Column, DrawerModal, Empty, ErrorDetails, ErrorText,
+    Form, FormButton, FormSection, GroupedRowTable, HAlignment, Image, ModalButton, NavButton,
+    NavPage, PlainPage, Scroll, Text, Textfield,
+};
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+use servlin::reexport::safina_executor;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::sync::Arc;
+use temp_dir::TempDir;
+
+/// Returns a schema that matches the definition `name` in the Applin schema.
+fn definition_schema(name: &str) -> Value {
+    let mut schema = json_schema();
+    let definitions = schema
+        .as_object_mut()
+        .unwrap()
+        .remove("definitions")
+        .unwrap();
+    json!({
+        "allOf": [{"$ref": format!("#/definitions/{name}")}],
+        "definitions": definitions,
+    })
+}
+
+fn errors(schema: &Value, value: &Value) -> Vec<String> {
+    let compiled = JSONSchema::compile(schema).unwrap();
+    let result = match compiled.validate(value) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|e| format!("{}: {e}", e.instance_path))
+            .collect(),
+    };
+    result
+}
+
+fn assert_valid(schema: &Value, value: &Value) {
+    let errors = errors(schema, value);
+    assert!(errors.is_empty(), "{value} does not match: {errors:?}");
+}
+
+/// Builds the session's pages with [`Harness`] and checks the update against the schema.
+fn assert_pages_match_schema<T: 'static + Send + Sync>(
+    page_map_fn: impl 'static + Send + Sync + Fn(Rebuilder<T>) -> Result<PageMap<T>, Box<dyn Error>>,
+    value: T,
+) {
+    let session = SessionSet::without_executor().new_session(page_map_fn, value);
+    let update = Harness::new(session).poll().unwrap();
+    assert!(!update.pages.is_empty());
+    assert_valid(&json_schema(), &update.to_value());
+}
+
+#[test]
+fn examples_match_schema() {
+    let executor = safina_executor::Executor::default();
+    assert_pages_match_schema(minimal::page_map, ());
+    let state = Arc::new(clock::ServerState::new(&executor));
+    assert_pages_match_schema(move |_| Ok(clock::page_map(&state)), clock::SessionState {});
+    let state = Arc::new(dynamic_page::ServerState::new(&executor));
+    assert_pages_match_schema(
+        move |rebuilder| dynamic_page::page_map(&state, rebuilder),
+        dynamic_page::SessionState {},
+    );
+    let state = Arc::new(server_state::ServerState::new(&executor));
+    assert_pages_match_schema(
+        move |_| Ok(server_state::page_map(&state)),
+        server_state::SessionState {},
+    );
+    let state = Arc::new(session_state::ServerState::new(&executor));
+    assert_pages_match_schema(
+        move |_| Ok(session_state::page_map(&state)),
+        session_state::SessionState::new(session_state::UserId(1)),
+    );
+    let image_dir = TempDir::new().unwrap();
+    let images = ImageStore::new(image_dir.path(), demo::IMAGES_PATH);
+    let state = Arc::new(demo::ServerState::new(&executor, images));
+    assert_pages_match_schema(move |_| Ok(demo::page_map(&state)), demo::Session::new());
+}
+
+#[test]
+fn pages_match_schema() {
+    let pages: Vec<Page> = vec![
+        AlertModal::new("Alert1")
+            .with_text("text1")
+            .with_ok()
+            .with_widget(ModalButton::cancel())
+            .into(),
+        DrawerModal::new("Drawer1")
+            .with_widget(ModalButton::new("b1").with_is_destructive())
+            .into(),
+        NavPage::new(
+            "Nav1",
+            Scroll::new(
+                Column::new((
+                    Button::new("b1").with_action(push(&PageKey::new("/p1"))),
+                    Button::new("b2").with_action(confirm(
+                        "Sure?",
+                        "",
+                        [rpc_branch("/rpc2", [pop()], [push(&PageKey::new("/p1"))])],
+                    )),
+                    Checkbox::new("check1", "Check 1")
+                        .with_initial(true)
+                        .with_rpc("/rpc1"),
+                    Empty::new(),
+                    ErrorDetails::new(),
+                    ErrorText::new("error1"),
+                    Form::new((
+                        FormButton::new("fb1").with_is_destructive(),
+                        FormSection::new().with_title("Section1").with_widget(
+                            Textfield::new("text1")
+                                .with_allow_numbers()
+                                .with_autocap_names()
+                                .with_error("error1")
+                                .with_initial("initial1")
+                                .with_label("Label1")
+                                .with_max_chars(10)
+                                .with_max_lines(1)
+                                .with_min_chars(1)
+                                .with_rpc("/rpc1"),
+                        ),
+                    )),
+                    GroupedRowTable::new()
+                        .with_spacing(4)
+                        .with_row_group(((Text::new("A1"), None),)),
+                    Image::new(1.5, "/image1.png").with_disposition(ImageDisposition::Cover),
+                    NavButton::new("nb1")
+                        .with_badge_text("1")
+                        .with_photo_url("/photo1.jpg")
+                        .with_sub_text("sub1"),
+                ))
+                .with_alignment(HAlignment::Center)
+                .with_spacing(8),
+            ),
+        )
+        .with_start(BackButton::new().with_action(rpc("/back")))
+        .with_end(Button::new("end1").with_style(Style::new().with_color("#ff0000")))
+        .with_poll(5)
+        .with_theme(Theme::empty().with_radius("card", 12))
+        .into(),
+        PlainPage::new("Plain1", Text::new("text1"))
+            .with_stream()
+            .into(),
+    ];
+    let update = Update {
+        pages: pages
+            .into_iter()
+            .enumerate()
+            .map(|(n, page)| (format!("/{n}"), Some(page)))
+            .chain([("/removed".to_string(), None)])
+            .collect::<BTreeMap<_, _>>(),
+        vars: Some(serde_json::from_value(json!({"text1": "abc", "check1": true})).unwrap()),
+        theme: Some(Theme::new()),
+        ..Update::default()
+    };
+    assert_valid(&json_schema(), &update.to_value());
+    assert_valid(&json_schema(), &json!({}));
+}
+
+#[test]
+fn actions_match_schema() {
+    let schema = definition_schema("Action");
+    for action in [
+        choose_photo("/upload"),
+        copy_to_clipboard(""),
+        copy_to_clipboard("line1\nline2"),
+        launch_url("https://example.com/"),
+        Action::Logout,
+        Action::Nothing,
+        Action::Pop,
+        push(&PageKey::new("/p1")),
+        rpc("/rpc1"),
+        take_photo("/upload"),
+    ] {
+        assert_valid(&schema, &action.to_value());
+    }
+    for bad in ["", "push:", "rpc:", "launch-url:", "pop:x", "unknown"] {
+        assert!(
+            !errors(&schema, &json!(bad)).is_empty(),
+            "{bad:?} should not match"
+        );
+        serde_json::from_value::<Action>(json!(bad)).unwrap_err();
+    }
+}
+
+/// Returns one value of every `Action` variant.
+fn every_action() -> Vec<Action> {
+    let actions = vec![
+        choose_file("/upload?a=b:c", ["image/png", "text/plain"], 1024),
+        choose_photo("/upload"),
+        confirm("Sure?", "text1", [pop()]),
+        copy_to_clipboard("line1\nline2:x"),
+        launch_url("https://example.com/"),
+        logout(),
+        nothing(),
+        pop(),
+        push(&PageKey::new("/p1")),
+        refresh("/p1"),
+        rpc("/rpc1"),
+        rpc_with_progress("/rpc1"),
+        rpc_branch("/rpc1", [pop()], [nothing()]),
+        take_photo("/upload"),
+    ];
+    // When you add a variant, add it above and here.
+    for action in &actions {
+        match action {
+            Action::ChooseFile { .. }
+            | Action::ChoosePhoto(_)
+            | Action::Confirm { .. }
+            | Action::CopyToClipboard(_)
+            | Action::LaunchUrl(_)
+            | Action::Logout
+            | Action::Nothing
+            | Action::Pop
+            | Action::Push(_)
+            | Action::Refresh(_)
+            | Action::Rpc(_)
+            | Action::RpcWithProgress(_)
+            | Action::RpcBranch { .. }
+            | Action::TakePhoto(_) => {}
+        }
+    }
+    actions
+}
+
+#[test]
+fn action_pattern_round_trips() {
+    let schema = json_schema();
+    let pattern = schema["definitions"]["Action"]["anyOf"][0]["pattern"].clone();
+    assert!(pattern.is_string(), "{schema}");
+    let string_schema = json!({"type": "string", "pattern": pattern});
+    let action_schema = definition_schema("Action");
+    for action in every_action() {
+        let value = action.to_value();
+        if value.is_string() {
+            assert_valid(&string_schema, &value);
+        }
+        assert_valid(&action_schema, &value);
+        assert_eq!(action, serde_json::from_value::<Action>(value).unwrap());
+    }
+}
+
+#[test]
+fn unknown_widget_does_not_match_schema() {
+    let value =
+        json!({"pages": {"/": {"typ": "nav-page", "title": "t1", "widget": {"typ": "unknown"}}}});
+    assert!(!errors(&json_schema(), &value).is_empty());
+}
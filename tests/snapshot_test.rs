@@ -0,0 +1,153 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::push;
+use applin::internal::Page;
+use applin::session::{PageKey, PageMap, SessionSet};
+use applin::testing::{diff_values, page_key_to_file_name, Harness, Snapshots};
+use applin::widget::{Button, Column, Form, NavPage, Text, Textfield};
+use serde_json::json;
+use std::sync::Arc;
+use temp_dir::TempDir;
+
+fn page_map() -> PageMap<()> {
+    PageMap::new()
+        .with_static_page(
+            "/",
+            NavPage::new(
+                "Home",
+                Column::new((
+                    Text::new("Welcome"),
+                    Button::new("Sign up").with_action(push(&PageKey::new("/sign-up"))),
+                )),
+            ),
+        )
+        .with_static_page(
+            "/sign-up",
+            NavPage::new(
+                "Sign Up",
+                Form::new(Textfield::new("name").with_label("Name")),
+            ),
+        )
+}
+
+#[test]
+fn stored_snapshots() {
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(|_| Ok(page_map()), ());
+    Snapshots::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"))
+        .check_session("snapshot_test", &session)
+        .unwrap();
+}
+
+#[test]
+fn update_and_check() {
+    let temp_dir = TempDir::new().unwrap();
+    let page: Page = NavPage::new("Page1", Text::new("text1")).into();
+    assert_eq!(
+        format!(
+            "snapshot {} does not exist, run with APPLIN_UPDATE_SNAPSHOTS=1 to create it",
+            temp_dir.child("page1.json").display()
+        ),
+        Snapshots::new(temp_dir.path())
+            .with_update(false)
+            .check_page("page1", &page)
+            .unwrap_err()
+    );
+    Snapshots::new(temp_dir.path())
+        .with_update(true)
+        .check_page("page1", &page)
+        .unwrap();
+    Snapshots::new(temp_dir.path())
+        .with_update(false)
+        .check_page("page1", &page)
+        .unwrap();
+    let changed: Page = NavPage::new("Page1", Text::new("text2")).into();
+    let err = Snapshots::new(temp_dir.path())
+        .with_update(false)
+        .check_page("page1", &changed)
+        .unwrap_err();
+    assert!(
+        err.contains("\n  /widget/text: \"text1\" -> \"text2\"\n"),
+        "{err}"
+    );
+}
+
+#[test]
+fn session_removes_stale_snapshots() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::create_dir(temp_dir.child("app")).unwrap();
+    std::fs::write(temp_dir.child("app").join("old.json"), "{}").unwrap();
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(|_| Ok(page_map()), ());
+    let err = Snapshots::new(temp_dir.path())
+        .with_update(false)
+        .check_session("app", &session)
+        .unwrap_err();
+    assert!(err.contains("old.json has no page"), "{err}");
+    let session = sessions.new_session(|_| Ok(page_map()), ());
+    Snapshots::new(temp_dir.path())
+        .with_update(true)
+        .check_session("app", &session)
+        .unwrap();
+    let mut file_names: Vec<String> = std::fs::read_dir(temp_dir.child("app"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    file_names.sort();
+    assert_eq!(vec!["index.json", "sign-up.json"], file_names);
+}
+
+#[test]
+fn diff() {
+    assert_eq!(
+        vec![
+            "/a: 1 -> 2",
+            "/b/1: removed \"x\"",
+            "/c~1d: added true",
+            "/e: [] -> {}",
+        ],
+        diff_values(
+            &json!({"a": 1, "b": ["w", "x"], "e": []}),
+            &json!({"a": 2, "b": ["w"], "c/d": true, "e": {}}),
+        )
+    );
+    assert!(diff_values(&json!({"a": [1]}), &json!({"a": [1]})).is_empty());
+}
+
+#[test]
+fn file_names() {
+    assert_eq!("index", page_key_to_file_name("/"));
+    assert_eq!("a~b", page_key_to_file_name("/a/b"));
+    assert_eq!("a%20b%2Ec", page_key_to_file_name("/a b.c"));
+    assert_eq!("a_b", page_key_to_file_name("/a_b"));
+    assert_eq!("a%7Eb", page_key_to_file_name("/a~b"));
+    assert_eq!("%C3%A9", page_key_to_file_name("/\u{e9}"));
+}
+
+#[test]
+fn session_file_name_collision() {
+    let temp_dir = TempDir::new().unwrap();
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| Ok(page_map().with_static_page("/index", NavPage::new("Index", Text::new("t1")))),
+        (),
+    );
+    assert_eq!(
+        "page keys \"/\" and \"/index\" both have snapshot file name \"index\"",
+        Snapshots::new(temp_dir.path())
+            .with_update(true)
+            .check_session("app", &session)
+            .unwrap_err()
+    );
+}
+
+#[test]
+fn polled_session() {
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(|_| Ok(page_map()), ());
+    let snapshots = Snapshots::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"));
+    snapshots.check_session("snapshot_test", &session).unwrap();
+    // Checking does not take pages from the next update.
+    let harness = Harness::new(Arc::clone(&session));
+    assert_eq!(2, harness.poll().unwrap().pages.len());
+    snapshots.check_session("snapshot_test", &session).unwrap();
+}
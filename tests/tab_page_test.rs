@@ -0,0 +1,123 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::push;
+use applin::data::{Context, Rebuilder};
+use applin::internal::Tab;
+use applin::session::{PageKey, PageMap, PendingUpdate};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{NavButton, NavPage, Scroll, TabPage, Text};
+use std::collections::HashSet;
+use std::error::Error;
+
+#[allow(clippy::unnecessary_wraps)]
+fn page_map(_rebuilder: Rebuilder<()>) -> Result<PageMap<()>, Box<dyn Error>> {
+    let mut keys = PageMap::new();
+    let order = keys.add_static_page("/order", NavPage::new("Order 1", Text::new("shoes")));
+    let orders = keys.add_static_page(
+        "/orders",
+        NavPage::new(
+            "Orders",
+            Scroll::new(NavButton::new("Order 1").with_action(push(&order))),
+        ),
+    );
+    let settings = keys.add_static_page(
+        "/settings",
+        NavPage::new("Settings", Text::new("dark mode")),
+    );
+    keys.add_static_page(
+        "/",
+        TabPage::new(
+            "Shop",
+            [
+                Tab::new("Orders", &orders).with_icon("list"),
+                Tab::new("Settings", &settings).with_badge_text("1"),
+            ],
+        ),
+    );
+    Ok(keys)
+}
+
+#[test]
+fn tab_stacks() {
+    let mut client = AppClient::new(Harness::with_pages(page_map, ())).unwrap();
+    client.assert_page("/orders");
+    client.assert_text("Orders");
+    client.assert_text("Settings");
+    client.assert_text("1");
+    client.tap("Order 1").unwrap();
+    client.assert_page("/order");
+    client.assert_text("shoes");
+    assert_eq!(["/".to_string()], client.stack());
+    // Each tab keeps its own stack.
+    client.select_tab("Settings").unwrap();
+    client.assert_page("/settings");
+    client.select_tab("Orders").unwrap();
+    client.assert_page("/order");
+    client.tap_back().unwrap();
+    client.assert_page("/orders");
+    client.tap("Order 1").unwrap();
+    // Tapping the selected tab returns to its first page.
+    client.select_tab("Orders").unwrap();
+    client.assert_page("/orders");
+    assert_eq!(
+        Err("tab page \"/\" has no tab \"Other\"".to_string()),
+        client.select_tab("Other")
+    );
+}
+
+#[test]
+fn select_tab_and_set_badge() {
+    let mut client = AppClient::new(Harness::with_pages(page_map, ())).unwrap();
+    let session = client.connection().session().clone();
+    session.select_tab("/", &PageKey::new("/settings"), Context::Empty);
+    session.set_tab_badge(
+        &PageKey::new("/orders"),
+        Some("5".to_string()),
+        Context::Empty,
+    );
+    session.set_tab_badge(&PageKey::new("/settings"), None, Context::Empty);
+    assert_eq!(Some("/settings".to_string()), session.selected_tab("/"));
+    assert_eq!(Some("5".to_string()), session.tab_badge("/orders"));
+    client.poll().unwrap();
+    client.assert_page("/settings");
+    client.assert_text("5");
+    client.assert_text("1");
+    // Rebuilding the page keeps the user's selection.
+    client.select_tab("Orders").unwrap();
+    session.set_tab_badge(
+        &PageKey::new("/settings"),
+        Some("2".to_string()),
+        Context::Empty,
+    );
+    client.poll().unwrap();
+    client.assert_page("/orders");
+    client.assert_text("2");
+    client.assert_no_text("1");
+    // The server can select a tab again after the user switches tabs.
+    session.select_tab("/", &PageKey::new("/settings"), Context::Empty);
+    client.poll().unwrap();
+    client.assert_page("/settings");
+}
+
+#[test]
+fn set_badge_rebuilds_tab_pages_with_the_tab() {
+    let mut client = AppClient::new(Harness::with_pages(page_map, ())).unwrap();
+    client.poll().unwrap();
+    let session = client.connection().session().clone();
+    assert!(session.lock_inner().rpc_updates.is_empty());
+    session.set_tab_badge(
+        &PageKey::new("/orders"),
+        Some("5".to_string()),
+        Context::Empty,
+    );
+    assert_eq!(
+        HashSet::from([PendingUpdate::Key("/".to_string())]),
+        session.lock_inner().rpc_updates
+    );
+    session.lock_inner().rpc_updates.clear();
+    session.set_tab_badge(
+        &PageKey::new("/order"),
+        Some("1".to_string()),
+        Context::Empty,
+    );
+    assert!(session.lock_inner().rpc_updates.is_empty());
+}
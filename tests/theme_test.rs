@@ -0,0 +1,171 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::data::Context;
+use applin::internal::{Diagnostic, Page, Widget};
+use applin::preview::page_to_html_with_theme;
+use applin::session::{PageMap, SessionSet};
+use applin::style::{Color, FontWeight, Style, TextStyle, Theme, ThemeColor};
+use applin::testing::Harness;
+use applin::widget::{Button, NavPage, Styled, Text};
+use serde_json::json;
+
+#[test]
+fn color() {
+    assert_eq!("#0a84ff", Color::rgb(10, 132, 255).to_string());
+    assert_eq!("#0a84ff80", Color::rgba(10, 132, 255, 128).to_string());
+    assert_eq!(Ok(Color::rgb(10, 132, 255)), "#0A84FF".parse());
+    assert_eq!(Ok(Color::rgba(0, 0, 0, 0)), "#00000000".parse());
+    for s in ["", "0a84ff", "#0a84f", "#0a84ffa", "#0a84fg", "red"] {
+        assert!(s.parse::<Color>().is_err(), "{s:?}");
+    }
+    assert_eq!(json!("#ff3b30"), json!(Color::rgb(255, 59, 48)));
+    assert!(serde_json::from_value::<Color>(json!("blue")).is_err());
+}
+
+#[test]
+fn theme() {
+    let theme = Theme::empty()
+        .with_color(
+            "brand",
+            ThemeColor::new(Color::rgb(255, 0, 0), Color::rgb(128, 0, 0)),
+        )
+        .with_text_style("headline", TextStyle::new(28, FontWeight::Semibold))
+        .with_radius("card", 12)
+        .with_spacing("gutter", 20);
+    assert_eq!(
+        json!({
+            "colors": {"brand": {"light": "#ff0000", "dark": "#800000"}},
+            "radii": {"card": 12},
+            "spacing": {"gutter": 20},
+            "text-styles": {"headline": {"size": 28, "weight": "semibold"}},
+        }),
+        json!(theme)
+    );
+    assert_eq!(theme, serde_json::from_value(json!(theme)).unwrap());
+    assert_eq!(json!({}), json!(Theme::empty()));
+    let merged = Theme::new().merged(&theme.with_radius("small", 2));
+    assert_eq!(Some(2), merged.radius("small"));
+    assert_eq!(Some(12), merged.radius("card"));
+    assert_eq!(Some(8), merged.radius("medium"));
+    assert_eq!(
+        Some(TextStyle::new(22, FontWeight::Bold)),
+        merged.text_style("title")
+    );
+    assert_eq!(None, merged.color("missing"));
+    assert!(std::panic::catch_unwind(|| Theme::empty().with_radius("Card", 1)).is_err());
+}
+
+#[test]
+fn style() {
+    let widget: Widget = Button::new("b")
+        .with_style(
+            Style::new()
+                .with_background("surface")
+                .with_color("#ffffff")
+                .with_corner_radius("medium")
+                .with_padding("small")
+                .with_text_style("caption"),
+        )
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "styled",
+            "style": {
+                "background": "surface",
+                "color": "#ffffff",
+                "corner-radius": "medium",
+                "padding": "small",
+                "text-style": "caption",
+            },
+            "widget": {"typ": "button", "text": "b"},
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+    assert_eq!(
+        Widget::from(Text::new("a")),
+        Styled::wrap(None, Text::new("a"))
+    );
+    assert!(std::panic::catch_unwind(|| Style::new().with_color("#12")).is_err());
+    assert!(std::panic::catch_unwind(|| Style::new().with_padding("Large")).is_err());
+    let widget: Widget = serde_json::from_value(json!({
+        "typ": "column",
+        "widgets": [{
+            "typ": "styled",
+            "style": {"color": "#1234", "padding": "x y"},
+            "widget": {"typ": "empty"},
+        }],
+    }))
+    .unwrap();
+    assert_eq!(
+        vec![
+            Diagnostic::new(
+                "/widgets/0",
+                "style color: expected a color like #0a84ff, got \"#1234\""
+            ),
+            Diagnostic::new(
+                "/widgets/0",
+                "style padding: expected a theme token name, got \"x y\""
+            ),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn page_theme() {
+    let page: Page = NavPage::new(
+        "Home",
+        Text::new("hi").with_style(Style::new().with_color("brand").with_padding("large")),
+    )
+    .with_theme(Theme::empty().with_color("brand", Color::rgb(1, 2, 3)))
+    .into();
+    assert_eq!(
+        json!({"brand": {"light": "#010203", "dark": "#010203"}}),
+        page.to_value()["theme"]["colors"]
+    );
+    assert!(NavPage::new("Home", Text::new("hi"))
+        .to_page()
+        .to_value()
+        .get("theme")
+        .is_none());
+    let session_theme = Theme::empty().with_spacing("large", 40);
+    let html = page_to_html_with_theme(&page, Some(&session_theme), None);
+    for part in [
+        "--color-brand: #010203;",
+        "--color-accent: #007aff;",
+        "--spacing-large: 40px;",
+        "--text-title-size: 22px; --text-title-weight: 700;",
+        "<div class=\"styled\" style=\"color: var(--color-brand); padding: var(--spacing-large); \">",
+    ] {
+        assert!(html.contains(part), "missing {part:?} in {html}");
+    }
+}
+
+#[test]
+fn session_theme() {
+    let sessions: SessionSet<()> = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| Ok(PageMap::new().with_static_page("/", NavPage::new("Home", Text::new("hi")))),
+        (),
+    );
+    let harness = Harness::new(session.clone());
+    assert_eq!(None, harness.poll().unwrap().theme);
+    let theme = Theme::empty().with_radius("card", 12);
+    session.set_theme(Some(theme.clone()), Context::Empty);
+    assert_eq!(Some(theme.clone()), session.theme());
+    assert_eq!(Some(theme.clone()), harness.poll().unwrap().theme);
+    // Every poll includes the theme, so reloaded clients get it.
+    assert_eq!(Some(theme.clone()), harness.poll().unwrap().theme);
+    // Connecting a stream sends the theme.
+    harness.stream().unwrap();
+    let events = harness.events().unwrap();
+    assert_eq!(Some(theme.clone()), events[0].theme);
+    // Setting the same theme sends nothing.
+    session.set_theme(Some(theme), Context::Empty);
+    assert!(harness.events().unwrap().iter().all(|u| u.theme.is_none()));
+    // Removing the theme sends an empty theme.
+    session.set_theme(None, Context::Empty);
+    let events = harness.events().unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!(Some(Theme::empty()), events[0].theme);
+}
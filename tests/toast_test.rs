@@ -0,0 +1,107 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::action::{push, rpc};
+use applin::data::{Context, Rebuilder};
+use applin::internal::Toast;
+use applin::session::{ApplinSession, PageKey, PageMap};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{Button, Column, NavPage, SheetDetent, SheetModal, Text};
+use serde_json::json;
+use servlin::{Request, Response};
+use std::error::Error;
+use std::sync::Arc;
+
+#[allow(clippy::unnecessary_wraps)]
+fn page_map(_rebuilder: Rebuilder<()>) -> Result<PageMap<()>, Box<dyn Error>> {
+    let mut keys = PageMap::new();
+    let filters = keys.add_static_page(
+        "/filters",
+        SheetModal::new("Filters", Text::new("in stock only"))
+            .with_detent(SheetDetent::Medium)
+            .with_detent(SheetDetent::Large),
+    );
+    keys.add_static_page(
+        "/",
+        NavPage::new(
+            "Orders",
+            Column::new((
+                Button::new("Filter").with_action(push(&filters)),
+                Button::new("Delete").with_action(rpc("/delete")),
+            )),
+        ),
+    );
+    Ok(keys)
+}
+
+fn handle(session: &Arc<ApplinSession<()>>, req: &Request) -> Result<Response, Response> {
+    match req.url.path() {
+        "/delete" => session.show_toast(
+            Toast::new("Deleted order")
+                .with_button("Undo", [rpc("/undo")])
+                .with_seconds(10),
+            session.rpc_context(),
+        ),
+        "/undo" => session.show_page(&PageKey::new("/filters"), session.rpc_context()),
+        _ => return Ok(Response::not_found_404()),
+    }
+    session.rpc_response()
+}
+
+#[test]
+fn sheet() {
+    let mut client = AppClient::new(Harness::with_pages(page_map, ())).unwrap();
+    client.tap("Filter").unwrap();
+    client.assert_page("/filters");
+    client.assert_text("in stock only");
+    client.tap_back().unwrap();
+    client.assert_page("/");
+}
+
+#[test]
+fn toast_from_rpc() {
+    let harness = Harness::with_pages(page_map, ()).with_session_handler(handle);
+    let mut client = AppClient::new(harness).unwrap();
+    assert!(client.toasts().is_empty());
+    client.tap("Delete").unwrap();
+    assert_eq!(
+        [Toast::new("Deleted order")
+            .with_button("Undo", [rpc("/undo")])
+            .with_seconds(10)],
+        client.toasts()
+    );
+    client.assert_page("/");
+    // The undo RPC makes the client push the sheet.
+    client.tap_toast("Undo").unwrap();
+    assert!(client.toasts().is_empty());
+    client.assert_page("/filters");
+    assert_eq!(
+        Err("no toast has button \"Undo\"".to_string()),
+        client.tap_toast("Undo")
+    );
+}
+
+#[test]
+fn toast_over_stream() {
+    let harness = Harness::with_pages(page_map, ());
+    harness.stream().unwrap();
+    harness.events().unwrap();
+    let session = harness.session();
+    session.show_toast(Toast::new("Shipped"), Context::Empty);
+    session.show_page(&PageKey::new("/filters"), Context::Empty);
+    let updates = harness.events().unwrap();
+    assert_eq!(1, updates.len());
+    assert_eq!(vec![Toast::new("Shipped")], updates[0].toasts);
+    assert_eq!(Some("/filters".to_string()), updates[0].push);
+    assert!(harness.events().unwrap().is_empty());
+}
+
+#[test]
+fn toast_serialize() {
+    let toast = Toast::new("Saved");
+    assert_eq!(json!({"text": "Saved"}), toast.to_value());
+    let toast = toast.with_button("Undo", [rpc("/undo")]).with_seconds(5);
+    assert_eq!(
+        json!({"text": "Saved", "button-text": "Undo", "actions": ["rpc:/undo"], "seconds": 5}),
+        toast.to_value()
+    );
+    assert_eq!(toast, serde_json::from_value(toast.to_value()).unwrap());
+}
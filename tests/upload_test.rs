@@ -0,0 +1,95 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::internal::Action;
+use applin::upload::UploadHandler;
+use std::io::Read;
+use temp_dir::TempDir;
+
+fn sha256_hex(reader: impl Read) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let handler = UploadHandler::new(temp_dir.path());
+    let upload = handler.save("text/plain", reader).ok().unwrap();
+    upload.sha256().to_string()
+}
+
+#[test]
+fn sha256() {
+    assert_eq!(
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        sha256_hex(&b""[..])
+    );
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        sha256_hex(&b"abc"[..])
+    );
+    assert_eq!(
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        sha256_hex(&b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"[..])
+    );
+    assert_eq!(
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+        sha256_hex(std::io::repeat(b'a').take(1_000_000))
+    );
+}
+
+#[test]
+fn accepts() {
+    let handler = UploadHandler::new(".");
+    assert!(handler.accepts("anything/else"));
+    let handler = handler.with_accepted_types(["image/*", "application/pdf"]);
+    assert!(handler.accepts("image/png"));
+    assert!(handler.accepts("IMAGE/JPEG"));
+    assert!(handler.accepts("application/pdf; charset=binary"));
+    assert!(!handler.accepts("application/json"));
+    assert!(!handler.accepts("imagex/png"));
+    assert!(!handler.accepts(""));
+    assert_eq!(
+        Action::ChooseFile {
+            accepted_types: vec!["image/*".to_string(), "application/pdf".to_string()],
+            max_bytes: 10 * 1024 * 1024,
+            upload_url: "/upload".to_string(),
+        },
+        handler.choose_file_action("/upload")
+    );
+}
+
+#[test]
+fn save() {
+    let temp_dir = TempDir::new().unwrap();
+    let handler = UploadHandler::new(temp_dir.path())
+        .with_accepted_types(["text/plain"])
+        .with_max_bytes(3);
+    let upload = handler.save("text/plain", &b"abc"[..]).ok().unwrap();
+    assert_eq!("text/plain", upload.content_type());
+    assert_eq!(3, upload.len());
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        upload.sha256()
+    );
+    assert_eq!(b"abc".to_vec(), upload.read_to_vec().unwrap());
+    assert!(upload.path().starts_with(temp_dir.path()));
+    // Drop deletes the file.
+    let path = upload.path().to_path_buf();
+    drop(upload);
+    assert!(!path.exists());
+    // Persist keeps the file.
+    let upload = handler.save("text/plain", &b"ab"[..]).ok().unwrap();
+    let dest = temp_dir.child("kept.txt");
+    upload.persist(&dest).unwrap();
+    assert_eq!(b"ab".to_vec(), std::fs::read(&dest).unwrap());
+    // Limits
+    assert_eq!(
+        413,
+        handler.save("text/plain", &b"abcd"[..]).err().unwrap().code
+    );
+    assert_eq!(
+        415,
+        handler.save("image/png", &b"a"[..]).err().unwrap().code
+    );
+    assert_eq!(
+        vec![dest],
+        std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,523 @@
+use applin::action::{choose_file, confirm, pop, push, refresh, rpc_branch};
+use applin::internal::{Diagnostic, Page, Tab, Widget};
+use applin::session::PageKey;
+use applin::widget::{
+    AlertModal, BackButton, Button, Checkbox, Column, Date, DatePicker, DateRange, DateRangePicker,
+    Empty, ErrorText, Form, FormButton, FormSection, Frame, GroupedRowTable, Icon, Image,
+    ModalButton, MultiSelect, NavButton, NavPage, NumberField, NumberRange, Padding, Picker,
+    ProgressBar, Scroll, SheetDetent, SheetModal, Stepper, TabPage, Text, Textfield, Time,
+    TimePicker,
+};
+use serde_json::json;
+
+#[test]
+fn valid_page() {
+    let page: Page = NavPage::new(
+        "Page1",
+        Scroll::new(Column::new((
+            Form::new(FormSection::new().with_widgets((
+                Textfield::new("name").with_min_chars(1).with_max_chars(10),
+                Checkbox::new("agree", "Agree"),
+            ))),
+            GroupedRowTable::new()
+                .with_row_group(((Text::new("A1"), Text::new("B1")), (Text::new("A2"), None))),
+        ))),
+    )
+    .with_start(BackButton::new().with_action(pop()))
+    .into();
+    assert_eq!(Vec::<Diagnostic>::new(), page.validate());
+    let page: Page = AlertModal::new("Alert1")
+        .with_ok()
+        .with_widget(ModalButton::cancel())
+        .into();
+    assert_eq!(Vec::<Diagnostic>::new(), page.validate());
+}
+
+#[test]
+fn grouped_row_table_columns() {
+    let widget: Widget = GroupedRowTable::new()
+        .with_row_group(((Text::new("A1"), Text::new("B1")),))
+        .with_row_group((
+            (Text::new("A2"), Text::new("B2")),
+            (Text::new("A3"), Text::new("B3"), Text::new("C3")),
+        ))
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/row-groups/1/1",
+            "row has 3 columns, expected 2"
+        )],
+        widget.validate()
+    );
+}
+
+#[test]
+fn form_contents() {
+    let widget: Widget = Form::new((
+        Form::new(()),
+        Scroll::new(Text::new("t1")),
+        FormSection::new().with_widget(BackButton::new()),
+        ModalButton::new("b1"),
+    ))
+    .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/widgets/0", "form is not allowed inside another form"),
+            Diagnostic::new("/widgets/1", "scroll is not allowed inside a form"),
+            Diagnostic::new(
+                "/widgets/2/widgets/0",
+                "back-button is only allowed in the nav-page `start`"
+            ),
+            Diagnostic::new(
+                "/widgets/3",
+                "modal-button is only allowed in alert and drawer modals"
+            ),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn form_allowed_widgets() {
+    let widget: Widget = Form::new((
+        FormSection::new().with_widgets((
+            Textfield::new("name"),
+            Checkbox::new("agree", "Agree"),
+            Picker::new("color").with_option("r", "Red"),
+            DatePicker::new("day"),
+            Stepper::new("guests", NumberRange::new()),
+        )),
+        Text::new("t1"),
+        ErrorText::new("e1"),
+        Empty::new(),
+        FormButton::new("Save"),
+    ))
+    .into();
+    assert_eq!(Vec::<Diagnostic>::new(), widget.validate());
+}
+
+#[test]
+fn form_disallowed_widgets() {
+    let widget: Widget = Form::new((
+        NavButton::new("n1"),
+        FormSection::new().with_widget(Image::new(1.0, "/a.png")),
+        Column::new(Form::new(())),
+    ))
+    .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/widgets/0", "nav-button is not allowed inside a form"),
+            Diagnostic::new("/widgets/1/widgets/0", "image is not allowed inside a form"),
+            Diagnostic::new(
+                "/widgets/2/widgets/0",
+                "form is not allowed inside another form"
+            ),
+            Diagnostic::new("/widgets/2", "column is not allowed inside a form"),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn form_button_outside_form() {
+    let widget: Widget = Column::new((
+        FormButton::new("b1"),
+        Form::new(FormSection::new().with_widget(FormButton::new("b2"))),
+    ))
+    .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/widgets/0",
+            "form-button is only allowed inside a form"
+        )],
+        widget.validate()
+    );
+}
+
+#[test]
+fn modal_contents() {
+    let page: Page = AlertModal::new("Alert1")
+        .with_widget(Text::new("t1"))
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/widgets/0",
+            "alert and drawer modals may contain only modal-button widgets"
+        )],
+        page.validate()
+    );
+}
+
+#[test]
+fn textfield_min_max_chars() {
+    let widget: Widget = Textfield::new("name")
+        .with_min_chars(5)
+        .with_max_chars(4)
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "",
+            "textfield min-chars 5 is greater than max-chars 4"
+        )],
+        widget.validate()
+    );
+}
+
+#[test]
+fn duplicate_var() {
+    let page: Page = NavPage::new(
+        "Page1",
+        Column::new((
+            Textfield::new("name"),
+            Form::new(Checkbox::new("name", "Name")),
+        )),
+    )
+    .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/widget/widgets/1/widgets/0",
+            "var \"name\" is also used by \"/widget/widgets/0\""
+        )],
+        page.validate()
+    );
+}
+
+#[test]
+fn empty_action_argument() {
+    let page: Page = NavPage::new(
+        "Page1",
+        ModalButton::new("b1").with_action(push(&PageKey::new(""))),
+    )
+    .into();
+    let diagnostics = page.validate();
+    assert_eq!(
+        vec![
+            Diagnostic::new(
+                "/widget",
+                "modal-button is only allowed in alert and drawer modals"
+            ),
+            Diagnostic::new(
+                "/widget/actions/0",
+                "action \"push:\" has an empty argument"
+            ),
+        ],
+        diagnostics
+    );
+    assert_eq!(
+        "\"/widget/actions/0\": action \"push:\" has an empty argument",
+        diagnostics[1].to_string()
+    );
+}
+
+#[test]
+fn choose_file_max_bytes() {
+    let widget: Widget = Button::new("Upload")
+        .with_action(choose_file("/upload", ["text/plain"], 0))
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/actions/0",
+            "choose-file max-bytes must be positive"
+        )],
+        widget.validate()
+    );
+}
+
+#[test]
+fn nested_actions() {
+    let widget: Widget = Button::new("Delete")
+        .with_action(confirm(
+            "",
+            "",
+            [rpc_branch("", [push(&PageKey::new(""))], [pop()])],
+        ))
+        .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/actions/0", "confirm title is empty"),
+            Diagnostic::new("/actions/0/then/0", "rpc url is empty"),
+            Diagnostic::new(
+                "/actions/0/then/0/on-success/0",
+                "action \"push:\" has an empty argument"
+            ),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn choice_options() {
+    let widget: Widget = Picker::new("p").with_initial("x").into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("", "options is empty"),
+            Diagnostic::new("", "initial id \"x\" is not an option id"),
+        ],
+        widget.validate()
+    );
+    let widget: Widget = MultiSelect::new("m")
+        .with_options([("a", "A"), ("a", "A2"), ("", "Empty")])
+        .with_initial(["a"])
+        .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/options/1", "option id \"a\" is used by another option"),
+            Diagnostic::new("/options/2", "option id is empty"),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn date_picker_bounds() {
+    let widget: Widget = DatePicker::new("d")
+        .with_initial(Date::new(2024, 1, 1))
+        .with_min(Date::new(2024, 2, 1))
+        .with_max(Date::new(2024, 1, 31))
+        .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("", "min-date 2024-02-01 is after max-date 2024-01-31"),
+            Diagnostic::new("", "initial date 2024-01-01 is out of bounds"),
+        ],
+        widget.validate()
+    );
+    let widget: Widget = TimePicker::new("t")
+        .with_initial(Time::new(18, 0))
+        .with_max(Time::new(17, 0))
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new("", "initial time 18:00 is out of bounds")],
+        widget.validate()
+    );
+    let widget: Widget = DateRangePicker::new("r")
+        .with_initial(DateRange::new(Date::new(2024, 7, 1), Date::new(2024, 7, 4)))
+        .with_min(Date::new(2024, 7, 1))
+        .with_max(Date::new(2024, 7, 4))
+        .into();
+    assert_eq!(Vec::<Diagnostic>::new(), widget.validate());
+}
+
+#[test]
+fn number_range() {
+    let widget: Widget = Stepper::new(
+        "s",
+        NumberRange::new()
+            .with_min(2.0)
+            .with_max(1.0)
+            .with_step(0.5)
+            .with_integer(),
+    )
+    .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("", "min 2 is greater than max 1"),
+            Diagnostic::new("", "step must be a whole number"),
+        ],
+        widget.validate()
+    );
+    let widget: Widget = NumberField::new("n", NumberRange::new().with_max(10.0))
+        .with_initial(11.0)
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new("", "initial-number 11 is out of bounds")],
+        widget.validate()
+    );
+    let widget: Widget = serde_json::from_value(json!({
+        "typ": "slider",
+        "max": 1.0,
+        "min": 0.0,
+        "step": 0.0,
+        "var": "s",
+    }))
+    .unwrap();
+    assert_eq!(
+        vec![Diagnostic::new("", "step must be positive")],
+        widget.validate()
+    );
+}
+
+#[test]
+fn frame_sizes() {
+    let widget: Widget = Frame::new(Text::new("a"))
+        .with_min_width(10)
+        .with_max_width(5)
+        .with_min_height(3)
+        .with_max_height(3)
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "",
+            "frame min-width is greater than max-width"
+        )],
+        widget.validate()
+    );
+    let widget: Widget = Padding::new(
+        1,
+        Frame::new(Text::new("a")).with_height(0).with_min_height(1),
+    )
+    .into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/widget",
+            "frame min-height is greater than max-height"
+        )],
+        widget.validate()
+    );
+}
+
+#[test]
+fn icon_names() {
+    let widget: Widget = Column::new((
+        Icon::new("star"),
+        Icon::new("stra"),
+        Button::new("a").with_icon("nope"),
+        Form::new(FormButton::new("b").with_icon("check")),
+        NavButton::new("c").with_icon(""),
+    ))
+    .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new(
+                "/widgets/1",
+                "unknown icon \"stra\", expected a name from icon set version 1"
+            ),
+            Diagnostic::new(
+                "/widgets/2",
+                "unknown icon \"nope\", expected a name from icon set version 1"
+            ),
+            Diagnostic::new(
+                "/widgets/4",
+                "unknown icon \"\", expected a name from icon set version 1"
+            ),
+        ],
+        widget.validate()
+    );
+    let page: Page =
+        TabPage::new("t", [Tab::new("A", &PageKey::new("/a")).with_icon("gears")]).into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "/tabs/0",
+            "unknown icon \"gears\", expected a name from icon set version 1"
+        )],
+        page.validate()
+    );
+}
+
+#[test]
+fn rich_text_spans() {
+    let mut widget: Widget = serde_json::from_value(json!({
+        "typ": "rich-text",
+        "spans": [
+            {"text": "a", "color": "not a color"},
+            {"spans": [{}, {"text": "b"}]},
+        ],
+    }))
+    .unwrap();
+    if let Widget::RichTextVariant { spans } = &mut widget {
+        spans[1].spans[1].actions.push(push(&PageKey::new("")));
+    }
+    assert_eq!(
+        vec![
+            Diagnostic::new(
+                "/spans/0",
+                "span color: expected a theme color name or a color like #0a84ff, got \"not a color\""
+            ),
+            Diagnostic::new("/spans/1/spans/0", "span has no text"),
+            Diagnostic::new(
+                "/spans/1/spans/1/actions/0",
+                "action \"push:\" has an empty argument"
+            ),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn sheet_detents() {
+    let page: Page = SheetModal::new("t", Text::new("a"))
+        .with_detent(SheetDetent::Large)
+        .with_detent(SheetDetent::Height(0))
+        .with_detent(SheetDetent::Large)
+        .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/detents/1", "sheet detent height is zero"),
+            Diagnostic::new("/detents/2", "sheet detent is repeated"),
+        ],
+        page.validate()
+    );
+}
+
+#[test]
+fn tab_page_tabs() {
+    let page: Page = TabPage::new("Shop", [])
+        .with_selected(&PageKey::new("/a"))
+        .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/tabs", "tab-page has no tabs"),
+            Diagnostic::new("/selected", "selected tab \"/a\" is not a tab"),
+        ],
+        page.validate()
+    );
+    let page: Page = TabPage::new(
+        "Shop",
+        [
+            Tab::new("A", &PageKey::new("/a")),
+            Tab::new("B", &PageKey::new("")),
+            Tab::new("C", &PageKey::new("/a")),
+        ],
+    )
+    .into();
+    assert_eq!(
+        vec![
+            Diagnostic::new("/tabs/1", "tab page-key is empty"),
+            Diagnostic::new("/tabs/2", "tab page-key \"/a\" is repeated"),
+        ],
+        page.validate()
+    );
+}
+
+#[test]
+fn lazy_list_url() {
+    let widget: Widget = serde_json::from_value(json!({
+        "typ": "lazy-list",
+        "url": "",
+        "widgets": [{"typ": "image", "aspect-ratio": 1.0, "disposition": "fit", "url": ""}],
+    }))
+    .unwrap();
+    assert_eq!(
+        vec![
+            Diagnostic::new("", "lazy-list url is empty"),
+            Diagnostic::new("/widgets/0", "image url is empty"),
+        ],
+        widget.validate()
+    );
+}
+
+#[test]
+fn progress_bar_percent() {
+    let widget: Widget = ProgressBar::new().with_percent(101).into();
+    assert_eq!(
+        vec![Diagnostic::new(
+            "",
+            "progress-bar percent must be at most 100"
+        )],
+        widget.validate()
+    );
+    assert!(Widget::from(ProgressBar::new().with_percent(100))
+        .validate()
+        .is_empty());
+}
+
+#[test]
+fn pull_to_refresh_url() {
+    let page: Page = NavPage::new("t", Button::new("b").with_action(refresh("/")))
+        .with_pull_to_refresh("")
+        .into();
+    assert_eq!(
+        vec![Diagnostic::new("", "pull-to-refresh is empty")],
+        page.validate()
+    );
+}
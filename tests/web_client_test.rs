@@ -0,0 +1,137 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::session::{PageMap, SessionSet};
+use applin::style::ICON_NAMES;
+use applin::testing::Harness;
+use applin::web_client::WebClient;
+use applin::widget::{NavPage, Text};
+use serde_json::Value;
+use servlin::{ContentType, Request, Response, ResponseBody};
+
+fn request(path: &str) -> Request {
+    let sessions = SessionSet::without_executor();
+    let session = sessions.new_session(
+        |_| Ok(PageMap::new().with_static_page("/", NavPage::new("Home", Text::new("hi")))),
+        (),
+    );
+    Harness::new(session).request("GET", path, &Value::Null)
+}
+
+fn body_string(response: Response) -> String {
+    match response.body {
+        ResponseBody::Vec(bytes) => String::from_utf8(bytes).unwrap(),
+        ResponseBody::StaticStr(s) => s.to_string(),
+        _ => panic!("unexpected body"),
+    }
+}
+
+#[test]
+fn index() {
+    let web_client = WebClient::new("/web/")
+        .with_poll_path("/poll")
+        .without_stream();
+    assert_eq!("/web", web_client.prefix());
+    for path in ["/web", "/web/"] {
+        let response = web_client.response(&request(path)).ok().unwrap();
+        assert_eq!(200, response.code);
+        assert_eq!(ContentType::Html, response.content_type);
+        let body = body_string(response);
+        assert!(
+            body.contains(
+                "<script src=\"/web/applin.js\" data-poll-path=\"/poll\" data-stream-path=\"\" "
+            ),
+            "{body}"
+        );
+        assert!(body.contains("data-refresh-path=\"/refresh\""), "{body}");
+        assert!(body.contains("href=\"/web/applin.css\""), "{body}");
+        assert!(
+            body.contains("data-default-theme=\"{&quot;colors&quot;:{&quot;accent&quot;:"),
+            "{body}"
+        );
+    }
+}
+
+#[test]
+fn assets() {
+    let web_client = WebClient::new("/web");
+    let response = web_client
+        .response(&request("/web/applin.js"))
+        .ok()
+        .unwrap();
+    assert_eq!(200, response.code);
+    assert_eq!(ContentType::JavaScript, response.content_type);
+    let js = body_string(response);
+    for typ in [
+        "back-button",
+        "button",
+        "checkbox",
+        "column",
+        "date-picker",
+        "date-range-picker",
+        "empty",
+        "error-details",
+        "error-text",
+        "form",
+        "form-button",
+        "form-section",
+        "frame",
+        "grouped-row-table",
+        "icon",
+        "image",
+        "lazy-list",
+        "modal-button",
+        "multi-select",
+        "nav-button",
+        "number-field",
+        "padding",
+        "picker",
+        "progress-bar",
+        "radio-group",
+        "rich-text",
+        "row",
+        "scroll",
+        "slider",
+        "spacer",
+        "stack",
+        "stepper",
+        "styled",
+        "text",
+        "textfield",
+        "time-picker",
+    ] {
+        assert!(
+            js.contains(&format!("\n    \"{typ}\": ")) || js.contains(&format!("\n    {typ}: ")),
+            "missing renderer for {typ}"
+        );
+    }
+    for name in ICON_NAMES {
+        assert!(
+            js.contains(&format!("\n    \"{name}\": \""))
+                || js.contains(&format!("\n    {name}: \"")),
+            "missing icon {name}"
+        );
+    }
+    let response = web_client
+        .response(&request("/web/applin.css"))
+        .ok()
+        .unwrap();
+    assert_eq!(200, response.code);
+    assert_eq!(ContentType::Css, response.content_type);
+    assert!(body_string(response).contains(".nav-bar"));
+}
+
+#[test]
+fn errors() {
+    let web_client = WebClient::new("/web");
+    assert_eq!(
+        404,
+        web_client
+            .response(&request("/web/other"))
+            .err()
+            .unwrap()
+            .code
+    );
+    assert_eq!(
+        400,
+        web_client.response(&request("/other")).err().unwrap().code
+    );
+}
@@ -1,5 +1,10 @@
-use applin::internal::{Action, Widget};
-use applin::widget::HAlignment;
+use applin::internal::{Action, Chunk, Widget};
+use applin::widget::{
+    Button, Choice, Date, DatePicker, DateRange, DateRangePicker, FormButton, Frame, HAlignment,
+    Icon, LazyList, MultiSelect, NavButton, NumberField, NumberRange, Padding, Picker, ProgressBar,
+    RadioGroup, RichText, Row, Slider, Spacer, Span, Stack, Stepper, Text, Time, TimePicker,
+    VAlignment,
+};
 use serde_json::{json, Value};
 
 #[test]
@@ -70,6 +75,7 @@ fn widget_button_serialize() {
         serde_json::to_string(&Widget::ButtonVariant {
             text: String::new(),
             actions: Vec::new(),
+            icon: None,
         })
         .unwrap(),
         r#"{"typ":"button","text":""}"#
@@ -78,6 +84,7 @@ fn widget_button_serialize() {
         serde_json::to_string(&Widget::ButtonVariant {
             text: "abc".to_string(),
             actions: vec![Action::Pop, Action::Logout],
+            icon: None,
         })
         .unwrap(),
         r#"{"typ":"button","text":"abc","actions":["pop","logout"]}"#
@@ -91,6 +98,7 @@ fn widget_button_deserialize() {
         Widget::ButtonVariant {
             text: String::new(),
             actions: Vec::new(),
+            icon: None,
         }
     );
     assert_eq!(
@@ -101,6 +109,7 @@ fn widget_button_deserialize() {
         Widget::ButtonVariant {
             text: "abc".to_string(),
             actions: vec![Action::Pop, Action::Logout],
+            icon: None,
         }
     );
 }
@@ -185,6 +194,7 @@ fn widget_nav_button_serialize() {
             sub_text: None,
             text: String::new(),
             badge_text: None,
+            icon: None,
         })
         .unwrap(),
         r#"{"typ":"nav-button","text":""}"#
@@ -196,6 +206,7 @@ fn widget_nav_button_serialize() {
             sub_text: Some("s1".to_string()),
             text: "t1".to_string(),
             badge_text: None,
+            icon: None,
         })
         .unwrap(),
         r#"{"typ":"nav-button","text":"t1","sub-text":"s1","photo-url":"/p1","actions":["pop","logout"]}"#
@@ -214,6 +225,7 @@ fn widget_nav_button_deserialize() {
             sub_text: None,
             text: String::new(),
             badge_text: None,
+            icon: None,
         }
     );
     assert_eq!(
@@ -227,6 +239,7 @@ fn widget_nav_button_deserialize() {
             sub_text: Some("s1".to_string()),
             text: "t1".to_string(),
             badge_text: None,
+            icon: None,
         }
     );
 }
@@ -369,3 +382,396 @@ fn widget_text_deserialize() {
         }
     );
 }
+
+#[test]
+fn widget_textfield_deserialize() {
+    let widget = applin::widget::Textfield::new("var1").to_widget();
+    assert_eq!(
+        serde_json::from_value::<Widget>(widget.to_value()).unwrap(),
+        widget
+    );
+}
+
+#[test]
+fn widget_radio_group_serialize() {
+    let widget: Widget = RadioGroup::new("size")
+        .with_label("Size")
+        .with_option("s", "Small")
+        .with_options([("m", "Medium"), ("l", "Large")])
+        .with_initial("m")
+        .with_rpc("/size")
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "radio-group",
+            "var": "size",
+            "label": "Size",
+            "options": [
+                {"id": "s", "text": "Small"},
+                {"id": "m", "text": "Medium"},
+                {"id": "l", "text": "Large"},
+            ],
+            "initial-id": "m",
+            "rpc": "/size",
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+}
+
+#[test]
+fn widget_picker_serialize() {
+    assert_eq!(
+        json!({"typ": "picker", "var": "color", "options": [{"id": "r", "text": "Red"}]}),
+        Picker::new("color")
+            .with_option("r", "Red")
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_multi_select_serialize() {
+    assert_eq!(
+        json!({
+            "typ": "multi-select",
+            "var": "toppings",
+            "options": [{"id": "a", "text": "A"}, {"id": "b", "text": "B"}],
+            "initial-ids": ["b"],
+        }),
+        MultiSelect::new("toppings")
+            .with_options([Choice::new("a", "A"), Choice::new("b", "B")])
+            .with_initial(["b"])
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_date_picker_serialize() {
+    let widget: Widget = DatePicker::new("birthday")
+        .with_label("Birthday")
+        .with_initial(Date::new(2000, 1, 2))
+        .with_min(Date::new(1900, 1, 1))
+        .with_max(Date::new(2024, 12, 31))
+        .with_rpc("/birthday")
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "date-picker",
+            "var": "birthday",
+            "label": "Birthday",
+            "initial-date": "2000-01-02",
+            "min-date": "1900-01-01",
+            "max-date": "2024-12-31",
+            "rpc": "/birthday",
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+    serde_json::from_value::<Widget>(
+        json!({"typ": "date-picker", "var": "d", "initial-date": "2024-13-01"}),
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn widget_time_picker_serialize() {
+    assert_eq!(
+        json!({"typ": "time-picker", "var": "alarm", "initial-time": "07:30"}),
+        TimePicker::new("alarm")
+            .with_initial(Time::new(7, 30))
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_date_range_picker_serialize() {
+    assert_eq!(
+        json!({
+            "typ": "date-range-picker",
+            "var": "trip",
+            "initial-range": "2024-07-01/2024-07-04",
+            "min-date": "2024-01-01",
+        }),
+        DateRangePicker::new("trip")
+            .with_initial(DateRange::new(Date::new(2024, 7, 1), Date::new(2024, 7, 4)))
+            .with_min(Date::new(2024, 1, 1))
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_slider_serialize() {
+    let widget: Widget = Slider::new(
+        "volume",
+        NumberRange::new()
+            .with_min(0.0)
+            .with_max(10.0)
+            .with_step(0.5),
+    )
+    .with_label("Volume")
+    .with_initial(5.0)
+    .with_units("dB")
+    .with_rpc("/volume")
+    .into();
+    assert_eq!(
+        json!({
+            "typ": "slider",
+            "var": "volume",
+            "label": "Volume",
+            "min": 0.0,
+            "max": 10.0,
+            "step": 0.5,
+            "initial-number": 5.0,
+            "units": "dB",
+            "rpc": "/volume",
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+}
+
+#[test]
+fn widget_stepper_serialize() {
+    assert_eq!(
+        json!({"typ": "stepper", "var": "guests", "integer": true, "min": 1.0}),
+        Stepper::new("guests", NumberRange::new().with_min(1.0).with_integer())
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_number_field_serialize() {
+    assert_eq!(
+        json!({"typ": "number-field", "var": "weight", "units": "kg"}),
+        NumberField::new("weight", NumberRange::new())
+            .with_units("kg")
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_row_serialize() {
+    let widget: Widget = Row::new((
+        Frame::new(Text::new("a")).with_width(40),
+        Spacer::new(),
+        Padding::new(4, Text::new("b")).with_start(8),
+    ))
+    .with_alignment(VAlignment::Center)
+    .with_spacing(2)
+    .into();
+    assert_eq!(
+        json!({
+            "typ": "row",
+            "spacing": 2,
+            "v-alignment": "center",
+            "widgets": [
+                {
+                    "typ": "frame",
+                    "max-width": 40,
+                    "min-width": 40,
+                    "widget": {"typ": "text", "text": "a"},
+                },
+                {"typ": "spacer"},
+                {
+                    "typ": "padding",
+                    "bottom": 4,
+                    "end": 4,
+                    "start": 8,
+                    "top": 4,
+                    "widget": {"typ": "text", "text": "b"},
+                },
+            ],
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+}
+
+#[test]
+fn widget_row_deserialize() {
+    assert_eq!(
+        Widget::RowVariant {
+            spacing: 0,
+            v_alignment: VAlignment::Top,
+            widgets: Vec::new(),
+        },
+        serde_json::from_value(json!({"typ": "row"})).unwrap()
+    );
+}
+
+#[test]
+fn widget_stack_serialize() {
+    let widget: Widget = Stack::new((Text::new("back"), Text::new("front")))
+        .with_alignment(HAlignment::End, VAlignment::Bottom)
+        .into();
+    assert_eq!(
+        json!({
+            "typ": "stack",
+            "h-alignment": "end",
+            "v-alignment": "bottom",
+            "widgets": [{"typ": "text", "text": "back"}, {"typ": "text", "text": "front"}],
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+}
+
+#[test]
+fn widget_frame_serialize() {
+    assert_eq!(
+        json!({"typ": "frame", "weight": 2, "min-height": 10, "widget": {"typ": "empty"}}),
+        Frame::new(Widget::EmptyVariant)
+            .with_weight(2)
+            .with_min_height(10)
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_spacer_serialize() {
+    assert_eq!(
+        json!({"typ": "spacer", "min-size": 6}),
+        Spacer::new().with_min_size(6).to_widget().to_value()
+    );
+}
+
+#[test]
+fn widget_padding_serialize() {
+    assert_eq!(
+        Widget::PaddingVariant {
+            bottom: 0,
+            end: 3,
+            start: 3,
+            top: 0,
+            widget: Box::new(Widget::EmptyVariant),
+        },
+        Padding::new(0, Widget::EmptyVariant)
+            .with_horizontal(3)
+            .to_widget()
+    );
+}
+
+#[test]
+fn widget_icon_serialize() {
+    let widget: Widget = Icon::new("star").with_size(32).into();
+    assert_eq!(
+        json!({"typ": "icon", "name": "star", "size": 32}),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+    assert_eq!(
+        json!({"typ": "icon", "name": "star"}),
+        Icon::new("star").to_widget().to_value()
+    );
+    assert_eq!(
+        json!({"typ": "button", "text": "Save", "icon": "check"}),
+        Button::new("Save")
+            .with_icon("check")
+            .to_widget()
+            .to_value()
+    );
+    assert_eq!(
+        json!({"typ": "form-button", "text": "Delete", "icon": "trash"}),
+        FormButton::new("Delete")
+            .with_icon("trash")
+            .to_widget()
+            .to_value()
+    );
+    assert_eq!(
+        json!({"typ": "nav-button", "text": "Mail", "icon": "mail"}),
+        NavButton::new("Mail")
+            .with_icon("mail")
+            .to_widget()
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_rich_text_serialize() {
+    let widget: Widget = RichText::new((
+        "Plain ",
+        Span::new("bold").with_bold(),
+        Span::group((
+            "italic ",
+            Span::new("code").with_monospace().with_color("accent"),
+        ))
+        .with_italic(),
+        Span::new("gone").with_strikethrough(),
+    ))
+    .into();
+    assert_eq!(
+        json!({
+            "typ": "rich-text",
+            "spans": [
+                {"text": "Plain "},
+                {"bold": true, "text": "bold"},
+                {
+                    "italic": true,
+                    "spans": [
+                        {"text": "italic "},
+                        {"color": "accent", "monospace": true, "text": "code"},
+                    ],
+                },
+                {"strikethrough": true, "text": "gone"},
+            ],
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+    assert!(std::panic::catch_unwind(|| Span::new("a").with_color("#12")).is_err());
+}
+
+#[test]
+fn widget_lazy_list_serialize() {
+    let widget: Widget = LazyList::new(
+        "/more",
+        Chunk::new((Text::new("a"), Text::new("b"))).with_next_cursor("2"),
+    )
+    .with_spacing(4)
+    .into();
+    assert_eq!(
+        json!({
+            "typ": "lazy-list",
+            "next-cursor": "2",
+            "spacing": 4,
+            "url": "/more",
+            "widgets": [{"typ": "text", "text": "a"}, {"typ": "text", "text": "b"}],
+        }),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+    assert_eq!(
+        json!({"typ": "lazy-list", "url": "/more"}),
+        LazyList::new("/more", Chunk::default())
+            .to_widget()
+            .to_value()
+    );
+    assert_eq!(
+        json!({"next-cursor": "x", "widgets": [{"typ": "empty"}]}),
+        Chunk::new(Widget::EmptyVariant)
+            .with_next_cursor("x")
+            .to_value()
+    );
+}
+
+#[test]
+fn widget_progress_bar_serialize() {
+    let widget: Widget = ProgressBar::new().into();
+    assert_eq!(json!({"typ": "progress-bar"}), widget.to_value());
+    let widget: Widget = ProgressBar::new()
+        .with_percent(40)
+        .with_text("Uploading")
+        .into();
+    assert_eq!(
+        json!({"typ": "progress-bar", "percent": 40, "text": "Uploading"}),
+        widget.to_value()
+    );
+    assert_eq!(widget, serde_json::from_value(widget.to_value()).unwrap());
+}
@@ -0,0 +1,190 @@
+#![allow(clippy::missing_panics_doc)]
+use applin::rules::{FormRules, Rule};
+use applin::session::{PageMap, SessionSet};
+use applin::testing::{AppClient, Harness};
+use applin::widget::{Button, NavPage, Text, Textfield};
+use applin::wizard::{Wizard, WizardStep};
+use serde_json::{json, Map, Value};
+use servlin::{Request, Response};
+use std::sync::{Arc, Mutex};
+
+type Answers = Arc<Mutex<Vec<Map<String, Value>>>>;
+
+fn start() -> (Answers, AppClient<Harness<()>>) {
+    let answers: Answers = Arc::new(Mutex::new(Vec::new()));
+    let answers2 = Arc::clone(&answers);
+    let wizard: Arc<Wizard<()>> = Arc::new(
+        Wizard::new("/signup", move |_session, values| {
+            if values.get("email") == Some(&json!("taken@example.com")) {
+                return Err("That email is taken".to_string());
+            }
+            answers2.lock().unwrap().push(values);
+            Ok(())
+        })
+        .with_done_widget(Text::new("Welcome!"))
+        .with_step(
+            WizardStep::new("Name", |_| Textfield::new("name").into())
+                .with_rules(FormRules::new().with_rule("name", Rule::required())),
+        )
+        .with_step(
+            WizardStep::new("Email", |answers| {
+                Textfield::new("email")
+                    .with_label(format!("Email for {}", answers["name"].as_str().unwrap()))
+                    .into()
+            })
+            .with_rules(FormRules::new().with_rules("email", [Rule::required(), Rule::email()])),
+        ),
+    );
+    let wizard2 = Arc::clone(&wizard);
+    let page_map_fn = move |_| {
+        let mut page_map = PageMap::new();
+        wizard2.add_to(&mut page_map);
+        page_map.add_static_page(
+            "/",
+            NavPage::new(
+                "Home",
+                Button::new("Sign Up").with_actions(wizard2.start_actions()),
+            ),
+        );
+        Ok(page_map)
+    };
+    let sessions: SessionSet<()> = SessionSet::without_executor();
+    let session = sessions.new_session(page_map_fn, ());
+    let harness = Harness::new(session).with_handler(move |req: Request| {
+        let session = sessions.get(&req)?;
+        if req.url.path().starts_with(wizard.key()) {
+            wizard.handle(&session, &req)
+        } else {
+            Ok(Response::not_found_404())
+        }
+    });
+    (answers, AppClient::new(harness).unwrap())
+}
+
+#[test]
+fn steps() {
+    let (answers, mut client) = start();
+    client.tap("Sign Up").unwrap();
+    client.assert_page("/signup");
+    client.assert_text("Name (1/2)");
+    assert_eq!(
+        "rpc \"/signup/next\" rejected",
+        client.tap("Next").unwrap_err()
+    );
+    client.assert_text("Name (1/2)");
+    assert_eq!(
+        Some("Required".to_string()),
+        client.connection().session().field_error("/signup", "name")
+    );
+    client.set_text("name", "Ann").unwrap();
+    client.tap("Next").unwrap();
+    assert_eq!(
+        None,
+        client.connection().session().field_error("/signup", "name")
+    );
+    client.assert_text("Email (2/2)");
+    client.assert_text("Email for Ann");
+    client.tap_back().unwrap();
+    client.assert_page("/signup");
+    client.assert_text("Name (1/2)");
+    client.tap("Next").unwrap();
+    client.set_text("email", "ann").unwrap();
+    assert_eq!(
+        "rpc \"/signup/next\" rejected",
+        client.tap("Done").unwrap_err()
+    );
+    assert_eq!(
+        Some("Enter a valid email address".to_string()),
+        client
+            .connection()
+            .session()
+            .field_error("/signup", "email")
+    );
+    client.set_text("email", "taken@example.com").unwrap();
+    assert!(client
+        .tap("Done")
+        .unwrap_err()
+        .contains("That email is taken"));
+    client.assert_text("Email (2/2)");
+    assert!(answers.lock().unwrap().is_empty());
+    client.set_text("email", "ann@example.com").unwrap();
+    client.tap("Done").unwrap();
+    client.assert_text("Welcome!");
+    assert_eq!(
+        vec![json!({"name": "Ann", "email": "ann@example.com"})],
+        answers
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .map(Value::Object)
+            .collect::<Vec<_>>()
+    );
+    client.tap("OK").unwrap();
+    client.assert_page("/");
+    // Starting again clears the answers.
+    client.tap("Sign Up").unwrap();
+    client.assert_text("Name (1/2)");
+}
+
+#[test]
+fn next_keeps_only_step_vars() {
+    let (answers, client) = start();
+    let connection = client.connection();
+    connection.rpc("/signup/start", &json!({})).unwrap();
+    connection
+        .rpc(
+            "/signup/next",
+            &json!({"name": "Ann", "email": "old@example.com", "other": true}),
+        )
+        .unwrap();
+    connection
+        .rpc(
+            "/signup/next",
+            &json!({"name": "Bob", "email": "ann@example.com"}),
+        )
+        .unwrap();
+    assert_eq!(
+        vec![json!({"name": "Ann", "email": "ann@example.com"})],
+        answers
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .map(Value::Object)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn back_after_done() {
+    let (answers, mut client) = start();
+    client.tap("Sign Up").unwrap();
+    client.set_text("name", "Ann").unwrap();
+    client.tap("Next").unwrap();
+    client.set_text("email", "ann@example.com").unwrap();
+    client.tap("Done").unwrap();
+    assert_eq!(
+        "404 not found",
+        client
+            .connection()
+            .rpc("/signup/back", &json!({}))
+            .unwrap_err()
+            .to_string()
+    );
+    client.assert_text("Welcome!");
+    assert_eq!(1, answers.lock().unwrap().len());
+}
+
+#[test]
+fn unknown_path() {
+    let (_answers, client) = start();
+    assert_eq!(
+        "404 not found",
+        client
+            .connection()
+            .rpc("/signup/other", &json!({}))
+            .unwrap_err()
+            .to_string()
+    );
+}